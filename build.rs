@@ -0,0 +1,32 @@
+#[cfg(feature = "pure-rust")]
+extern crate alloc;
+
+#[cfg(feature = "fixed-point")]
+#[path = "build/fixed.rs"]
+mod fixed;
+#[cfg(feature = "pure-rust")]
+#[path = "build/lut.rs"]
+mod lut;
+#[cfg(feature = "pure-rust")]
+#[allow(dead_code)]
+#[path = "src/lut_codec.rs"]
+mod lut_codec;
+#[cfg(feature = "pure-rust")]
+#[allow(dead_code)]
+#[path = "src/mixbox_lut.rs"]
+mod mixbox_lut;
+#[cfg(feature = "pure-rust")]
+#[allow(dead_code)]
+#[path = "src/polynomial.rs"]
+mod polynomial;
+
+fn main() {
+    #[cfg(feature = "node")]
+    napi_build::setup();
+
+    #[cfg(feature = "pure-rust")]
+    lut::write_lut();
+
+    #[cfg(feature = "fixed-point")]
+    fixed::write_tables();
+}
@@ -0,0 +1,63 @@
+//! Generates the tables of the fixed-point Mixbox model.
+use crate::polynomial::POLYNOMIAL;
+use std::{env, fmt::Write, fs, path::Path};
+
+/// Decodes an encoded sRGB component to linear.
+fn decode(encoded: f64) -> f64 {
+    if encoded <= 0.04045 {
+        encoded / 12.92
+    } else {
+        ((encoded + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Returns `x` in Q16.16.
+fn to_fixed(x: f64) -> i32 {
+    (x * 65536.0).round() as i32
+}
+
+/// Writes the tables to `fixed_tables.rs` in `OUT_DIR`.
+pub fn write_tables() {
+    println!("cargo:rerun-if-changed=build/fixed.rs");
+    println!("cargo:rerun-if-changed=src/polynomial.rs");
+
+    let mut source = String::new();
+
+    writeln!(
+        source,
+        "/// Linear Q16.16 components of the `u8` sRGB components.\n\
+         const DECODE_SRGB_U8: [i32; 256] = {:?};\n",
+        (0..=255u8)
+            .map(|c| to_fixed(decode(c as f64 / 255.0)))
+            .collect::<Vec<_>>(),
+    )
+    .unwrap();
+
+    writeln!(
+        source,
+        "/// The linear Q16.16 components half way between two consecutive \
+         `u8`\n/// sRGB components.\n\
+         const ENCODE_SRGB_U8: [i32; 255] = {:?};\n",
+        (0..255u8)
+            .map(|c| to_fixed(decode((c as f64 + 0.5) / 255.0)))
+            .collect::<Vec<_>>(),
+    )
+    .unwrap();
+
+    writeln!(
+        source,
+        "/// The polynomial of the floating point model in Q16.16.\n\
+         const POLYNOMIAL: [([usize; 3], [i32; 3]); {}] = {:?};",
+        POLYNOMIAL.len(),
+        POLYNOMIAL
+            .iter()
+            .map(|(indices, coefficients)| {
+                (*indices, coefficients.map(|c| to_fixed(c as f64)))
+            })
+            .collect::<Vec<_>>(),
+    )
+    .unwrap();
+
+    let out_dir = env::var_os("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("fixed_tables.rs"), source).unwrap();
+}
@@ -0,0 +1,226 @@
+//! Fits the table mapping RGB to pigment concentrations of the pure Rust
+//! Mixbox model.
+use crate::{
+    lut_codec::{predict, LUT_LEN, MAGIC},
+    mixbox_lut::LUT_RESOLUTION,
+    polynomial::{eval_polynomial, POLYNOMIAL},
+};
+use std::{env, fs, path::Path};
+
+/// The number of descent steps for the first sample of the table, which
+/// has no neighbors to start from.
+const COLD_STEPS: usize = 256;
+/// The number of descent steps for all other starts of a sample.
+const WARM_STEPS: usize = 12;
+/// The weight of the chroma of the residual, relative to its magnitude, in
+/// the error.
+const CHROMA_WEIGHT: f32 = 4.0;
+
+/// Returns the error of the mix of `c` to `rgb` and its gradient with
+/// respect to `c`.
+///
+/// The error is the squared residual plus the weighted squared chroma of the
+/// residual, its deviation from gray. Colors darker than any mix of the
+/// primaries, like black, would otherwise be fitted with the single darkest
+/// primary, phthalo blue, and a large blue residual. Mixed with white they
+/// give a light blue instead of a gray. Penalizing chroma fits them with a
+/// dark mix of the primaries and a gray residual instead.
+fn error_gradient(c: &[f32; 4], rgb: &[f32; 3]) -> (f32, [f32; 4]) {
+    let mix = eval_polynomial(c);
+    let residual = [mix[0] - rgb[0], mix[1] - rgb[1], mix[2] - rgb[2]];
+    let mean = residual.iter().sum::<f32>() / 3.0;
+    let chroma = residual.map(|r| r - mean);
+    // Half the derivative of the error with respect to the residual.
+    let slope: [f32; 3] =
+        core::array::from_fn(|i| residual[i] + CHROMA_WEIGHT * chroma[i]);
+
+    let mut gradient = [0.0; 4];
+    for ([i, j, k], coefficients) in POLYNOMIAL.iter() {
+        let weight: f32 = slope
+            .iter()
+            .zip(coefficients)
+            .map(|(slope, coefficient)| slope * coefficient)
+            .sum();
+        gradient[*i] += 2.0 * weight * c[*j] * c[*k];
+        gradient[*j] += 2.0 * weight * c[*i] * c[*k];
+        gradient[*k] += 2.0 * weight * c[*i] * c[*j];
+    }
+
+    let error = residual
+        .iter()
+        .zip(&chroma)
+        .map(|(r, c)| r * r + CHROMA_WEIGHT * c * c)
+        .sum();
+
+    (error, gradient)
+}
+
+/// Projects `c` onto the probability simplex.
+fn project_to_simplex(c: &mut [f32; 4]) {
+    let mut sorted = *c;
+    sorted.sort_by(|a, b| b.total_cmp(a));
+
+    let mut sum = 0.0;
+    let mut theta = 0.0;
+    for (i, &x) in sorted.iter().enumerate() {
+        sum += x;
+        let t = (sum - 1.0) / (i + 1) as f32;
+        if x - t > 0.0 {
+            theta = t;
+        }
+    }
+
+    c.iter_mut().for_each(|c| *c = (*c - theta).max(0.0));
+}
+
+/// Fits the concentrations `c` to `rgb` with projected gradient descent
+/// and a backtracking line search.
+fn fit(rgb: &[f32; 3], c: &mut [f32; 4], steps: usize) {
+    let mut rate = 0.1;
+    let (mut error, mut gradient) = error_gradient(c, rgb);
+
+    for _ in 0..steps {
+        loop {
+            let mut candidate = *c;
+            candidate
+                .iter_mut()
+                .zip(&gradient)
+                .for_each(|(c, g)| *c -= rate * g);
+            project_to_simplex(&mut candidate);
+
+            let (candidate_error, candidate_gradient) =
+                error_gradient(&candidate, rgb);
+            if candidate_error < error {
+                *c = candidate;
+                error = candidate_error;
+                gradient = candidate_gradient;
+                rate *= 1.5;
+                break;
+            }
+
+            rate *= 0.5;
+            if rate < 1.0e-6 {
+                return;
+            }
+        }
+    }
+}
+
+/// Builds the table mapping RGB to the concentrations of the first three
+/// primaries. The fourth is implied as the concentrations sum to `1`.
+///
+/// Descent only finds a local minimum. Each sample is fitted from every
+/// neighbor fitted so far and from the center of the simplex, keeping the
+/// best fit. Starting from a single neighbor carries a poor local minimum
+/// along the scan, e.g. fitting saturated reds with pure blue. Mixing two
+/// similar colors whose concentrations lie in different minima gives a
+/// color far from both.
+fn build_lut() -> Vec<[f32; 3]> {
+    let n = LUT_RESOLUTION;
+    let scale = 1.0 / (n - 1) as f32;
+    let mut lut: Vec<[f32; 3]> = Vec::with_capacity(LUT_LEN);
+
+    for r in 0..n {
+        for g in 0..n {
+            for b in 0..n {
+                let rgb =
+                    [r as f32 * scale, g as f32 * scale, b as f32 * scale];
+
+                let neighbors = [(b, 1), (g, n), (r, n * n)]
+                    .into_iter()
+                    .filter(|(position, _)| 0 < *position)
+                    .map(|(_, stride)| (lut[lut.len() - stride], WARM_STEPS));
+                let center_steps = if lut.is_empty() {
+                    COLD_STEPS
+                } else {
+                    WARM_STEPS
+                };
+
+                let (_, c) = neighbors
+                    .chain([([0.25; 3], center_steps)])
+                    .map(|(start, steps)| {
+                        let mut c = [
+                            start[0],
+                            start[1],
+                            start[2],
+                            1.0 - start[0] - start[1] - start[2],
+                        ];
+                        fit(&rgb, &mut c, steps);
+
+                        (error_gradient(&c, &rgb).0, c)
+                    })
+                    .min_by(|(a, _), (b, _)| a.total_cmp(b))
+                    .unwrap();
+
+                lut.push([c[0], c[1], c[2]]);
+            }
+        }
+    }
+
+    lut
+}
+
+/// Returns the quantized table compressed as described in
+/// [`mixbox_lut`](crate::mixbox_lut).
+fn encode(lut: &[[u16; 3]]) -> Vec<u8> {
+    let mut bytes = MAGIC.to_vec();
+
+    for (index, sample) in lut.iter().enumerate() {
+        let prediction = predict(&lut[..index], index);
+        for (&c, prediction) in sample.iter().zip(prediction) {
+            let residual = c as i32 - prediction;
+            let mut zigzag = ((residual << 1) ^ (residual >> 31)) as u32;
+            while 0x80 <= zigzag {
+                bytes.push(zigzag as u8 | 0x80);
+                zigzag >>= 7;
+            }
+            bytes.push(zigzag as u8);
+        }
+    }
+
+    bytes
+}
+
+/// Fits the table and writes it to `OUT_DIR`, compressed to
+/// `mixbox_lut.bin` and as little endian `u16`s to `mixbox_lut.raw`.
+///
+/// If `PIGMENT_MIXING_LUT_DIR` is set, `mixbox_lut.bin` is copied there, to
+/// be loaded at runtime with the `runtime-lut` feature.
+pub fn write_lut() {
+    for path in [
+        "build.rs",
+        "build/lut.rs",
+        "src/lut_codec.rs",
+        "src/mixbox_lut.rs",
+        "src/polynomial.rs",
+    ] {
+        println!("cargo:rerun-if-changed={path}");
+    }
+    println!("cargo:rerun-if-env-changed=PIGMENT_MIXING_LUT_DIR");
+
+    let out_dir = env::var_os("OUT_DIR").unwrap();
+    let out_dir = Path::new(&out_dir);
+
+    let lut: Vec<[u16; 3]> = build_lut()
+        .into_iter()
+        .map(|c| {
+            c.map(|c| (c.clamp(0.0, 1.0) * u16::MAX as f32).round() as u16)
+        })
+        .collect();
+
+    let compressed = encode(&lut);
+    fs::write(out_dir.join("mixbox_lut.bin"), &compressed).unwrap();
+    if let Some(lut_dir) = env::var_os("PIGMENT_MIXING_LUT_DIR") {
+        fs::create_dir_all(&lut_dir).unwrap();
+        fs::write(Path::new(&lut_dir).join("mixbox_lut.bin"), &compressed)
+            .unwrap();
+    }
+    fs::write(
+        out_dir.join("mixbox_lut.raw"),
+        lut.iter()
+            .flatten()
+            .flat_map(|c| c.to_le_bytes())
+            .collect::<Vec<_>>(),
+    )
+    .unwrap();
+}
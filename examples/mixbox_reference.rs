@@ -0,0 +1,39 @@
+//! Writes mixes of all combinations of a few colors as CSV to stdout.
+//!
+//! Run it with the C++ and the Rust implementation to compare both:
+//!
+//! ```text
+//! cargo run --example mixbox_reference > cpp.csv
+//! cargo run --example mixbox_reference \
+//!     --no-default-features --features pure-rust > pure_rust.csv
+//! ```
+use pigment_mixing::mix_srgb_u8;
+
+/// The component values of the colors, which are all combinations of them.
+const LEVELS: [u8; 4] = [0, 85, 170, 255];
+/// The ratios each pair of colors is mixed at.
+const RATIOS: [f32; 3] = [0.25, 0.5, 0.75];
+
+fn main() {
+    let colors: Vec<[u8; 3]> = LEVELS
+        .iter()
+        .flat_map(|&r| {
+            LEVELS
+                .iter()
+                .flat_map(move |&g| LEVELS.iter().map(move |&b| [r, g, b]))
+        })
+        .collect();
+
+    println!("a_r,a_g,a_b,b_r,b_g,b_b,ratio,r,g,b");
+    for (i, a) in colors.iter().enumerate() {
+        for b in &colors[i + 1..] {
+            for ratio in RATIOS {
+                let [r, g, b_] = mix_srgb_u8(a, b, ratio);
+                println!(
+                    "{},{},{},{},{},{},{},{},{},{}",
+                    a[0], a[1], a[2], b[0], b[1], b[2], ratio, r, g, b_
+                );
+            }
+        }
+    }
+}
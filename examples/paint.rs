@@ -0,0 +1,116 @@
+//! A minimal painting program that mixes paint on a canvas in real time.
+//!
+//! Run with `cargo run --release --example paint --features minifb`.
+//!
+//! * Left mouse button – paint with the current color.
+//! * Right mouse button – smudge.
+//! * `1` … `6` – pick a color.
+//! * `C` – clear the canvas.
+//! * `Escape` – quit.
+use minifb::{Key, KeyRepeat, MouseButton, MouseMode, Window, WindowOptions};
+use pigment_mixing::{Canvas, Pigment, RoundBrush, Smudge, Substrate};
+
+const WIDTH: usize = 640;
+const HEIGHT: usize = 480;
+
+/// Returns the dab positions from `from` to `to`, `spacing` pixels apart,
+/// excluding `from`.
+fn dabs(
+    from: (f32, f32),
+    to: (f32, f32),
+    spacing: f32,
+) -> impl Iterator<Item = (f32, f32)> {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let steps = ((dx * dx + dy * dy).sqrt() / spacing).max(1.0) as usize;
+
+    (1..=steps).map(move |step| {
+        let t = step as f32 / steps as f32;
+        (from.0 + dx * t, from.1 + dy * t)
+    })
+}
+
+fn main() {
+    let colors = [
+        Pigment::from_srgb_u8(254, 236, 0),
+        Pigment::from_srgb_u8(255, 39, 2),
+        Pigment::from_srgb_u8(128, 2, 46),
+        Pigment::from_srgb_u8(25, 0, 89),
+        Pigment::from_srgb_u8(0, 60, 50),
+        Pigment::from_srgb_u8(255, 255, 255),
+    ];
+    let color_keys = [
+        Key::Key1,
+        Key::Key2,
+        Key::Key3,
+        Key::Key4,
+        Key::Key5,
+        Key::Key6,
+    ];
+
+    let mut window = Window::new(
+        "pigment-mixing – paint with 1 … 6, smudge with right click",
+        WIDTH,
+        HEIGHT,
+        WindowOptions::default(),
+    )
+    .expect("window can be opened");
+    window.set_target_fps(60);
+
+    let mut canvas =
+        Canvas::new(WIDTH, HEIGHT, Substrate::from_brightness(0.95));
+    let brush = RoundBrush::new(12.0, 0.4);
+    let spacing = (0.25 * brush.radius).max(1.0);
+    let mut smudge = Smudge::new(0.3, 0.8);
+    let mut color = colors[0];
+
+    let mut rgb = vec![0u8; WIDTH * HEIGHT * 3];
+    let mut frame = vec![0u32; WIDTH * HEIGHT];
+    let mut previous: Option<(f32, f32)> = None;
+
+    while window.is_open() && !window.is_key_down(Key::Escape) {
+        for key in window.get_keys_pressed(KeyRepeat::No) {
+            if let Some(index) = color_keys.iter().position(|&k| k == key) {
+                color = colors[index];
+            } else if Key::C == key {
+                canvas = Canvas::new(WIDTH, HEIGHT, *canvas.substrate());
+            }
+        }
+
+        let painting = window.get_mouse_down(MouseButton::Left);
+        let smudging = window.get_mouse_down(MouseButton::Right);
+
+        match window.get_mouse_pos(MouseMode::Discard) {
+            Some(position) if painting || smudging => {
+                let from = previous.unwrap_or_else(|| {
+                    // A new stroke starts with a clean brush.
+                    smudge.clean();
+                    position
+                });
+
+                for dab in dabs(from, position, spacing) {
+                    if painting {
+                        canvas.stamp(&brush, dab, &color, 0.3);
+                    } else {
+                        smudge.dab(&mut canvas, &brush, dab);
+                    }
+                }
+                previous = Some(position);
+            }
+            _ => previous = None,
+        }
+
+        canvas.tick();
+
+        canvas.to_srgb_u8_into(&mut rgb);
+        frame
+            .iter_mut()
+            .zip(rgb.chunks_exact(3))
+            .for_each(|(pixel, c)| {
+                *pixel = u32::from_be_bytes([0, c[0], c[1], c[2]])
+            });
+
+        window
+            .update_with_buffer(&frame, WIDTH, HEIGHT)
+            .expect("frame has the size of the window");
+    }
+}
@@ -0,0 +1,72 @@
+#ifndef PIGMENT_MIXING_H
+#define PIGMENT_MIXING_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+// The number of floats in a latent.
+#define PM_LATENT_SIZE 7
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Mixes two colors.
+//
+// # Safety
+//
+// `a`, `b` and `out` must each point to three `uint8_t`s.
+void pm_mix_srgb8(const uint8_t *a, const uint8_t *b, float ratio, uint8_t *out);
+
+// Mixes `pixel_count` pairs of colors, e.g. two images, pixel by pixel.
+//
+// `out` may be the same as `a` or `b`.
+//
+// # Safety
+//
+// `a`, `b` and `out` must each point to `3 * pixel_count` `uint8_t`s.
+void pm_mix_srgb8_buffer(const uint8_t *a,
+                         const uint8_t *b,
+                         float ratio,
+                         uint8_t *out,
+                         size_t pixel_count);
+
+// Writes `count` evenly spaced mixes from `a` to `b`, both ends included,
+// to `out`.
+//
+// # Safety
+//
+// `a` and `b` must each point to three `uint8_t`s, `out` to `3 * count`
+// `uint8_t`s.
+void pm_gradient_srgb8(const uint8_t *a, const uint8_t *b, uint8_t *out, size_t count);
+
+// Converts a color to its latent.
+//
+// # Safety
+//
+// `srgb` must point to three `uint8_t`s, `latent` to `PM_LATENT_SIZE`
+// `float`s.
+void pm_srgb_to_latent(const uint8_t *srgb, float *latent);
+
+// Converts a latent to a color.
+//
+// # Safety
+//
+// `latent` must point to `PM_LATENT_SIZE` `float`s, `srgb` to three
+// `uint8_t`s.
+void pm_latent_to_srgb(const float *latent, uint8_t *srgb);
+
+// Mixes two latents. This is a linear interpolation.
+//
+// `out` may be the same as `a` or `b`.
+//
+// # Safety
+//
+// `a`, `b` and `out` must each point to `PM_LATENT_SIZE` `float`s.
+void pm_mix_latent(const float *a, const float *b, float ratio, float *out);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif // PIGMENT_MIXING_H
@@ -1,10 +1,30 @@
 #![allow(unreachable_code)]
-use std::{env, path::PathBuf};
 
 //#[cfg(all(target_os = "macos", feature = "openmp"))]
 //static MAC_OS_BREW_CLANG_PATH: &str = "/usr/local/opt/llvm";
 
 pub fn main() {
+    #[cfg(feature = "dynamic")]
+    link_dynamic();
+
+    #[cfg(all(feature = "static", not(feature = "dynamic")))]
+    build_static();
+
+    #[cfg(not(any(feature = "static", feature = "dynamic")))]
+    panic!("enable either the `static` or the `dynamic` feature");
+
+    #[cfg(feature = "regenerate")]
+    generate_bindings();
+
+    #[cfg(not(any(feature = "bundled-bindings", feature = "regenerate")))]
+    panic!("enable either the `bundled-bindings` or the `regenerate` feature");
+
+    println!("cargo:rerun-if-changed=build.rs");
+}
+
+/// Compiles the C++ source and links it statically.
+#[cfg(all(feature = "static", not(feature = "dynamic")))]
+fn build_static() {
     cc::Build::new()
         //.include(&osd_inlude_path)
         .cpp(true)
@@ -17,6 +37,28 @@ pub fn main() {
         .compile("mixbox");
 
     println!("cargo:rustc-link-lib=static=mixbox");
+}
+
+/// Links a prebuilt shared `libmixbox`, searched for in `MIXBOX_LIB_DIR` if
+/// set and in the system's library paths otherwise.
+#[cfg(feature = "dynamic")]
+fn link_dynamic() {
+    println!("cargo:rerun-if-env-changed=MIXBOX_LIB_DIR");
+    if let Some(lib_dir) = std::env::var_os("MIXBOX_LIB_DIR") {
+        println!(
+            "cargo:rustc-link-search=native={}",
+            std::path::Path::new(&lib_dir).display()
+        );
+    }
+
+    println!("cargo:rustc-link-lib=dylib=mixbox");
+}
+
+/// Runs bindgen on `wrapper.hpp`. Copy the result from `OUT_DIR` to
+/// `src/bindings.rs` to update the bundled bindings.
+#[cfg(feature = "regenerate")]
+fn generate_bindings() {
+    use std::{env, path::PathBuf};
 
     println!("cargo:rerun-if-changed=wrapper.hpp");
 
@@ -40,6 +82,4 @@ pub fn main() {
     bindings
         .write_to_file(&bindings_path)
         .expect("Couldn't write bindings");
-
-    println!("cargo:rerun-if-changed=build.rs");
 }
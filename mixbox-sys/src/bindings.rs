@@ -0,0 +1,63 @@
+/* automatically generated by rust-bindgen 0.59.2 */
+
+pub const MIXBOX_NUMLATENTS: u32 = 7;
+extern "C" {
+    pub fn mixbox_lerp_srgb8(
+        r1: ::std::os::raw::c_uchar,
+        g1: ::std::os::raw::c_uchar,
+        b1: ::std::os::raw::c_uchar,
+        r2: ::std::os::raw::c_uchar,
+        g2: ::std::os::raw::c_uchar,
+        b2: ::std::os::raw::c_uchar,
+        t: f32,
+        out_r: *mut ::std::os::raw::c_uchar,
+        out_g: *mut ::std::os::raw::c_uchar,
+        out_b: *mut ::std::os::raw::c_uchar,
+    );
+}
+extern "C" {
+    pub fn mixbox_lerp_srgb32f(
+        r1: f32,
+        g1: f32,
+        b1: f32,
+        r2: f32,
+        g2: f32,
+        b2: f32,
+        t: f32,
+        out_r: *mut f32,
+        out_g: *mut f32,
+        out_b: *mut f32,
+    );
+}
+extern "C" {
+    pub fn mixbox_srgb8_to_latent(
+        r: ::std::os::raw::c_uchar,
+        g: ::std::os::raw::c_uchar,
+        b: ::std::os::raw::c_uchar,
+        out_latent: *mut f32,
+    );
+}
+extern "C" {
+    pub fn mixbox_latent_to_srgb8(
+        latent: *const f32,
+        out_r: *mut ::std::os::raw::c_uchar,
+        out_g: *mut ::std::os::raw::c_uchar,
+        out_b: *mut ::std::os::raw::c_uchar,
+    );
+}
+extern "C" {
+    pub fn mixbox_srgb32f_to_latent(
+        r: f32,
+        g: f32,
+        b: f32,
+        out_latent: *mut f32,
+    );
+}
+extern "C" {
+    pub fn mixbox_latent_to_srgb32f(
+        latent: *const f32,
+        out_r: *mut f32,
+        out_g: *mut f32,
+        out_b: *mut f32,
+    );
+}
@@ -5,5 +5,12 @@
 #![allow(non_snake_case)]
 #![allow(improper_ctypes)]*/
 //! You probably want the high level wrapper – [`pigment-mixing`](https://crates.io/crates/pigment-mixing).
+//!
+//! By default the bindings bundled with this crate are used, so building does
+//! not need `libclang`. The `regenerate` feature runs bindgen instead.
 
+#[cfg(feature = "regenerate")]
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+#[cfg(not(feature = "regenerate"))]
+include!("bindings.rs");
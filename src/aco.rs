@@ -0,0 +1,148 @@
+use crate::{
+    bytes::{utf16_len, ByteReader, ByteWriter},
+    palette::{pigment_from_cmyk, pigment_from_lab_d50},
+    Error, Palette, Pigment,
+};
+use alloc::{string::String, vec::Vec};
+
+const COLOR_SPACE_RGB: u16 = 0;
+const COLOR_SPACE_HSB: u16 = 1;
+const COLOR_SPACE_CMYK: u16 = 2;
+const COLOR_SPACE_LAB: u16 = 7;
+const COLOR_SPACE_GRAY: u16 = 8;
+
+impl Palette {
+    /// Reads a palette from the contents of a Photoshop color swatch
+    /// (`.aco`) file.
+    ///
+    /// If the file contains a version 2 section the names are read from it.
+    /// Otherwise all names are empty.
+    ///
+    /// RGB, HSB, CMYK, CIELAB and grayscale colors are supported. CMYK is
+    /// converted naively, without an ICC profile.
+    pub fn from_aco(aco: &[u8]) -> Result<Self, Error> {
+        let mut reader = ByteReader::new(aco);
+
+        let mut palette = read_section(&mut reader, 1)?;
+        // The version 2 section repeats the colors, adding names.
+        if !reader.is_empty() {
+            palette = read_section(&mut reader, 2)?;
+        }
+
+        Ok(palette)
+    }
+
+    /// Writes the palette as the contents of a Photoshop color swatch
+    /// (`.aco`) file.
+    ///
+    /// Both a version 1 and a version 2 section are written so older
+    /// applications can read the colors. All colors are written as RGB.
+    ///
+    /// Returns [`Error::TooLarge`] if the palette has more than 65535 colors.
+    pub fn to_aco(&self) -> Result<Vec<u8>, Error> {
+        let len = u16::try_from(self.len())
+            .map_err(|_| Error::TooLarge("ACO holds at most 65535 colors"))?;
+        let mut aco = Vec::new();
+
+        for version in 1..=2 {
+            aco.put_u16(version);
+            aco.put_u16(len);
+
+            self.iter().for_each(|entry| {
+                let srgb: [f32; 3] = entry.pigment.to_srgb();
+
+                aco.put_u16(COLOR_SPACE_RGB);
+                srgb.iter().for_each(|&c| {
+                    aco.put_u16(
+                        (c.clamp(0.0, 1.0) * u16::MAX as f32 + 0.5) as _,
+                    )
+                });
+                aco.put_u16(0);
+
+                if 2 == version {
+                    aco.put_u32(utf16_len(&entry.name) as _);
+                    aco.put_utf16(&entry.name);
+                }
+            });
+        }
+
+        Ok(aco)
+    }
+}
+
+fn read_section(
+    reader: &mut ByteReader,
+    expected_version: u16,
+) -> Result<Palette, Error> {
+    if expected_version != reader.u16()? {
+        return Err(Error::InvalidFormat("unexpected ACO version"));
+    }
+
+    let count = reader.u16()?;
+    let mut palette = Palette::new();
+
+    for _ in 0..count {
+        let color_space = reader.u16()?;
+        let (w, x, y, z) =
+            (reader.u16()?, reader.u16()?, reader.u16()?, reader.u16()?);
+
+        let name = if 2 == expected_version {
+            let len = reader.u32()? as usize;
+            reader.utf16(len)?
+        } else {
+            String::new()
+        };
+
+        let unit = |value: u16| value as f32 / u16::MAX as f32;
+
+        let pigment = match color_space {
+            COLOR_SPACE_RGB => Pigment::from_srgb(unit(w), unit(x), unit(y)),
+            COLOR_SPACE_HSB => {
+                let [r, g, b] = hsv_to_rgb(unit(w), unit(x), unit(y));
+                Pigment::from_srgb(r, g, b)
+            }
+            // 0 means 100% ink.
+            COLOR_SPACE_CMYK => pigment_from_cmyk(
+                1.0 - unit(w),
+                1.0 - unit(x),
+                1.0 - unit(y),
+                1.0 - unit(z),
+            ),
+            COLOR_SPACE_LAB => pigment_from_lab_d50(
+                w as f32 / 100.0,
+                x as i16 as f32 / 100.0,
+                y as i16 as f32 / 100.0,
+            ),
+            COLOR_SPACE_GRAY => {
+                // 0 is white, 10000 is black.
+                let gray = 1.0 - w as f32 / 10000.0;
+                Pigment::from_srgb(gray, gray, gray)
+            }
+            _ => return Err(Error::InvalidFormat("unknown ACO color space")),
+        };
+
+        palette.push(name, pigment);
+    }
+
+    Ok(palette)
+}
+
+/// Converts a hue, saturation, value triplet, all in `0.0..=1.0`, to RGB.
+fn hsv_to_rgb(h: f32, s: f32, v: f32) -> [f32; 3] {
+    let h = 6.0 * h;
+    let sector = h as u32 % 6;
+    let f = h - h as u32 as f32;
+
+    let p = v * (1.0 - s);
+    let q = v * (1.0 - s * f);
+    let t = v * (1.0 - s * (1.0 - f));
+
+    match sector {
+        0 => [v, t, p],
+        1 => [q, v, p],
+        2 => [p, v, t],
+        3 => [p, q, v],
+        4 => [t, p, v],
+        _ => [v, p, q],
+    }
+}
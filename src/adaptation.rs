@@ -0,0 +1,183 @@
+//! Chromatic adaptation of colors with a white point other than D65.
+use crate::Pigment;
+
+type Matrix = [[f32; 3]; 3];
+
+/// Linear sRGB to CIE XYZ.
+const SRGB_TO_XYZ: Matrix = [
+    [0.412_390_8, 0.357_584_33, 0.180_480_8],
+    [0.212_639, 0.715_168_65, 0.072_192_32],
+    [0.019_330_818, 0.119_194_78, 0.950_532_1],
+];
+
+/// CIE XYZ to linear sRGB.
+const XYZ_TO_SRGB: Matrix = [
+    [3.240_97, -1.537_383_2, -0.498_610_76],
+    [-0.969_243_6, 1.875_967_5, 0.041_555_06],
+    [0.055_630_08, -0.203_976_96, 1.056_971_5],
+];
+
+const BRADFORD: Matrix = [
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296],
+];
+
+const BRADFORD_INVERSE: Matrix = [
+    [0.986_993, -0.147_054_3, 0.159_962_7],
+    [0.432_305_3, 0.518_360_3, 0.049_291_2],
+    [-0.008_528_7, 0.040_042_8, 0.968_486_7],
+];
+
+const CAT02: Matrix = [
+    [0.7328, 0.4296, -0.1624],
+    [-0.7036, 1.6975, 0.0061],
+    [0.0030, 0.0136, 0.9834],
+];
+
+const CAT02_INVERSE: Matrix = [
+    [1.096_124, -0.278_869, 0.182_745],
+    [0.454_369, 0.473_533, 0.072_098],
+    [-0.009_628, -0.005_698, 1.015_326],
+];
+
+#[inline]
+fn apply(m: &Matrix, v: [f32; 3]) -> [f32; 3] {
+    m.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+/// The white point of a color, i.e. the color of the light it was measured
+/// or encoded under.
+///
+/// sRGB, and thus everything in this crate, uses D65.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum WhitePoint {
+    /// CIE standard illuminant D50, the white point of ICC profile connection
+    /// spaces and of most print workflows.
+    D50,
+    /// CIE standard illuminant D65, the white point of sRGB.
+    #[default]
+    D65,
+    /// A custom white point given by its CIE 1931 `x`, `y` chromaticity.
+    Xy(f32, f32),
+}
+
+impl WhitePoint {
+    /// Returns the CIE 1931 `x`, `y` chromaticity.
+    #[inline]
+    pub fn xy(self) -> (f32, f32) {
+        match self {
+            WhitePoint::D50 => (0.3457, 0.3585),
+            WhitePoint::D65 => (0.3127, 0.3290),
+            WhitePoint::Xy(x, y) => (x, y),
+        }
+    }
+
+    /// Returns the CIE XYZ of the white point, normalized to `Y = 1`.
+    #[inline]
+    pub fn xyz(self) -> [f32; 3] {
+        let (x, y) = self.xy();
+        [x / y, 1.0, (1.0 - x - y) / y]
+    }
+}
+
+/// The method used to adapt colors from one white point to another.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ChromaticAdaptation {
+    /// The Bradford transform, as used by ICC profiles.
+    #[default]
+    Bradford,
+    /// The CAT02 transform of CIECAM02.
+    Cat02,
+}
+
+impl ChromaticAdaptation {
+    /// Adapts the CIE XYZ color `xyz` from white point `from` to `to`.
+    pub fn adapt_xyz(
+        self,
+        xyz: [f32; 3],
+        from: WhitePoint,
+        to: WhitePoint,
+    ) -> [f32; 3] {
+        if from == to {
+            return xyz;
+        }
+
+        let (m, m_inverse) = match self {
+            ChromaticAdaptation::Bradford => (&BRADFORD, &BRADFORD_INVERSE),
+            ChromaticAdaptation::Cat02 => (&CAT02, &CAT02_INVERSE),
+        };
+
+        // Von Kries scaling of the cone responses.
+        let (source, destination) = (apply(m, from.xyz()), apply(m, to.xyz()));
+        let cone = apply(m, xyz);
+
+        apply(
+            m_inverse,
+            [0, 1, 2].map(|i| cone[i] * destination[i] / source[i]),
+        )
+    }
+
+    /// Adapts the linear color `rgb`, which has the sRGB primaries but is
+    /// relative to `white`, to linear sRGB.
+    ///
+    /// ```
+    /// # use pigment_mixing::{ChromaticAdaptation, WhitePoint};
+    /// // A color from a scan with a D50 white point.
+    /// let rgb = ChromaticAdaptation::Bradford
+    ///     .adapt_linear_srgb([0.8, 0.45, 0.1], WhitePoint::D50);
+    /// ```
+    #[inline]
+    pub fn adapt_linear_srgb(
+        self,
+        rgb: [f32; 3],
+        white: WhitePoint,
+    ) -> [f32; 3] {
+        apply(
+            &XYZ_TO_SRGB,
+            self.adapt_xyz(apply(&SRGB_TO_XYZ, rgb), white, WhitePoint::D65),
+        )
+    }
+}
+
+impl Pigment {
+    /// Constructs a `Pigment` from a CIE XYZ color relative to `white`,
+    /// adapting it to D65 with `adaptation`.
+    ///
+    /// `Y = 1` is the luminance of white.
+    ///
+    /// ```
+    /// # use pigment_mixing::{ChromaticAdaptation, Pigment, WhitePoint};
+    /// // A print swatch measured under D50.
+    /// let swatch = Pigment::from_xyz(
+    ///     [0.38, 0.35, 0.08],
+    ///     WhitePoint::D50,
+    ///     ChromaticAdaptation::Bradford,
+    /// );
+    /// ```
+    #[inline]
+    pub fn from_xyz(
+        xyz: [f32; 3],
+        white: WhitePoint,
+        adaptation: ChromaticAdaptation,
+    ) -> Self {
+        Self::from(apply(
+            &XYZ_TO_SRGB,
+            adaptation.adapt_xyz(xyz, white, WhitePoint::D65),
+        ))
+    }
+
+    /// Constructs a `Pigment` from a linear color with the sRGB primaries
+    /// relative to `white`, adapting it to D65 with `adaptation`.
+    ///
+    /// Mixing colors with a white point other than D65 without adapting them
+    /// shifts the hue of every result.
+    #[inline]
+    pub fn from_linear_srgb_adapted(
+        rgb: [f32; 3],
+        white: WhitePoint,
+        adaptation: ChromaticAdaptation,
+    ) -> Self {
+        Self::from(adaptation.adapt_linear_srgb(rgb, white))
+    }
+}
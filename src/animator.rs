@@ -0,0 +1,78 @@
+use crate::Pigment;
+
+/// Interpolates between `N` colors, evenly spaced over `0.0..=1.0`, for
+/// animation.
+///
+/// The colors are converted to pigments once, on construction.
+/// [`sample()`](MixAnimator::sample) then only mixes latents, which is what
+/// makes this cheap enough for tweening every frame. It does not allocate.
+///
+/// ```
+/// # use pigment_mixing::MixAnimator;
+/// let animator = MixAnimator::new([[252, 211, 0], [0, 0, 96]]);
+///
+/// for frame in 0..60 {
+///     let color = animator.sample_srgb_u8(frame as f32 / 59.0);
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MixAnimator<const N: usize> {
+    keys: [Pigment; N],
+}
+
+impl<const N: usize> MixAnimator<N> {
+    /// Constructs a `MixAnimator` from `u8` component encoded sRGB (gamma
+    /// 2.2) colors.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is `0`.
+    #[inline]
+    pub fn new(srgb: [[u8; 3]; N]) -> Self {
+        Self::from_pigments(
+            srgb.map(|[r, g, b]| Pigment::from_srgb_u8(r, g, b)),
+        )
+    }
+
+    /// Constructs a `MixAnimator` from pigments.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is `0`.
+    #[inline]
+    pub fn from_pigments(keys: [Pigment; N]) -> Self {
+        assert!(0 < N, "an animator needs at least one color");
+        Self { keys }
+    }
+
+    /// Returns the colors.
+    #[inline]
+    pub fn keys(&self) -> &[Pigment; N] {
+        &self.keys
+    }
+
+    /// Returns the mix at `t`, clamped to `0.0..=1.0`.
+    #[inline]
+    pub fn sample(&self, t: f32) -> Pigment {
+        if N < 2 {
+            return self.keys[0];
+        }
+
+        let t = t.clamp(0.0, 1.0) * (N - 1) as f32;
+        let i = (t as usize).min(N - 2);
+
+        Pigment::from_mix(self.keys[i], self.keys[i + 1], t - i as f32)
+    }
+
+    /// Returns the mix at `t` as `u8` component encoded sRGB (gamma 2.2).
+    #[inline]
+    pub fn sample_srgb_u8(&self, t: f32) -> [u8; 3] {
+        self.sample(t).into()
+    }
+
+    /// Returns the mix at `t` as linear sRGB.
+    #[inline]
+    pub fn sample_linear_srgb(&self, t: f32) -> [f32; 3] {
+        self.sample(t).into()
+    }
+}
@@ -0,0 +1,138 @@
+//! Previews of colors as 24-bit ANSI colored blocks for terminals.
+//!
+//! The previews implement [`Display`](fmt::Display) and do not allocate.
+//! They need a terminal with truecolor support.
+#[cfg(feature = "palette")]
+use crate::Palette;
+use crate::Pigment;
+#[cfg(feature = "gradient")]
+use crate::{sample_position, Gradient};
+use core::fmt;
+
+/// Writes `width` blocks of `srgb` to `f`.
+#[inline]
+fn write_blocks(
+    f: &mut fmt::Formatter<'_>,
+    srgb: [u8; 3],
+    width: usize,
+) -> fmt::Result {
+    write!(f, "\x1b[48;2;{};{};{}m", srgb[0], srgb[1], srgb[2])?;
+    (0..width).try_for_each(|_| f.write_str(" "))?;
+    f.write_str("\x1b[0m")
+}
+
+/// A terminal preview of a [`Pigment`], see [`Pigment::ansi_swatch()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnsiSwatch {
+    srgb: [u8; 3],
+}
+
+impl fmt::Display for AnsiSwatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_blocks(f, self.srgb, 2)
+    }
+}
+
+impl Pigment {
+    /// Returns a preview of the color for terminal output.
+    ///
+    /// ```
+    /// # use pigment_mixing::Pigment;
+    /// let green = Pigment::from_mix(
+    ///     Pigment::from_srgb_u8(252, 211, 0),
+    ///     Pigment::from_srgb_u8(0, 0, 96),
+    ///     0.5,
+    /// );
+    ///
+    /// println!("{} green", green.ansi_swatch());
+    /// ```
+    #[inline]
+    pub fn ansi_swatch(&self) -> AnsiSwatch {
+        AnsiSwatch {
+            srgb: (*self).into(),
+        }
+    }
+}
+
+/// A terminal preview of a [`Palette`], see [`Palette::ansi_swatch()`].
+#[cfg(feature = "palette")]
+#[derive(Clone, Copy, Debug)]
+pub struct AnsiPaletteSwatch<'a> {
+    palette: &'a Palette,
+}
+
+#[cfg(feature = "palette")]
+impl fmt::Display for AnsiPaletteSwatch<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.palette.iter().enumerate().try_for_each(|(i, entry)| {
+            if 0 != i {
+                f.write_str("\n")?;
+            }
+            write!(f, "{} {}", entry.pigment.ansi_swatch(), entry.name)
+        })
+    }
+}
+
+#[cfg(feature = "palette")]
+impl Palette {
+    /// Returns a preview of the palette for terminal output, one entry with
+    /// its name per line.
+    ///
+    /// ```
+    /// # use pigment_mixing::{Palette, Pigment};
+    /// let palette = Palette::new()
+    ///     .with_entry("Cadmium Yellow", Pigment::from_srgb_u8(254, 236, 0))
+    ///     .with_entry("Ultramarine", Pigment::from_srgb_u8(25, 0, 89));
+    ///
+    /// println!("{}", palette.ansi_swatch());
+    /// ```
+    #[inline]
+    pub fn ansi_swatch(&self) -> AnsiPaletteSwatch<'_> {
+        AnsiPaletteSwatch { palette: self }
+    }
+}
+
+/// A terminal preview of a [`Gradient`], see [`Gradient::ansi_swatch()`].
+#[cfg(feature = "gradient")]
+#[derive(Clone, Copy, Debug)]
+pub struct AnsiGradientSwatch<'a> {
+    gradient: &'a Gradient,
+    width: usize,
+}
+
+#[cfg(feature = "gradient")]
+impl fmt::Display for AnsiGradientSwatch<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (0..self.width).try_for_each(|i| {
+            let srgb =
+                self.gradient.sample(sample_position(i, self.width)).into();
+            write_blocks(f, srgb, 1)
+        })
+    }
+}
+
+#[cfg(feature = "gradient")]
+impl Gradient {
+    /// Returns a preview of the gradient for terminal output, `width`
+    /// characters wide.
+    ///
+    /// # Panics
+    ///
+    /// When displayed, if the gradient has no stops.
+    ///
+    /// ```
+    /// # use pigment_mixing::{Gradient, Pigment};
+    /// let gradient = Gradient::new()
+    ///     .with_stop(0.0, Pigment::from_srgb_u8(252, 211, 0))
+    ///     .with_stop(1.0, Pigment::from_srgb_u8(0, 0, 96));
+    ///
+    /// println!("{}", gradient.ansi_swatch(64));
+    /// ```
+    #[inline]
+    pub fn ansi_swatch(&self, width: usize) -> AnsiGradientSwatch<'_> {
+        AnsiGradientSwatch {
+            gradient: self,
+            width,
+        }
+    }
+}
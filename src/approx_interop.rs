@@ -0,0 +1,121 @@
+//! Implementations of the [`approx`](https://docs.rs/approx/) traits.
+//!
+//! `Pigment`s compare by their latent values. `approx` already covers the
+//! plain color arrays this crate returns when they are compared as slices:
+//!
+//! ```
+//! # use pigment_mixing::Pigment;
+//! use approx::assert_relative_eq;
+//!
+//! let yellow = Pigment::from_srgb_u8(254, 236, 0);
+//! let blue = Pigment::from_srgb_u8(25, 0, 89);
+//!
+//! assert_relative_eq!(
+//!     Pigment::from_mix(yellow, blue, 0.25),
+//!     Pigment::mix_amounts(yellow, 3.0, blue, 1.0),
+//!     epsilon = 1.0e-6
+//! );
+//!
+//! let green: [f32; 3] = Pigment::from_mix(yellow, blue, 0.5).into();
+//! assert_relative_eq!(green[..], green[..]);
+//! ```
+use crate::{pigment::PIGMENT_LEN, Pigment, PigmentAlpha};
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+impl AbsDiffEq for Pigment {
+    type Epsilon = f32;
+
+    #[inline]
+    fn default_epsilon() -> f32 {
+        f32::default_epsilon()
+    }
+
+    #[inline]
+    fn abs_diff_eq(&self, other: &Self, epsilon: f32) -> bool {
+        let (a, b): ([f32; PIGMENT_LEN], [f32; PIGMENT_LEN]) =
+            ((*self).into(), (*other).into());
+        a[..].abs_diff_eq(&b[..], epsilon)
+    }
+}
+
+impl RelativeEq for Pigment {
+    #[inline]
+    fn default_max_relative() -> f32 {
+        f32::default_max_relative()
+    }
+
+    #[inline]
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: f32,
+        max_relative: f32,
+    ) -> bool {
+        let (a, b): ([f32; PIGMENT_LEN], [f32; PIGMENT_LEN]) =
+            ((*self).into(), (*other).into());
+        a[..].relative_eq(&b[..], epsilon, max_relative)
+    }
+}
+
+impl UlpsEq for Pigment {
+    #[inline]
+    fn default_max_ulps() -> u32 {
+        f32::default_max_ulps()
+    }
+
+    #[inline]
+    fn ulps_eq(&self, other: &Self, epsilon: f32, max_ulps: u32) -> bool {
+        let (a, b): ([f32; PIGMENT_LEN], [f32; PIGMENT_LEN]) =
+            ((*self).into(), (*other).into());
+        a[..].ulps_eq(&b[..], epsilon, max_ulps)
+    }
+}
+
+impl AbsDiffEq for PigmentAlpha {
+    type Epsilon = f32;
+
+    #[inline]
+    fn default_epsilon() -> f32 {
+        f32::default_epsilon()
+    }
+
+    #[inline]
+    fn abs_diff_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.pigment.abs_diff_eq(&other.pigment, epsilon)
+            && self.opacity.abs_diff_eq(&other.opacity, epsilon)
+    }
+}
+
+impl RelativeEq for PigmentAlpha {
+    #[inline]
+    fn default_max_relative() -> f32 {
+        f32::default_max_relative()
+    }
+
+    #[inline]
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: f32,
+        max_relative: f32,
+    ) -> bool {
+        self.pigment
+            .relative_eq(&other.pigment, epsilon, max_relative)
+            && self
+                .opacity
+                .relative_eq(&other.opacity, epsilon, max_relative)
+    }
+}
+
+impl UlpsEq for PigmentAlpha {
+    #[inline]
+    fn default_max_ulps() -> u32 {
+        f32::default_max_ulps()
+    }
+
+    #[inline]
+    fn ulps_eq(&self, other: &Self, epsilon: f32, max_ulps: u32) -> bool {
+        self.pigment.ulps_eq(&other.pigment, epsilon, max_ulps)
+            && self.opacity.ulps_eq(&other.opacity, epsilon, max_ulps)
+    }
+}
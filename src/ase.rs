@@ -0,0 +1,116 @@
+use crate::{
+    bytes::{utf16_len, ByteReader, ByteWriter},
+    palette::{pigment_from_cmyk, pigment_from_lab_d50},
+    Error, Palette, Pigment,
+};
+use alloc::vec::Vec;
+
+const SIGNATURE: &[u8; 4] = b"ASEF";
+
+const BLOCK_COLOR: u16 = 0x0001;
+
+// Global color; the other types are spot (1) and normal (2).
+const COLOR_TYPE_GLOBAL: u16 = 0;
+
+impl Palette {
+    /// Reads a palette from the contents of an Adobe Swatch Exchange (`.ase`)
+    /// file.
+    ///
+    /// Groups are flattened. RGB, gray, CIELAB and CMYK colors are supported.
+    /// CMYK is converted naively, without an ICC profile.
+    pub fn from_ase(ase: &[u8]) -> Result<Self, Error> {
+        let mut reader = ByteReader::new(ase);
+
+        if SIGNATURE != reader.bytes(4)? {
+            return Err(Error::InvalidFormat("missing ASEF signature"));
+        }
+        // Version.
+        reader.u16()?;
+        reader.u16()?;
+
+        let block_count = reader.u32()?;
+        let mut palette = Palette::new();
+
+        for _ in 0..block_count {
+            let block_type = reader.u16()?;
+            let block_len = reader.u32()? as usize;
+            let mut block = ByteReader::new(reader.bytes(block_len)?);
+
+            // Group start and end blocks carry nothing we need.
+            if BLOCK_COLOR != block_type {
+                continue;
+            }
+
+            let name_len = block.u16()? as usize;
+            let name = block.utf16(name_len)?;
+
+            let pigment = match block.bytes(4)? {
+                b"RGB " => {
+                    Pigment::from_srgb(block.f32()?, block.f32()?, block.f32()?)
+                }
+                b"Gray" => {
+                    let gray = block.f32()?;
+                    Pigment::from_srgb(gray, gray, gray)
+                }
+                // L is stored in 0..1.
+                b"LAB " => pigment_from_lab_d50(
+                    100.0 * block.f32()?,
+                    block.f32()?,
+                    block.f32()?,
+                ),
+                b"CMYK" => pigment_from_cmyk(
+                    block.f32()?,
+                    block.f32()?,
+                    block.f32()?,
+                    block.f32()?,
+                ),
+                _ => {
+                    return Err(Error::InvalidFormat("unknown ASE color model"))
+                }
+            };
+
+            palette.push(name, pigment);
+        }
+
+        Ok(palette)
+    }
+
+    /// Writes the palette as the contents of an Adobe Swatch Exchange
+    /// (`.ase`) file.
+    ///
+    /// All colors are written as global RGB colors. Components outside
+    /// `0.0..=1.0` are clamped.
+    ///
+    /// Returns [`Error::TooLarge`] if the palette has more than
+    /// 4294967295 colors or a name is longer than 65534 UTF-16 code units.
+    pub fn to_ase(&self) -> Result<Vec<u8>, Error> {
+        let len = u32::try_from(self.len()).map_err(|_| {
+            Error::TooLarge("ASE holds at most 4294967295 colors")
+        })?;
+        let mut ase = Vec::new();
+
+        ase.extend_from_slice(SIGNATURE);
+        ase.put_u16(1);
+        ase.put_u16(0);
+        ase.put_u32(len);
+
+        for entry in self.iter() {
+            let name_len =
+                u16::try_from(utf16_len(&entry.name)).map_err(|_| {
+                    Error::TooLarge("ASE names hold at most 65534 code units")
+                })?;
+            let srgb = entry.pigment.to_srgb();
+
+            ase.put_u16(BLOCK_COLOR);
+            // Name length, name, model, three components and color type.
+            ase.put_u32(2 + 2 * name_len as u32 + 4 + 3 * 4 + 2);
+            ase.put_u16(name_len);
+            ase.put_utf16(&entry.name);
+            ase.extend_from_slice(b"RGB ");
+            srgb.iter().for_each(|&c| ase.put_f32(c.clamp(0.0, 1.0)));
+            ase.put_u16(COLOR_TYPE_GLOBAL);
+        }
+
+        Ok(ase)
+    }
+}
@@ -0,0 +1,157 @@
+//! Conversions from and to [Bevy](https://bevyengine.org/) colors and a
+//! plugin that tweens sprite and UI colors like paint.
+use crate::Pigment;
+use bevy::{
+    color::{Alpha, LinearRgba, Srgba},
+    prelude::*,
+};
+
+/// Convert a [`LinearRgba`] to a `Pigment`. The alpha channel is ignored.
+impl From<LinearRgba> for Pigment {
+    #[inline]
+    fn from(color: LinearRgba) -> Self {
+        Pigment::from_linear_srgb(color.red, color.green, color.blue)
+    }
+}
+
+/// Convert an [`Srgba`] to a `Pigment`. The alpha channel is ignored.
+impl From<Srgba> for Pigment {
+    #[inline]
+    fn from(color: Srgba) -> Self {
+        Pigment::from_srgb(color.red, color.green, color.blue)
+    }
+}
+
+/// Convert a Bevy [`Color`] in any color space to a `Pigment`. The alpha
+/// channel is ignored.
+impl From<Color> for Pigment {
+    #[inline]
+    fn from(color: Color) -> Self {
+        color.to_linear().into()
+    }
+}
+
+/// Convert a `Pigment` to an opaque [`LinearRgba`].
+impl From<Pigment> for LinearRgba {
+    #[inline]
+    fn from(pigment: Pigment) -> Self {
+        let [red, green, blue]: [f32; 3] = pigment.into();
+        LinearRgba::rgb(red, green, blue)
+    }
+}
+
+/// Convert a `Pigment` to an opaque [`Srgba`].
+impl From<Pigment> for Srgba {
+    #[inline]
+    fn from(pigment: Pigment) -> Self {
+        let [red, green, blue] = pigment.to_srgb();
+        Srgba::rgb(red, green, blue)
+    }
+}
+
+/// Convert a `Pigment` to an opaque Bevy [`Color`] in linear sRGB.
+impl From<Pigment> for Color {
+    #[inline]
+    fn from(pigment: Pigment) -> Self {
+        Color::LinearRgba(pigment.into())
+    }
+}
+
+/// Mixes two Bevy colors as pigments.
+///
+/// Alpha is interpolated linearly. The result is in linear sRGB.
+pub fn mix_color(a: Color, b: Color, ratio: f32) -> Color {
+    let alpha = a.alpha() + (b.alpha() - a.alpha()) * ratio;
+
+    Color::from(Pigment::from_mix(a.into(), b.into(), ratio)).with_alpha(alpha)
+}
+
+/// Animates the color of a [`Sprite`] or [`BackgroundColor`] on the same
+/// entity from one pigment to another.
+///
+/// The alpha of the color is left alone. Requires the
+/// [`PigmentMixingPlugin`].
+#[derive(Clone, Copy, Debug, Component)]
+pub struct PigmentTween {
+    /// The pigment at the start.
+    pub from: Pigment,
+    /// The pigment at the end.
+    pub to: Pigment,
+    /// The length of the tween in seconds.
+    pub duration: f32,
+    /// The time since the start in seconds.
+    pub elapsed: f32,
+}
+
+impl PigmentTween {
+    /// Constructs a `PigmentTween` of `duration` seconds.
+    pub fn new(
+        from: impl Into<Pigment>,
+        to: impl Into<Pigment>,
+        duration: f32,
+    ) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Returns the progress from `0.0` to `1.0`.
+    #[inline]
+    pub fn ratio(&self) -> f32 {
+        if 0.0 < self.duration {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// Returns `true` once the tween has reached its end.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.duration <= self.elapsed
+    }
+
+    /// Advances the tween by `seconds` and returns the current color with
+    /// the alpha of `color`.
+    fn advance(&mut self, seconds: f32, color: Color) -> Color {
+        self.elapsed += seconds;
+
+        Color::from(Pigment::from_mix(self.from, self.to, self.ratio()))
+            .with_alpha(color.alpha())
+    }
+}
+
+/// Runs every [`PigmentTween`] each frame.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PigmentMixingPlugin;
+
+impl Plugin for PigmentMixingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (tween_sprites, tween_backgrounds));
+    }
+}
+
+fn tween_sprites(
+    time: Res<Time>,
+    mut query: Query<(&mut PigmentTween, &mut Sprite)>,
+) {
+    for (mut tween, mut sprite) in &mut query {
+        if !tween.is_finished() {
+            sprite.color = tween.advance(time.delta_seconds(), sprite.color);
+        }
+    }
+}
+
+fn tween_backgrounds(
+    time: Res<Time>,
+    mut query: Query<(&mut PigmentTween, &mut BackgroundColor)>,
+) {
+    for (mut tween, mut background) in &mut query {
+        if !tween.is_finished() {
+            background.0 = tween.advance(time.delta_seconds(), background.0);
+        }
+    }
+}
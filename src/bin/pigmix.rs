@@ -0,0 +1,178 @@
+//! `pigmix` – mix colors as pigments from the command line.
+//!
+//! ```text
+//! pigmix mix "#fcd300" "#000060" 0.5
+//! pigmix gradient gold navy --steps 32 --png gradient.png
+//! pigmix chart palette.json chart.png
+//! pigmix recipe "#4a7023" --palette paints.json
+//! ```
+//!
+//! Colors can be given in any CSS syntax, e.g. `gold`, `#ffd700` or
+//! `rgb(255 215 0)`.
+use clap::{Parser, Subcommand};
+use pigment_mixing::{
+    mixing_chart_image, Gradient, GradientImageOptions, Palette, Pigment,
+    RecipeOptions,
+};
+use std::{
+    error::Error,
+    fs,
+    io::{self, IsTerminal},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+#[derive(Parser)]
+#[command(version, about = "Mix colors as pigments")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the mix of two colors as hex.
+    Mix {
+        /// The first color.
+        a: String,
+        /// The second color.
+        b: String,
+        /// How much of the second color to mix in, `0.0..=1.0`.
+        #[arg(default_value_t = 0.5)]
+        ratio: f32,
+    },
+    /// Print, or render, a gradient between two colors.
+    Gradient {
+        /// The start color.
+        a: String,
+        /// The end color.
+        b: String,
+        /// The number of colors, both ends included.
+        #[arg(long, default_value_t = 8)]
+        steps: usize,
+        /// Render the gradient to this PNG instead of printing it.
+        #[arg(long)]
+        png: Option<PathBuf>,
+        /// The height of the PNG in pixels.
+        #[arg(long, default_value_t = 32)]
+        height: u32,
+    },
+    /// Render the pairwise mixing chart of a JSON palette to a PNG.
+    Chart {
+        /// The palette, as written by `Palette::to_json()`.
+        palette: PathBuf,
+        /// The PNG to write.
+        out: PathBuf,
+        /// How much of the column color to mix in, `0.0..=1.0`.
+        #[arg(long, default_value_t = 0.5)]
+        ratio: f32,
+        /// The size of a chart cell in pixels.
+        #[arg(long, default_value_t = 64)]
+        cell_size: usize,
+    },
+    /// Print how to mix a color from the paints of a JSON palette.
+    Recipe {
+        /// The color to mix.
+        target: String,
+        /// The paints, as written by `Palette::to_json()`.
+        #[arg(long)]
+        palette: PathBuf,
+        /// The largest number of distinct paints to use.
+        #[arg(long, default_value_t = 3)]
+        max_paints: usize,
+        /// The largest number of parts in total.
+        #[arg(long, default_value_t = 10)]
+        max_parts: u32,
+    },
+}
+
+/// Formats `srgb` as a CSS hex color.
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Prints `pigment` as hex, preceded by a swatch when writing to a terminal.
+fn print_color(pigment: Pigment) {
+    if io::stdout().is_terminal() {
+        println!("{} {}", pigment.ansi_swatch(), hex(pigment.into()));
+    } else {
+        println!("{}", hex(pigment.into()));
+    }
+}
+
+fn run(command: Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Mix { a, b, ratio } => {
+            let [r, g, b] = pigment_mixing::mix_css(&a, &b, ratio)?;
+            print_color(Pigment::from_srgb_u8(r, g, b));
+        }
+        Command::Gradient {
+            a,
+            b,
+            steps,
+            png,
+            height,
+        } => {
+            let gradient = Gradient::new()
+                .with_stop(0.0, Pigment::parse(&a)?)
+                .with_stop(1.0, Pigment::parse(&b)?);
+            let steps = steps.max(2);
+
+            match png {
+                Some(path) => gradient.to_png(
+                    path,
+                    steps as _,
+                    height.max(1),
+                    &GradientImageOptions::default(),
+                )?,
+                None => gradient.samples(steps).for_each(print_color),
+            }
+        }
+        Command::Chart {
+            palette,
+            out,
+            ratio,
+            cell_size,
+        } => {
+            let palette = Palette::from_json(&fs::read_to_string(palette)?)?;
+            mixing_chart_image(&palette, ratio, cell_size).save(out)?;
+        }
+        Command::Recipe {
+            target,
+            palette,
+            max_paints,
+            max_parts,
+        } => {
+            let target = Pigment::parse(&target)?;
+            let palette = Palette::from_json(&fs::read_to_string(palette)?)?;
+            let recipe = palette
+                .recipe_sparse(
+                    &target,
+                    &RecipeOptions {
+                        max_paints,
+                        ..Default::default()
+                    },
+                )
+                .ok_or("the palette has no usable paints")?;
+            let parts = recipe.to_parts(&palette, &target, max_parts);
+
+            println!("{}", parts);
+            println!(
+                "ΔE {:.2} (exact weights {:.2})",
+                parts.delta_e, recipe.delta_e
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("pigmix: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
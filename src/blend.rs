@@ -0,0 +1,130 @@
+use crate::{glaze, Pigment, PigmentAlpha};
+
+/// Rec. 709 luminance coefficients of linear sRGB.
+const LUMINANCE: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+/// How paint is combined with the paint below it, e.g. by a
+/// [`Layer`](crate::Layer).
+///
+/// These are pigment analogues of the usual compositing modes. Modes other
+/// than [`Mix`](BlendMode::Mix) and [`Glaze`](BlendMode::Glaze) combine the
+/// resolved linear sRGB colors and mix the result into the base as pigment,
+/// so partial amounts still blend like paint.
+///
+/// ```
+/// # use pigment_mixing::{BlendMode, Pigment};
+/// let yellow = Pigment::from_srgb_u8(254, 236, 0);
+/// let blue = Pigment::from_srgb_u8(0, 33, 133);
+///
+/// let darker = BlendMode::Darken.blend(&yellow, &blue, 1.0);
+/// assert_eq!(blue, darker);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// Mixes the paint into the base as pigment.
+    #[default]
+    Mix,
+    /// Puts the paint on top as a thin transparent film. See [`glaze()`].
+    Glaze,
+    /// Multiplies the colors of the paint and the base in linear sRGB, like
+    /// stacked filters, then mixes the result in as pigment.
+    Multiply,
+    /// Multiplies the inverted colors in linear sRGB and inverts the result,
+    /// then mixes it in as pigment. The inverse of
+    /// [`Multiply`](BlendMode::Multiply): it only ever lightens.
+    Screen,
+    /// [`Multiply`](BlendMode::Multiply) where the base is dark and
+    /// [`Screen`](BlendMode::Screen) where it is light, per linear sRGB
+    /// channel. Increases contrast.
+    Overlay,
+    /// Mixes in the paint where its luminance is lower than the base's.
+    Darken,
+    /// Mixes in the paint where its luminance is higher than the base's.
+    Lighten,
+}
+
+impl BlendMode {
+    /// Combines `paint` with `base` using `amount`, from `0.0` to `1.0`, of
+    /// the mode.
+    ///
+    /// An `amount` of `0.0` returns `base`.
+    pub fn blend(
+        self,
+        base: &Pigment,
+        paint: &Pigment,
+        amount: f32,
+    ) -> Pigment {
+        let per_channel = |f: fn(f32, f32) -> f32| {
+            let a: [f32; 3] = (*base).into();
+            let b: [f32; 3] = (*paint).into();
+
+            Pigment::from_linear_srgb(
+                f(a[0], b[0]),
+                f(a[1], b[1]),
+                f(a[2], b[2]),
+            )
+        };
+
+        let blended = match self {
+            BlendMode::Mix => *paint,
+            BlendMode::Glaze => return glaze(base, paint, amount),
+            BlendMode::Multiply => per_channel(|a, b| a * b),
+            BlendMode::Screen => per_channel(screen),
+            BlendMode::Overlay => per_channel(|a, b| {
+                if a < 0.5 {
+                    2.0 * a * b
+                } else {
+                    screen(2.0 * a - 1.0, b)
+                }
+            }),
+            BlendMode::Darken | BlendMode::Lighten => {
+                let darker = luminance(paint) < luminance(base);
+                if darker == (BlendMode::Darken == self) {
+                    *paint
+                } else {
+                    *base
+                }
+            }
+        };
+
+        Pigment::from_mix(*base, blended, amount)
+    }
+
+    /// Combines `paint` with `base` like [`blend()`](BlendMode::blend),
+    /// taking the opacity (covering power) of the paints into account.
+    ///
+    /// [`Mix`](BlendMode::Mix) is [`PigmentAlpha::mix()`]. The other modes
+    /// scale `amount` by the opacity of `paint` and keep the opacity of
+    /// `base`.
+    pub fn blend_alpha(
+        self,
+        base: &PigmentAlpha,
+        paint: &PigmentAlpha,
+        amount: f32,
+    ) -> PigmentAlpha {
+        match self {
+            BlendMode::Mix => base.mix(paint, amount),
+            _ => PigmentAlpha {
+                pigment: self.blend(
+                    &base.pigment,
+                    &paint.pigment,
+                    amount.clamp(0.0, 1.0) * paint.opacity,
+                ),
+                opacity: base.opacity,
+            },
+        }
+    }
+}
+
+/// Screens the linear sRGB channel `b` over `a`.
+#[inline]
+fn screen(a: f32, b: f32) -> f32 {
+    1.0 - (1.0 - a) * (1.0 - b)
+}
+
+/// Returns the luminance of the resolved color of `pigment`.
+#[inline]
+fn luminance(pigment: &Pigment) -> f32 {
+    let rgb: [f32; 3] = (*pigment).into();
+    rgb.iter().zip(LUMINANCE).map(|(c, w)| c * w).sum()
+}
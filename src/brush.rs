@@ -0,0 +1,199 @@
+use crate::noise::ValueNoise;
+use alloc::vec::Vec;
+use num_traits::Float;
+
+/// A brush tip for [`Canvas::stamp()`](crate::Canvas::stamp) and
+/// [`Smudge`](crate::Smudge).
+///
+/// Implement this to use custom stamp shapes.
+pub trait Brush {
+    /// Returns the distance from the center, in pixels, beyond which
+    /// [`footprint()`](Brush::footprint) is zero.
+    fn radius(&self) -> f32;
+
+    /// Returns the strength, from `0.0` to `1.0`, at the offset `dx`, `dy`
+    /// pixels from the center.
+    fn footprint(&self, dx: f32, dy: f32) -> f32;
+
+    /// Returns the fraction, from `0.0` to `1.0`, of the requested flow the
+    /// brush delivers. Defaults to `1.0`.
+    #[inline]
+    fn flow(&self) -> f32 {
+        1.0
+    }
+
+    /// Returns the fraction of the radius, from `0.0` to `1.0`, that
+    /// deposits paint at full strength. Defaults to `1.0`.
+    #[inline]
+    fn hardness(&self) -> f32 {
+        1.0
+    }
+}
+
+/// Returns the strength of a round tip at `distance` pixels from its center.
+fn round_footprint(radius: f32, hardness: f32, distance: f32) -> f32 {
+    let inner = hardness * radius;
+    if distance <= inner {
+        1.0
+    } else if radius <= distance {
+        0.0
+    } else {
+        let t = 1.0 - (distance - inner) / (radius - inner);
+        t * t * (3.0 - 2.0 * t)
+    }
+}
+
+/// A round brush tip with a soft edge.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RoundBrush {
+    /// The radius in pixels.
+    pub radius: f32,
+    /// The fraction of the radius, from `0.0` to `1.0`, that deposits paint at
+    /// full strength. The rest falls off smoothly.
+    pub hardness: f32,
+    /// The fraction of the requested flow the brush delivers.
+    pub flow: f32,
+}
+
+impl RoundBrush {
+    /// Constructs a `RoundBrush` with full flow. The `hardness` is clamped to
+    /// `0.0..=1.0`.
+    #[inline]
+    pub fn new(radius: f32, hardness: f32) -> Self {
+        Self {
+            radius: radius.max(0.0),
+            hardness: hardness.clamp(0.0, 1.0),
+            flow: 1.0,
+        }
+    }
+
+    /// Sets the flow and returns the brush.
+    #[inline]
+    pub fn with_flow(mut self, flow: f32) -> Self {
+        self.flow = flow.clamp(0.0, 1.0);
+        self
+    }
+}
+
+impl Brush for RoundBrush {
+    #[inline]
+    fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    #[inline]
+    fn footprint(&self, dx: f32, dy: f32) -> f32 {
+        round_footprint(
+            self.radius,
+            self.hardness,
+            Float::sqrt(dx * dx + dy * dy),
+        )
+    }
+
+    #[inline]
+    fn flow(&self) -> f32 {
+        self.flow
+    }
+
+    #[inline]
+    fn hardness(&self) -> f32 {
+        self.hardness
+    }
+}
+
+/// A round brush tip modulated by a grayscale texture.
+///
+/// The texture is stretched over the square enclosing the tip.
+///
+/// ```
+/// # use pigment_mixing::{Canvas, Pigment, Substrate, TexturedBrush};
+/// let mut canvas = Canvas::new(64, 64, Substrate::default());
+/// let brush = TexturedBrush::bristles(16.0, 0.5, 7);
+///
+/// canvas.stamp(&brush, (32.0, 32.0), &Pigment::from_srgb_u8(25, 0, 89), 1.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TexturedBrush {
+    round: RoundBrush,
+    width: usize,
+    height: usize,
+    texture: Vec<f32>,
+}
+
+impl TexturedBrush {
+    /// Constructs a `TexturedBrush` from a round tip and a `width` × `height`
+    /// texture with values from `0.0` to `1.0` in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// If `texture` does not hold exactly `width` × `height` values or is
+    /// empty.
+    pub fn new(
+        round: RoundBrush,
+        width: usize,
+        height: usize,
+        texture: Vec<f32>,
+    ) -> Self {
+        assert_eq!(width * height, texture.len(), "texture has the wrong size");
+        assert!(!texture.is_empty(), "texture is empty");
+
+        Self {
+            round,
+            width,
+            height,
+            texture,
+        }
+    }
+
+    /// Constructs a brush whose texture resembles the streaks left by the
+    /// bristles of a flat brush, running along the y axis.
+    ///
+    /// `seed` selects the pattern.
+    pub fn bristles(radius: f32, hardness: f32, seed: u64) -> Self {
+        const SIZE: usize = 64;
+        let noise = ValueNoise::new(seed);
+
+        let texture = (0..SIZE * SIZE)
+            .map(|i| {
+                let (x, y) = ((i % SIZE) as f32, (i / SIZE) as f32);
+                let bristle = noise.uniform(x * 0.5, y * 0.03);
+                (2.0 * bristle - 0.3).clamp(0.0, 1.0)
+            })
+            .collect();
+
+        Self::new(RoundBrush::new(radius, hardness), SIZE, SIZE, texture)
+    }
+}
+
+impl Brush for TexturedBrush {
+    #[inline]
+    fn radius(&self) -> f32 {
+        self.round.radius
+    }
+
+    fn footprint(&self, dx: f32, dy: f32) -> f32 {
+        let strength = self.round.footprint(dx, dy);
+        if strength <= 0.0 {
+            return 0.0;
+        }
+
+        // Map the offset to texture coordinates, nearest neighbor.
+        let scale = 0.5 / self.round.radius.max(f32::EPSILON);
+        let u = ((dx * scale + 0.5) * self.width as f32) as usize;
+        let v = ((dy * scale + 0.5) * self.height as f32) as usize;
+
+        strength
+            * self.texture
+                [v.min(self.height - 1) * self.width + u.min(self.width - 1)]
+    }
+
+    #[inline]
+    fn flow(&self) -> f32 {
+        self.round.flow
+    }
+
+    #[inline]
+    fn hardness(&self) -> f32 {
+        self.round.hardness
+    }
+}
@@ -0,0 +1,236 @@
+use crate::{Pigment, Substrate};
+use alloc::{vec, vec::Vec};
+
+/// A rectangle of pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Region {
+    /// The left edge.
+    pub x: usize,
+    /// The top edge.
+    pub y: usize,
+    /// The width in pixels.
+    pub width: usize,
+    /// The height in pixels.
+    pub height: usize,
+}
+
+impl Region {
+    /// Constructs a `Region`.
+    #[inline]
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the part of the region inside a `width` × `height` image.
+    pub(crate) fn clip(&self, width: usize, height: usize) -> Region {
+        let x = self.x.min(width);
+        let y = self.y.min(height);
+
+        Region {
+            x,
+            y,
+            width: self.width.min(width - x),
+            height: self.height.min(height - y),
+        }
+    }
+}
+
+/// A two dimensional image of [`Pigment`]s.
+///
+/// Keeping paint in latent space, rather than converting to RGB after every
+/// operation, lets later operations mix it as pigment.
+///
+/// ```
+/// # use pigment_mixing::{Pigment, PigmentBuffer};
+/// let mut buffer =
+///     PigmentBuffer::new(64, 64, Pigment::from_srgb_u8(255, 255, 255));
+///
+/// buffer.pixel_mut(10, 20).mix(Pigment::from_srgb_u8(25, 0, 89), 0.5);
+///
+/// let rgb = buffer.to_srgb_u8();
+/// assert_eq!(64 * 64 * 3, rgb.len());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PigmentBuffer {
+    width: usize,
+    height: usize,
+    pixels: Vec<Pigment>,
+}
+
+impl PigmentBuffer {
+    /// Constructs a `width` × `height` buffer filled with `pigment`.
+    pub fn new(width: usize, height: usize, pigment: Pigment) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![pigment; width * height],
+        }
+    }
+
+    /// Constructs a buffer from `u8` component encoded sRGB (gamma 2.2)
+    /// pixels in row-major order with three bytes per pixel.
+    ///
+    /// # Panics
+    ///
+    /// If `srgb` does not hold exactly `width` × `height` pixels.
+    pub fn from_srgb_u8(width: usize, height: usize, srgb: &[u8]) -> Self {
+        assert_eq!(width * height * 3, srgb.len(), "input has the wrong size");
+
+        Self {
+            width,
+            height,
+            pixels: srgb
+                .chunks_exact(3)
+                .map(|rgb| Pigment::from_srgb_u8(rgb[0], rgb[1], rgb[2]))
+                .collect(),
+        }
+    }
+
+    /// Returns the width in pixels.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height in pixels.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the pixels in row-major order.
+    #[inline]
+    pub fn pixels(&self) -> &[Pigment] {
+        &self.pixels
+    }
+
+    /// Returns the pixels in row-major order.
+    #[inline]
+    pub fn pixels_mut(&mut self) -> &mut [Pigment] {
+        &mut self.pixels
+    }
+
+    /// Returns the pixel at `x`, `y`.
+    ///
+    /// # Panics
+    ///
+    /// If the position is outside the buffer.
+    #[inline]
+    pub fn pixel(&self, x: usize, y: usize) -> &Pigment {
+        assert!(x < self.width && y < self.height, "position out of bounds");
+        &self.pixels[y * self.width + x]
+    }
+
+    /// Returns the pixel at `x`, `y`.
+    ///
+    /// # Panics
+    ///
+    /// If the position is outside the buffer.
+    #[inline]
+    pub fn pixel_mut(&mut self, x: usize, y: usize) -> &mut Pigment {
+        assert!(x < self.width && y < self.height, "position out of bounds");
+        &mut self.pixels[y * self.width + x]
+    }
+
+    /// Writes the buffer as `u8` component encoded sRGB (gamma 2.2) pixels
+    /// in row-major order with three bytes per pixel into `out`.
+    ///
+    /// # Panics
+    ///
+    /// If `out` does not hold exactly [`width()`](Self::width) ×
+    /// [`height()`](Self::height) pixels.
+    pub fn to_srgb_u8_into(&self, out: &mut [u8]) {
+        assert_eq!(
+            self.pixels.len() * 3,
+            out.len(),
+            "output has the wrong size"
+        );
+
+        out.chunks_exact_mut(3).zip(&self.pixels).for_each(
+            |(out, &pigment)| {
+                let srgb: [u8; 3] = pigment.into();
+                out.copy_from_slice(&srgb);
+            },
+        );
+    }
+
+    /// Returns the buffer as `u8` component encoded sRGB (gamma 2.2) pixels
+    /// in row-major order with three bytes per pixel.
+    pub fn to_srgb_u8(&self) -> Vec<u8> {
+        let mut out = vec![0; self.pixels.len() * 3];
+        self.to_srgb_u8_into(&mut out);
+
+        out
+    }
+
+    /// Writes the buffer resolved against `substrate` as `u8` component
+    /// encoded sRGB (gamma 2.2) pixels in row-major order with three bytes per
+    /// pixel into `out`.
+    ///
+    /// `thickness` holds the paint thickness of every pixel. See
+    /// [`Substrate::resolve()`].
+    ///
+    /// # Panics
+    ///
+    /// If `thickness` does not hold one value per pixel or `out` does not
+    /// hold exactly [`width()`](Self::width) × [`height()`](Self::height)
+    /// pixels.
+    pub fn to_srgb_u8_over_into(
+        &self,
+        substrate: &Substrate,
+        thickness: &[f32],
+        out: &mut [u8],
+    ) {
+        assert_eq!(self.pixels.len(), thickness.len(), "one value per pixel");
+        assert_eq!(
+            self.pixels.len() * 3,
+            out.len(),
+            "output has the wrong size"
+        );
+
+        out.chunks_exact_mut(3)
+            .zip(self.pixels.iter().zip(thickness))
+            .for_each(|(out, (pigment, &thickness))| {
+                let srgb: [u8; 3] =
+                    substrate.resolve(pigment, thickness).into();
+                out.copy_from_slice(&srgb);
+            });
+    }
+
+    /// Returns the buffer resolved against `substrate` as `u8` component
+    /// encoded sRGB (gamma 2.2) pixels.
+    ///
+    /// See [`to_srgb_u8_over_into()`](Self::to_srgb_u8_over_into).
+    pub fn to_srgb_u8_over(
+        &self,
+        substrate: &Substrate,
+        thickness: &[f32],
+    ) -> Vec<u8> {
+        let mut out = vec![0; self.pixels.len() * 3];
+        self.to_srgb_u8_over_into(substrate, thickness, &mut out);
+
+        out
+    }
+
+    /// Calls `f` with the position and pixel of every pixel inside `region`.
+    pub(crate) fn for_each_in(
+        &mut self,
+        region: Region,
+        mut f: impl FnMut(usize, usize, &mut Pigment),
+    ) {
+        let region = region.clip(self.width, self.height);
+
+        for y in region.y..region.y + region.height {
+            let row = &mut self.pixels[y * self.width..(y + 1) * self.width];
+            row[region.x..region.x + region.width]
+                .iter_mut()
+                .enumerate()
+                .for_each(|(x, pixel)| f(region.x + x, y, pixel));
+        }
+    }
+}
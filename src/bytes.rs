@@ -0,0 +1,98 @@
+use crate::Error;
+use alloc::{string::String, vec::Vec};
+
+/// Reads big-endian values from a byte slice.
+pub(crate) struct ByteReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    #[inline]
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    #[inline]
+    pub(crate) fn bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.data.len() < len {
+            return Err(Error::UnexpectedEof);
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+
+        Ok(bytes)
+    }
+
+    #[inline]
+    pub(crate) fn u16(&mut self) -> Result<u16, Error> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    #[inline]
+    pub(crate) fn u32(&mut self) -> Result<u32, Error> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    #[inline]
+    pub(crate) fn f32(&mut self) -> Result<f32, Error> {
+        Ok(f32::from_bits(self.u32()?))
+    }
+
+    /// Reads `len` UTF-16 code units and strips a trailing null terminator.
+    pub(crate) fn utf16(&mut self, len: usize) -> Result<String, Error> {
+        let units = (0..len)
+            .map(|_| self.u16())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(
+            char::decode_utf16(units.into_iter().take_while(|&u| 0 != u))
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect(),
+        )
+    }
+}
+
+/// Writes big-endian values to a byte vector.
+pub(crate) trait ByteWriter {
+    fn put_u16(&mut self, value: u16);
+    fn put_u32(&mut self, value: u32);
+    fn put_f32(&mut self, value: f32);
+    /// Writes `text` as UTF-16 code units followed by a null terminator.
+    fn put_utf16(&mut self, text: &str);
+}
+
+impl ByteWriter for Vec<u8> {
+    #[inline]
+    fn put_u16(&mut self, value: u16) {
+        self.extend_from_slice(&value.to_be_bytes());
+    }
+
+    #[inline]
+    fn put_u32(&mut self, value: u32) {
+        self.extend_from_slice(&value.to_be_bytes());
+    }
+
+    #[inline]
+    fn put_f32(&mut self, value: f32) {
+        self.put_u32(value.to_bits());
+    }
+
+    fn put_utf16(&mut self, text: &str) {
+        text.encode_utf16().for_each(|unit| self.put_u16(unit));
+        self.put_u16(0);
+    }
+}
+
+/// Returns the number of UTF-16 code units of `text` including a null
+/// terminator.
+#[inline]
+pub(crate) fn utf16_len(text: &str) -> usize {
+    text.encode_utf16().count() + 1
+}
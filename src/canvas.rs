@@ -0,0 +1,212 @@
+use crate::{Brush, Pigment, PigmentBuffer, Region, Substrate};
+use alloc::{vec, vec::Vec};
+use num_traits::Float;
+
+/// A surface to paint on.
+///
+/// Paint is accumulated in latent space in a [`PigmentBuffer`] so strokes mix
+/// like real paint. Each pixel also tracks how much paint it holds, which
+/// decides how much of the [`Substrate`] shows through when the canvas is
+/// resolved to RGB.
+///
+/// Fresh paint is wet. Wet paint on the canvas blends with new paint while
+/// dry paint lets new paint sit on top of it. Wetness decays with every call
+/// to [`tick()`](Canvas::tick) by the [drying
+/// rate](Canvas::with_drying_rate).
+///
+/// ```
+/// # use pigment_mixing::{Canvas, Pigment, RoundBrush, Substrate};
+/// let mut canvas = Canvas::new(128, 128, Substrate::default());
+/// let brush = RoundBrush::new(12.0, 0.5);
+///
+/// let yellow = Pigment::from_srgb_u8(254, 236, 0);
+/// let blue = Pigment::from_srgb_u8(25, 0, 89);
+///
+/// for i in 0..32 {
+///     let x = 16.0 + i as f32 * 3.0;
+///     canvas.stamp(&brush, (x, 48.0), &yellow, 0.5);
+///     canvas.stamp(&brush, (x, 64.0), &blue, 0.5);
+/// }
+///
+/// let rgb = canvas.to_srgb_u8();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Canvas {
+    paint: PigmentBuffer,
+    thickness: Vec<f32>,
+    wetness: Vec<f32>,
+    drying_rate: f32,
+    substrate: Substrate,
+}
+
+impl Canvas {
+    /// Constructs a blank `width` × `height` canvas.
+    pub fn new(width: usize, height: usize, substrate: Substrate) -> Self {
+        Self {
+            paint: PigmentBuffer::new(width, height, substrate.pigment),
+            thickness: vec![0.0; width * height],
+            wetness: vec![0.0; width * height],
+            drying_rate: 0.05,
+            substrate,
+        }
+    }
+
+    /// Sets the fraction of its wetness paint loses per
+    /// [`tick()`](Canvas::tick) and returns the canvas.
+    ///
+    /// The default is `0.05`. Use small values like `0.001` for slow drying
+    /// oils, `0.05` for acrylics and `0.1` or more for watercolor on dry
+    /// paper.
+    #[inline]
+    pub fn with_drying_rate(mut self, drying_rate: f32) -> Self {
+        self.drying_rate = drying_rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Lets the paint dry for one time step.
+    pub fn tick(&mut self) {
+        let keep = 1.0 - self.drying_rate;
+        self.wetness.iter_mut().for_each(|wetness| *wetness *= keep);
+    }
+
+    /// Returns the width in pixels.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.paint.width()
+    }
+
+    /// Returns the height in pixels.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.paint.height()
+    }
+
+    /// Returns the paint on the canvas, without the substrate.
+    #[inline]
+    pub fn paint(&self) -> &PigmentBuffer {
+        &self.paint
+    }
+
+    /// Returns the paint thickness of every pixel in row-major order.
+    #[inline]
+    pub fn thickness(&self) -> &[f32] {
+        &self.thickness
+    }
+
+    /// Returns the wetness, from `0.0` (dry) to `1.0` (fresh), of every pixel
+    /// in row-major order.
+    #[inline]
+    pub fn wetness(&self) -> &[f32] {
+        &self.wetness
+    }
+
+    /// Returns the substrate.
+    #[inline]
+    pub fn substrate(&self) -> &Substrate {
+        &self.substrate
+    }
+
+    /// Deposits `pigment` with a single dab of `brush` centered at
+    /// `position`.
+    ///
+    /// `flow`, from `0.0` to `1.0`, is how much paint the dab deposits at the
+    /// center of the brush. It is scaled by the brush's
+    /// [`flow()`](Brush::flow). It adds to the thickness of the paint on the
+    /// canvas and wets it.
+    ///
+    /// On dry paint, the new paint sits on top and is mixed in by the local
+    /// brush strength. On wet paint, it blends into the paint already there
+    /// by amount, see [`Pigment::mix_amounts()`]: a light dab into a thick
+    /// puddle of wet paint barely changes its color.
+    pub fn stamp(
+        &mut self,
+        brush: &(impl Brush + ?Sized),
+        position: (f32, f32),
+        pigment: &Pigment,
+        flow: f32,
+    ) {
+        let flow = flow.clamp(0.0, 1.0) * brush.flow();
+        let region = self.region_around(position, brush.radius());
+        let width = self.width();
+        let (thickness, wetness) = (&mut self.thickness, &mut self.wetness);
+
+        self.paint.for_each_in(region, |x, y, paint| {
+            let (dx, dy) =
+                (x as f32 + 0.5 - position.0, y as f32 + 0.5 - position.1);
+            let amount = flow * brush.footprint(dx, dy);
+            if amount <= 0.0 {
+                return;
+            }
+
+            let index = y * width + x;
+            let (thickness, wetness) =
+                (&mut thickness[index], &mut wetness[index]);
+            if 0.0 < *thickness {
+                let dry = Pigment::from_mix(*paint, *pigment, amount);
+                let wet =
+                    Pigment::mix_amounts(*paint, *thickness, *pigment, amount);
+                *paint = Pigment::from_mix(dry, wet, *wetness);
+            } else {
+                *paint = *pigment;
+            }
+            *thickness += amount;
+            *wetness += (1.0 - *wetness) * amount;
+        });
+    }
+
+    /// Writes the canvas, with the paint resolved against the substrate, as
+    /// `u8` component encoded sRGB (gamma 2.2) pixels in row-major order with
+    /// three bytes per pixel into `out`.
+    ///
+    /// # Panics
+    ///
+    /// If `out` does not hold exactly [`width()`](Self::width) ×
+    /// [`height()`](Self::height) pixels.
+    #[inline]
+    pub fn to_srgb_u8_into(&self, out: &mut [u8]) {
+        self.paint
+            .to_srgb_u8_over_into(&self.substrate, &self.thickness, out);
+    }
+
+    /// Returns the canvas, with the paint resolved against the substrate, as
+    /// `u8` component encoded sRGB (gamma 2.2) pixels in row-major order with
+    /// three bytes per pixel.
+    #[inline]
+    pub fn to_srgb_u8(&self) -> Vec<u8> {
+        self.paint.to_srgb_u8_over(&self.substrate, &self.thickness)
+    }
+
+    /// Returns the canvas, with the paint resolved against the substrate, as
+    /// an [`image::RgbImage`].
+    #[cfg(feature = "image")]
+    pub fn to_rgb_image(&self) -> image::RgbImage {
+        image::RgbImage::from_raw(
+            self.width() as _,
+            self.height() as _,
+            self.to_srgb_u8(),
+        )
+        .expect("buffer size matches the image dimensions")
+    }
+
+    /// Returns the paint, thickness and wetness for modification by tools.
+    #[inline]
+    pub(crate) fn state_mut(
+        &mut self,
+    ) -> (&mut PigmentBuffer, &mut [f32], &mut [f32]) {
+        (&mut self.paint, &mut self.thickness, &mut self.wetness)
+    }
+
+    /// Returns the pixels within `radius` of `position`.
+    pub(crate) fn region_around(
+        &self,
+        position: (f32, f32),
+        radius: f32,
+    ) -> Region {
+        let x0 = Float::floor(position.0 - radius).max(0.0) as usize;
+        let y0 = Float::floor(position.1 - radius).max(0.0) as usize;
+        let x1 = Float::ceil(position.0 + radius).max(0.0) as usize;
+        let y1 = Float::ceil(position.1 + radius).max(0.0) as usize;
+
+        Region::new(x0, y0, x1.saturating_sub(x0), y1.saturating_sub(y0))
+    }
+}
@@ -0,0 +1,148 @@
+//! A C ABI for use from C, C++, Swift and other languages.
+//!
+//! Build a shared or static library with
+//! `cargo rustc --release --features capi --crate-type cdylib` (or
+//! `staticlib`). The matching header is `include/pigment_mixing.h`,
+//! generated with `cbindgen --config cbindgen.toml --output
+//! include/pigment_mixing.h`.
+//!
+//! Colors are encoded sRGB (gamma 2.2) `uint8_t[3]`. Latents are
+//! `float[PM_LATENT_SIZE]`. All pointers must be valid for the documented
+//! number of elements. Null pointers make the functions return without
+//! doing anything.
+use crate::{gradient_into_slice, mix_srgb_u8, pigment::PIGMENT_LEN, Pigment};
+use core::slice;
+
+/// The number of floats in a latent.
+pub const PM_LATENT_SIZE: usize = PIGMENT_LEN;
+
+/// Mixes two colors.
+///
+/// # Safety
+///
+/// `a`, `b` and `out` must each point to three `uint8_t`s.
+#[no_mangle]
+pub unsafe extern "C" fn pm_mix_srgb8(
+    a: *const u8,
+    b: *const u8,
+    ratio: f32,
+    out: *mut u8,
+) {
+    pm_mix_srgb8_buffer(a, b, ratio, out, 1);
+}
+
+/// Mixes `pixel_count` pairs of colors, e.g. two images, pixel by pixel.
+///
+/// `out` may be the same as `a` or `b`.
+///
+/// # Safety
+///
+/// `a`, `b` and `out` must each point to `3 * pixel_count` `uint8_t`s.
+#[no_mangle]
+pub unsafe extern "C" fn pm_mix_srgb8_buffer(
+    a: *const u8,
+    b: *const u8,
+    ratio: f32,
+    out: *mut u8,
+    pixel_count: usize,
+) {
+    if a.is_null() || b.is_null() || out.is_null() {
+        return;
+    }
+
+    for i in 0..pixel_count {
+        let (a, b, out) = (a.add(3 * i), b.add(3 * i), out.add(3 * i));
+        let srgb = mix_srgb_u8(
+            &[*a, *a.add(1), *a.add(2)],
+            &[*b, *b.add(1), *b.add(2)],
+            ratio,
+        );
+        out.copy_from_nonoverlapping(srgb.as_ptr(), 3);
+    }
+}
+
+/// Writes `count` evenly spaced mixes from `a` to `b`, both ends included,
+/// to `out`.
+///
+/// # Safety
+///
+/// `a` and `b` must each point to three `uint8_t`s, `out` to `3 * count`
+/// `uint8_t`s.
+#[no_mangle]
+pub unsafe extern "C" fn pm_gradient_srgb8(
+    a: *const u8,
+    b: *const u8,
+    out: *mut u8,
+    count: usize,
+) {
+    if a.is_null() || b.is_null() || out.is_null() {
+        return;
+    }
+
+    gradient_into_slice(
+        &*a.cast::<[u8; 3]>(),
+        &*b.cast::<[u8; 3]>(),
+        slice::from_raw_parts_mut(out.cast::<[u8; 3]>(), count),
+    );
+}
+
+/// Converts a color to its latent.
+///
+/// # Safety
+///
+/// `srgb` must point to three `uint8_t`s, `latent` to `PM_LATENT_SIZE`
+/// `float`s.
+#[no_mangle]
+pub unsafe extern "C" fn pm_srgb_to_latent(srgb: *const u8, latent: *mut f32) {
+    if srgb.is_null() || latent.is_null() {
+        return;
+    }
+
+    let pigment = Pigment::from_srgb_u8(*srgb, *srgb.add(1), *srgb.add(2));
+    let values: [f32; PIGMENT_LEN] = pigment.into();
+    latent.copy_from_nonoverlapping(values.as_ptr(), PIGMENT_LEN);
+}
+
+/// Converts a latent to a color.
+///
+/// # Safety
+///
+/// `latent` must point to `PM_LATENT_SIZE` `float`s, `srgb` to three
+/// `uint8_t`s.
+#[no_mangle]
+pub unsafe extern "C" fn pm_latent_to_srgb(latent: *const f32, srgb: *mut u8) {
+    if latent.is_null() || srgb.is_null() {
+        return;
+    }
+
+    let pigment = Pigment::from(latent.cast::<[f32; PIGMENT_LEN]>().read());
+    let values: [u8; 3] = pigment.into();
+    srgb.copy_from_nonoverlapping(values.as_ptr(), 3);
+}
+
+/// Mixes two latents. This is a linear interpolation.
+///
+/// `out` may be the same as `a` or `b`.
+///
+/// # Safety
+///
+/// `a`, `b` and `out` must each point to `PM_LATENT_SIZE` `float`s.
+#[no_mangle]
+pub unsafe extern "C" fn pm_mix_latent(
+    a: *const f32,
+    b: *const f32,
+    ratio: f32,
+    out: *mut f32,
+) {
+    if a.is_null() || b.is_null() || out.is_null() {
+        return;
+    }
+
+    let mixed: [f32; PIGMENT_LEN] = Pigment::from_mix(
+        Pigment::from(a.cast::<[f32; PIGMENT_LEN]>().read()),
+        Pigment::from(b.cast::<[f32; PIGMENT_LEN]>().read()),
+        ratio,
+    )
+    .into();
+    out.copy_from_nonoverlapping(mixed.as_ptr(), PIGMENT_LEN);
+}
@@ -0,0 +1,204 @@
+use crate::{metrics::delta_e_ok, sample_position, Palette, Pigment};
+use alloc::{format, string::String, vec, vec::Vec};
+
+/// Renders the pairwise mixing chart of `palette` into `out`.
+///
+/// The chart is a grid of `n` × `n` square cells, `cell_size` pixels wide,
+/// where `n` is the number of palette entries. The cell in row `i` and column
+/// `j` shows entry `i` mixed with entry `j` using `ratio`. With the default
+/// ratio of `0.5` the chart is symmetric and the diagonal shows the unmixed
+/// paints.
+///
+/// `out` receives `u8` component encoded sRGB (gamma 2.2) pixels in row-major
+/// order with three bytes per pixel.
+///
+/// # Panics
+///
+/// If `out` does not hold exactly `(n * cell_size)²` pixels.
+pub fn mixing_chart_into(
+    palette: &Palette,
+    ratio: f32,
+    cell_size: usize,
+    out: &mut [u8],
+) {
+    let n = palette.len();
+    let width = n * cell_size;
+    assert_eq!(width * width * 3, out.len(), "output has the wrong size");
+
+    if 0 == n || 0 == cell_size {
+        return;
+    }
+
+    for (row, a) in palette.iter().enumerate() {
+        for (column, b) in palette.iter().enumerate() {
+            let srgb: [u8; 3] =
+                Pigment::from_mix(a.pigment, b.pigment, ratio).into();
+
+            for y in row * cell_size..(row + 1) * cell_size {
+                let start = 3 * (y * width + column * cell_size);
+                out[start..start + 3 * cell_size]
+                    .chunks_exact_mut(3)
+                    .for_each(|pixel| pixel.copy_from_slice(&srgb));
+            }
+        }
+    }
+}
+
+/// Renders the pairwise mixing chart of `palette`.
+///
+/// Returns the pixels and the width (which equals the height) of the image.
+/// See [`mixing_chart_into()`] for details.
+pub fn mixing_chart(
+    palette: &Palette,
+    ratio: f32,
+    cell_size: usize,
+) -> (Vec<u8>, usize) {
+    let width = palette.len() * cell_size;
+    let mut out = vec![0; width * width * 3];
+    mixing_chart_into(palette, ratio, cell_size, &mut out);
+
+    (out, width)
+}
+
+/// Renders the pairwise mixing chart of `palette` as an [`image::RgbImage`].
+///
+/// See [`mixing_chart_into()`] for details.
+#[cfg(feature = "image")]
+pub fn mixing_chart_image(
+    palette: &Palette,
+    ratio: f32,
+    cell_size: usize,
+) -> image::RgbImage {
+    let (pixels, width) = mixing_chart(palette, ratio, cell_size);
+
+    image::RgbImage::from_raw(width as _, width as _, pixels)
+        .expect("buffer size matches the image dimensions")
+}
+
+/// The order of the strips of a [`StripSheet`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StripOrder {
+    /// Pairs in palette order: `(0, 1)`, `(0, 2)`, …, `(1, 2)`, ….
+    #[default]
+    Palette,
+    /// Pairs sorted by the perceptual distance of their end points, largest
+    /// first.
+    Distance,
+}
+
+/// Layout options for [`strip_sheet()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StripSheetOptions {
+    /// The number of discrete steps per strip, including both ends.
+    pub steps: usize,
+    /// The width of each step in pixels.
+    pub step_width: usize,
+    /// The height of each strip in pixels.
+    pub strip_height: usize,
+    /// The vertical space between strips in pixels. Filled with white.
+    pub gap: usize,
+    /// The order of the strips.
+    pub order: StripOrder,
+}
+
+impl Default for StripSheetOptions {
+    fn default() -> Self {
+        Self {
+            steps: 9,
+            step_width: 32,
+            strip_height: 32,
+            gap: 8,
+            order: StripOrder::default(),
+        }
+    }
+}
+
+/// The label of a strip in a [`StripSheet`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StripLabel {
+    /// The names of the two palette entries, e.g. `Cadmium Yellow →
+    /// Ultramarine`.
+    pub text: String,
+    /// The index of the first pixel row of the strip.
+    pub y: usize,
+}
+
+/// A rendered sheet of gradient strips.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StripSheet {
+    /// `u8` component encoded sRGB (gamma 2.2) pixels in row-major order with
+    /// three bytes per pixel.
+    pub pixels: Vec<u8>,
+    /// The width of the sheet in pixels.
+    pub width: usize,
+    /// The height of the sheet in pixels.
+    pub height: usize,
+    /// One label per strip, from top to bottom.
+    ///
+    /// This crate does not render text. Use these to draw the labels with the
+    /// text renderer of your choice.
+    pub labels: Vec<StripLabel>,
+}
+
+/// Renders a sheet with one gradient strip per pair of `palette` entries.
+///
+/// Each strip mixes one entry into the other in `options.steps` discrete
+/// steps.
+pub fn strip_sheet(
+    palette: &Palette,
+    options: &StripSheetOptions,
+) -> StripSheet {
+    let entries = palette.entries();
+
+    let mut pairs: Vec<(usize, usize)> = (0..entries.len())
+        .flat_map(|a| (a + 1..entries.len()).map(move |b| (a, b)))
+        .collect();
+
+    if StripOrder::Distance == options.order {
+        pairs.sort_by(|&(a0, b0), &(a1, b1)| {
+            delta_e_ok(&entries[a1].pigment, &entries[b1].pigment).total_cmp(
+                &delta_e_ok(&entries[a0].pigment, &entries[b0].pigment),
+            )
+        });
+    }
+
+    let width = options.steps * options.step_width;
+    let pitch = options.strip_height + options.gap;
+    let height = (pairs.len() * pitch).saturating_sub(options.gap);
+
+    let mut pixels = vec![u8::MAX; width * height * 3];
+    let mut labels = Vec::with_capacity(pairs.len());
+
+    for (strip, &(a, b)) in pairs.iter().enumerate() {
+        let (a, b) = (&entries[a], &entries[b]);
+        let top = strip * pitch;
+
+        for step in 0..options.steps {
+            let srgb: [u8; 3] = Pigment::from_mix(
+                a.pigment,
+                b.pigment,
+                sample_position(step, options.steps),
+            )
+            .into();
+
+            for y in top..top + options.strip_height {
+                let start = 3 * (y * width + step * options.step_width);
+                pixels[start..start + 3 * options.step_width]
+                    .chunks_exact_mut(3)
+                    .for_each(|pixel| pixel.copy_from_slice(&srgb));
+            }
+        }
+
+        labels.push(StripLabel {
+            text: format!("{} → {}", a.name, b.name),
+            y: top,
+        });
+    }
+
+    StripSheet {
+        pixels,
+        width,
+        height,
+        labels,
+    }
+}
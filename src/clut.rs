@@ -0,0 +1,126 @@
+use crate::{sample_position, Pigment};
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt::Write;
+
+/// Mixes the encoded sRGB grid point `(i, j, k)` of a lattice with `size`
+/// points per axis toward `target`.
+fn toned(
+    target: Pigment,
+    ratio: f32,
+    size: usize,
+    i: usize,
+    j: usize,
+    k: usize,
+) -> Pigment {
+    Pigment::from_mix(
+        Pigment::from_srgb(
+            sample_position(i, size),
+            sample_position(j, size),
+            sample_position(k, size),
+        ),
+        target,
+        ratio,
+    )
+}
+
+/// Writes the operation "mix toward `target` by `ratio`" as the contents of
+/// an Adobe/Resolve 3D LUT (`.cube`) file.
+///
+/// The LUT has `size` points per axis (commonly `17`, `33` or `65`) and maps
+/// encoded sRGB (gamma 2.2) input to encoded sRGB output.
+///
+/// # Panics
+///
+/// If `size` is less than `2`.
+pub fn mix_to_cube(
+    target: &Pigment,
+    ratio: f32,
+    size: usize,
+    title: &str,
+) -> String {
+    assert!(1 < size, "a LUT needs at least two points per axis");
+
+    let mut cube = String::new();
+
+    let _ = writeln!(cube, "TITLE \"{}\"", title.replace('"', "'"));
+    let _ = writeln!(cube, "LUT_3D_SIZE {}", size);
+    let _ = writeln!(cube, "DOMAIN_MIN 0.0 0.0 0.0");
+    let _ = writeln!(cube, "DOMAIN_MAX 1.0 1.0 1.0");
+
+    // Red varies fastest.
+    for k in 0..size {
+        for j in 0..size {
+            for i in 0..size {
+                let srgb = toned(*target, ratio, size, i, j, k).to_srgb();
+                let _ = writeln!(
+                    cube,
+                    "{:.6} {:.6} {:.6}",
+                    srgb[0].clamp(0.0, 1.0),
+                    srgb[1].clamp(0.0, 1.0),
+                    srgb[2].clamp(0.0, 1.0),
+                );
+            }
+        }
+    }
+
+    cube
+}
+
+/// Renders the operation "mix toward `target` by `ratio`" as a HALD CLUT.
+///
+/// A HALD CLUT of `level` is a square image of `level³` × `level³` pixels
+/// that holds a LUT with `level²` points per axis, red varying fastest. Apply
+/// it with e.g. ImageMagick's `-hald-clut`, G'MIC or darktable.
+///
+/// Returns `u8` component encoded sRGB (gamma 2.2) pixels in row-major order
+/// with three bytes per pixel and the width (which equals the height) of the
+/// image.
+///
+/// # Panics
+///
+/// If `level` is less than `2`.
+pub fn mix_to_hald_clut(
+    target: &Pigment,
+    ratio: f32,
+    level: usize,
+) -> (Vec<u8>, usize) {
+    assert!(1 < level, "a HALD CLUT needs a level of at least two");
+
+    let size = level * level;
+    let width = size * level;
+    let mut pixels = vec![0; width * width * 3];
+
+    pixels
+        .chunks_exact_mut(3)
+        .enumerate()
+        .for_each(|(index, pixel)| {
+            let srgb: [u8; 3] = toned(
+                *target,
+                ratio,
+                size,
+                index % size,
+                index / size % size,
+                index / (size * size),
+            )
+            .into();
+            pixel.copy_from_slice(&srgb);
+        });
+
+    (pixels, width)
+}
+
+/// Renders the operation "mix toward `target` by `ratio`" as a HALD CLUT
+/// [`image::RgbImage`].
+///
+/// See [`mix_to_hald_clut()`] for details.
+#[cfg(feature = "image")]
+pub fn mix_to_hald_clut_image(
+    target: &Pigment,
+    ratio: f32,
+    level: usize,
+) -> image::RgbImage {
+    let (pixels, width) = mix_to_hald_clut(target, ratio, level);
+
+    image::RgbImage::from_raw(width as _, width as _, pixels)
+        .expect("buffer size matches the image dimensions")
+}
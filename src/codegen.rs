@@ -0,0 +1,63 @@
+//! Generation of `Pigment` constants in build scripts.
+use crate::{pigment::PIGMENT_LEN, Pigment};
+use core::fmt::{Result, Write};
+
+/// Writes a `pub const` for each named pigment to `out`, as Rust source.
+///
+/// Converting a color to a [`Pigment`] samples the Mixbox model, which can
+/// not be done in a `const` context. Call this from a build script instead
+/// and [`include!`] the result. The constants are built with
+/// [`Pigment::from_latent()`], so using them costs nothing at runtime.
+///
+/// The latents are those of the backend the build dependency is built with.
+///
+/// In `Cargo.toml`:
+///
+/// ```toml
+/// [build-dependencies]
+/// pigment-mixing = { version = "0.1", features = ["codegen"] }
+/// ```
+///
+/// In `build.rs`:
+///
+/// ```no_run
+/// use pigment_mixing::{write_pigment_consts, Pigment};
+/// use std::{env, fs, path::Path};
+///
+/// let mut source = String::new();
+/// write_pigment_consts(
+///     &mut source,
+///     &[
+///         ("CADMIUM_YELLOW", Pigment::from_srgb_u8(254, 236, 0)),
+///         ("ULTRAMARINE_BLUE", Pigment::from_srgb_u8(25, 0, 89)),
+///     ],
+/// )
+/// .unwrap();
+///
+/// let out_dir = env::var_os("OUT_DIR").unwrap();
+/// fs::write(Path::new(&out_dir).join("pigments.rs"), source).unwrap();
+/// ```
+///
+/// In the crate:
+///
+/// ```ignore
+/// include!(concat!(env!("OUT_DIR"), "/pigments.rs"));
+///
+/// static PALETTE: [pigment_mixing::Pigment; 2] =
+///     [CADMIUM_YELLOW, ULTRAMARINE_BLUE];
+/// ```
+pub fn write_pigment_consts<W: Write>(
+    out: &mut W,
+    pigments: &[(&str, Pigment)],
+) -> Result {
+    for (name, pigment) in pigments {
+        let latent: [f32; PIGMENT_LEN] = (*pigment).into();
+        writeln!(
+            out,
+            "pub const {name}: ::pigment_mixing::Pigment = \
+             ::pigment_mixing::Pigment::from_latent({latent:?});"
+        )?;
+    }
+
+    Ok(())
+}
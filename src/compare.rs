@@ -0,0 +1,146 @@
+use crate::{
+    metrics::delta_e_ok_linear_srgb, mix_linear_srgb_f32, sample_position,
+    transfer,
+};
+
+/// Statistics of the perceptual difference between pigment mixing and naive
+/// linear interpolation.
+///
+/// Differences are Euclidean distances in
+/// [Oklab](https://bottosson.github.io/posts/oklab/), scaled by `100`. See
+/// [`delta_e_ok()`](crate::delta_e_ok).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LerpDifference {
+    /// The mean difference.
+    pub mean: f32,
+    /// The largest difference.
+    pub max: f32,
+    /// The index of the sample or pixel with the largest difference.
+    pub max_index: usize,
+}
+
+/// Accumulates [`LerpDifference`].
+#[derive(Default)]
+struct Accumulator {
+    sum: f32,
+    count: usize,
+    max: f32,
+    max_index: usize,
+}
+
+impl Accumulator {
+    fn add(&mut self, delta_e: f32) {
+        if self.max < delta_e {
+            self.max = delta_e;
+            self.max_index = self.count;
+        }
+        self.sum += delta_e;
+        self.count += 1;
+    }
+
+    fn finish(self) -> LerpDifference {
+        LerpDifference {
+            mean: if 0 == self.count {
+                0.0
+            } else {
+                self.sum / self.count as f32
+            },
+            max: self.max,
+            max_index: self.max_index,
+        }
+    }
+}
+
+#[inline]
+fn linearize(srgb: &[u8]) -> [f32; 3] {
+    transfer::decode_srgb_u8([srgb[0], srgb[1], srgb[2]])
+}
+
+/// Returns the perceptual difference of the pigment mix and the linear
+/// interpolation of two linear sRGB colors.
+#[inline]
+fn difference(a: &[f32; 3], b: &[f32; 3], ratio: f32) -> f32 {
+    let mix = mix_linear_srgb_f32(a, b, ratio);
+    let lerp = [
+        a[0] + (b[0] - a[0]) * ratio,
+        a[1] + (b[1] - a[1]) * ratio,
+        a[2] + (b[2] - a[2]) * ratio,
+    ];
+
+    delta_e_ok_linear_srgb(mix, lerp)
+}
+
+/// Compares mixing two `u8` component sRGB colors as pigments with naive
+/// linear interpolation in linear sRGB.
+///
+/// The colors are assumed to be in encoded sRGB (gamma 2.2). Both are mixed
+/// at `steps` evenly spaced ratios from `0` to `1`. The
+/// [`max_index`](LerpDifference::max_index) of the result is the index of the
+/// ratio with the largest difference.
+///
+/// Use this to decide where pigment mixing is worth its cost.
+///
+/// ```
+/// # use pigment_mixing::compare_to_lerp;
+/// let difference = compare_to_lerp(&[254, 236, 0], &[25, 0, 89], 11);
+///
+/// println!("mean ΔE {}, max ΔE {}", difference.mean, difference.max);
+/// ```
+pub fn compare_to_lerp(
+    srgb_a: &[u8; 3],
+    srgb_b: &[u8; 3],
+    steps: usize,
+) -> LerpDifference {
+    let (a, b) = (linearize(srgb_a), linearize(srgb_b));
+    let mut accumulator = Accumulator::default();
+
+    (0..steps).for_each(|i| {
+        accumulator.add(difference(&a, &b, sample_position(i, steps)))
+    });
+
+    accumulator.finish()
+}
+
+/// Compares mixing two `u8` component sRGB frames as pigments with naive
+/// linear interpolation in linear sRGB.
+///
+/// The frames are assumed to be RGB in encoded sRGB (gamma 2.2) and are mixed
+/// with `ratio`. The [`max_index`](LerpDifference::max_index) of the result
+/// is the index of the pixel with the largest difference.
+///
+/// If `heat_map` is given, it receives the difference of every pixel.
+///
+/// This function does not allocate.
+///
+/// # Panics
+///
+/// If `srgb_a` and `srgb_b` have different lengths, if their length is not a
+/// multiple of three or if `heat_map` does not hold one value per pixel.
+pub fn compare_frames_to_lerp(
+    srgb_a: &[u8],
+    srgb_b: &[u8],
+    ratio: f32,
+    mut heat_map: Option<&mut [f32]>,
+) -> LerpDifference {
+    assert_eq!(srgb_a.len(), srgb_b.len(), "frames must have the same size");
+    assert_eq!(srgb_a.len() % 3, 0, "frames must be RGB");
+    if let Some(heat_map) = &heat_map {
+        assert_eq!(srgb_a.len() / 3, heat_map.len(), "one value per pixel");
+    }
+
+    let mut accumulator = Accumulator::default();
+
+    srgb_a
+        .chunks_exact(3)
+        .zip(srgb_b.chunks_exact(3))
+        .enumerate()
+        .for_each(|(index, (a, b))| {
+            let delta_e = difference(&linearize(a), &linearize(b), ratio);
+            if let Some(heat_map) = heat_map.as_deref_mut() {
+                heat_map[index] = delta_e;
+            }
+            accumulator.add(delta_e);
+        });
+
+    accumulator.finish()
+}
@@ -0,0 +1,48 @@
+//! Parsing of CSS colors via
+//! [`csscolorparser`](https://docs.rs/csscolorparser/).
+use crate::{mix_srgb_u8, Error, Pigment};
+
+/// Parses any CSS color to `u8` component encoded sRGB (gamma 2.2). Alpha is
+/// ignored.
+#[inline]
+fn parse_css(css: &str) -> Result<[u8; 3], Error> {
+    let [r, g, b, _] = csscolorparser::parse(css)
+        .map_err(|_| Error::InvalidFormat("unsupported CSS color"))?
+        .to_rgba8();
+
+    Ok([r, g, b])
+}
+
+impl Pigment {
+    /// Constructs a `Pigment` from any color
+    /// [`csscolorparser`](https://docs.rs/csscolorparser/) understands, e.g.
+    /// `"gold"`, `"#ffd700"`, `"rgb(255 215 0)"` or `"hsl(51 100% 50%)"`.
+    ///
+    /// Alpha is ignored.
+    ///
+    /// ```
+    /// # use pigment_mixing::Pigment;
+    /// assert_eq!(
+    ///     Pigment::parse("gold").unwrap(),
+    ///     Pigment::parse("rgb(255, 215, 0)").unwrap()
+    /// );
+    /// ```
+    pub fn parse(css: &str) -> Result<Self, Error> {
+        let [r, g, b] = parse_css(css)?;
+        Ok(Pigment::from_srgb_u8(r, g, b))
+    }
+}
+
+/// Mixes two colors given in any syntax
+/// [`csscolorparser`](https://docs.rs/csscolorparser/) understands.
+///
+/// Returns the result as `u8` component encoded sRGB (gamma 2.2). See
+/// [`mix_srgb_u8()`].
+///
+/// ```
+/// # use pigment_mixing::mix_css;
+/// let olive = mix_css("gold", "navy", 0.5).unwrap();
+/// ```
+pub fn mix_css(css_a: &str, css_b: &str, ratio: f32) -> Result<[u8; 3], Error> {
+    Ok(mix_srgb_u8(&parse_css(css_a)?, &parse_css(css_b)?, ratio))
+}
@@ -0,0 +1,78 @@
+//! Conversions from and to [egui](https://www.egui.rs/)'s [`Color32`].
+//!
+//! `Color32` stores premultiplied encoded sRGB (gamma 2.2) with alpha. Color
+//! is only meaningful after unmultiplying, which these functions take care
+//! of.
+use crate::Pigment;
+use ecolor::Color32;
+
+impl Pigment {
+    /// Constructs a `Pigment` from an egui [`Color32`].
+    ///
+    /// The color is unmultiplied first. The alpha channel is otherwise
+    /// ignored, see [`mix_color32()`] to keep it. Fully transparent colors
+    /// have no color information and become black.
+    #[inline]
+    pub fn from_color32(color: Color32) -> Self {
+        let [r, g, b, _] = color.to_srgba_unmultiplied();
+        Pigment::from_srgb_u8(r, g, b)
+    }
+
+    /// Converts the `Pigment` to an opaque egui [`Color32`].
+    #[inline]
+    pub fn to_color32(self) -> Color32 {
+        let [r, g, b]: [u8; 3] = self.into();
+        Color32::from_rgb(r, g, b)
+    }
+}
+
+/// Convert an egui [`Color32`], see [`Pigment::from_color32()`].
+impl From<Color32> for Pigment {
+    #[inline]
+    fn from(color: Color32) -> Self {
+        Pigment::from_color32(color)
+    }
+}
+
+/// Convert a `Pigment` to an opaque egui [`Color32`].
+impl From<Pigment> for Color32 {
+    #[inline]
+    fn from(pigment: Pigment) -> Self {
+        pigment.to_color32()
+    }
+}
+
+/// Mixes two egui [`Color32`]s as pigments.
+///
+/// Each color contributes to the mix in proportion to its opacity, so a
+/// nearly transparent color barely tints the result and a fully transparent
+/// one, which has no color, does not turn it black. Alpha is interpolated
+/// linearly.
+///
+/// ```
+/// # use ecolor::Color32;
+/// # use pigment_mixing::mix_color32;
+/// let yellow = Color32::from_rgb(254, 236, 0);
+///
+/// let mixed = mix_color32(yellow, Color32::TRANSPARENT, 0.5);
+///
+/// // Half as opaque but still yellow, not a darker yellow.
+/// assert_eq!(128, mixed.a());
+/// let [r, g, b, _] = mixed.to_srgba_unmultiplied();
+/// assert!(250 <= r && 230 <= g && b < 4);
+/// ```
+pub fn mix_color32(a: Color32, b: Color32, ratio: f32) -> Color32 {
+    let ratio = ratio.clamp(0.0, 1.0);
+    let (alpha_a, alpha_b) = (a.a() as f32, b.a() as f32);
+
+    let [r, g, b]: [u8; 3] = Pigment::mix_amounts(
+        a.into(),
+        (1.0 - ratio) * alpha_a,
+        b.into(),
+        ratio * alpha_b,
+    )
+    .into();
+    let alpha = (alpha_a + (alpha_b - alpha_a) * ratio + 0.5) as u8;
+
+    Color32::from_rgba_unmultiplied(r, g, b, alpha)
+}
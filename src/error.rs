@@ -0,0 +1,67 @@
+use core::fmt;
+
+/// Errors that can happen when reading or writing color data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A line of a text format could not be parsed.
+    Parse {
+        /// The line number, starting at `1`.
+        line: usize,
+        /// What went wrong.
+        reason: &'static str,
+    },
+    /// The data ended prematurely.
+    UnexpectedEof,
+    /// The data is not in the expected format.
+    InvalidFormat(&'static str),
+    /// The data exceeds a limit of the format it is written in, e.g. the
+    /// number of colors in a palette file.
+    TooLarge(&'static str),
+    /// Buffers that are combined differ in size.
+    SizeMismatch(&'static str),
+    /// A color component is outside `0.0..=1.0` or not finite. See
+    /// [`RangePolicy::Error`](crate::RangePolicy::Error).
+    OutOfRange,
+    /// A [`Pigment`](crate::Pigment) latent is not finite or far outside
+    /// the range of the model. See
+    /// [`Pigment::validate()`](crate::Pigment::validate).
+    InvalidLatent {
+        /// The index of the bad latent component. `None` if the pigment
+        /// concentrations, components `0..4`, do not sum to `1`.
+        component: Option<usize>,
+        /// What went wrong.
+        reason: &'static str,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse { line, reason } => {
+                write!(f, "parse error on line {}: {}", line, reason)
+            }
+            Error::UnexpectedEof => f.write_str("unexpected end of data"),
+            Error::InvalidFormat(reason) => {
+                write!(f, "invalid format: {}", reason)
+            }
+            Error::TooLarge(reason) => write!(f, "too large: {}", reason),
+            Error::SizeMismatch(reason) => {
+                write!(f, "size mismatch: {}", reason)
+            }
+            Error::OutOfRange => f.write_str("color component out of range"),
+            Error::InvalidLatent {
+                component: Some(component),
+                reason,
+            } => {
+                write!(f, "invalid latent component {}: {}", component, reason)
+            }
+            Error::InvalidLatent {
+                component: None,
+                reason,
+            } => write!(f, "invalid latent: {}", reason),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
@@ -0,0 +1,147 @@
+//! A fixed-point implementation of the Mixbox model for targets without an
+//! FPU.
+//!
+//! All values are Q16.16 `i32`s, i.e. [`ONE`] is `1.0`. No floating point
+//! operation is executed at runtime. The table is kept uncompressed in
+//! flash, so there is no heap allocation either.
+//!
+//! # Accuracy
+//!
+//! Compared to [`mix_srgb_u8()`](crate::mix_srgb_u8) of the `pure-rust`
+//! floating point implementation, over random colors and ratios, `u8`
+//! components differ by at most one and on average by about `0.01`.
+//!
+//! ```
+//! # use pigment_mixing::fixed::{mix_srgb_u8, ONE};
+//! // A 50/50 mix of yellow and blue is green.
+//! let green = mix_srgb_u8(&[252, 211, 0], &[0, 0, 96], ONE / 2);
+//! ```
+use crate::{
+    mixbox::{with_lut, LATENT_LEN},
+    mixbox_lut::LUT_RESOLUTION,
+};
+
+include!(concat!(env!("OUT_DIR"), "/fixed_tables.rs"));
+
+/// `1.0` in Q16.16.
+pub const ONE: i32 = 1 << 16;
+
+/// A latent in Q16.16. Mixing latents linearly mixes the pigments.
+pub type Latent = [i32; LATENT_LEN];
+
+/// Multiplies two Q16.16 numbers, rounding to nearest.
+#[inline]
+fn mul(a: i32, b: i32) -> i32 {
+    ((a as i64 * b as i64 + (1 << 15)) >> 16) as i32
+}
+
+/// Returns the RGB of the mix of the primaries with the concentrations
+/// `c`.
+#[inline]
+fn eval_polynomial(c: &[i32; 4]) -> [i32; 3] {
+    let mut rgb = [0; 3];
+    for ([i, j, k], coefficients) in POLYNOMIAL.iter() {
+        let term = mul(mul(c[*i], c[*j]), c[*k]);
+        rgb.iter_mut()
+            .zip(coefficients)
+            .for_each(|(rgb, &coefficient)| *rgb += mul(term, coefficient));
+    }
+
+    rgb
+}
+
+/// Returns the concentrations for `rgb`, trilinearly interpolated from the
+/// table.
+#[inline]
+fn sample_lut(rgb: &[i32; 3]) -> [i32; 4] {
+    let n = LUT_RESOLUTION;
+    let cell = |x: i32| {
+        let x = x.clamp(0, ONE) * (n - 1) as i32;
+        let i = ((x >> 16) as usize).min(n - 2);
+        (i, x - ((i as i32) << 16))
+    };
+    let ((r, tr), (g, tg), (b, tb)) =
+        (cell(rgb[0]), cell(rgb[1]), cell(rgb[2]));
+
+    let mut c = [0; 3];
+    with_lut(|lut| {
+        for (dr, wr) in [(0, ONE - tr), (1, tr)] {
+            for (dg, wg) in [(0, ONE - tg), (1, tg)] {
+                for (db, wb) in [(0, ONE - tb), (1, tb)] {
+                    let sample =
+                        lut.sample(((r + dr) * n + g + dg) * n + b + db);
+                    let weight = mul(mul(wr, wg), wb);
+                    c.iter_mut().zip(sample).for_each(|(c, sample)| {
+                        // Rescale from `0..=u16::MAX` to `0..=ONE`.
+                        let sample = sample as i32 + (sample as i32 >> 15);
+                        *c += mul(weight, sample);
+                    });
+                }
+            }
+        }
+    });
+
+    [c[0], c[1], c[2], ONE - c[0] - c[1] - c[2]]
+}
+
+/// Converts a linear sRGB color to its latent.
+#[inline]
+pub fn linear_srgb_to_latent(rgb: &[i32; 3]) -> Latent {
+    let c = sample_lut(rgb);
+    let mix = eval_polynomial(&c);
+
+    [
+        c[0],
+        c[1],
+        c[2],
+        c[3],
+        rgb[0] - mix[0],
+        rgb[1] - mix[1],
+        rgb[2] - mix[2],
+    ]
+}
+
+/// Converts a latent to a linear sRGB color.
+#[inline]
+pub fn latent_to_linear_srgb(latent: &Latent) -> [i32; 3] {
+    let mix = eval_polynomial(&[latent[0], latent[1], latent[2], latent[3]]);
+
+    [mix[0] + latent[4], mix[1] + latent[5], mix[2] + latent[6]]
+}
+
+/// Converts a `u8` component encoded sRGB (gamma 2.2) color to its latent.
+#[inline]
+pub fn srgb_u8_to_latent(srgb: &[u8; 3]) -> Latent {
+    linear_srgb_to_latent(&srgb.map(|c| DECODE_SRGB_U8[c as usize]))
+}
+
+/// Converts a latent to a `u8` component encoded sRGB (gamma 2.2) color.
+///
+/// Components outside `0..=ONE` are clamped.
+#[inline]
+pub fn latent_to_srgb_u8(latent: &Latent) -> [u8; 3] {
+    latent_to_linear_srgb(latent).map(|c| {
+        ENCODE_SRGB_U8.partition_point(|&threshold| threshold <= c) as u8
+    })
+}
+
+/// Mixes two latents using the Q16.16 `ratio`.
+#[inline]
+pub fn mix_latent(latent_a: &Latent, latent_b: &Latent, ratio: i32) -> Latent {
+    core::array::from_fn(|i| {
+        latent_a[i] + mul(latent_b[i] - latent_a[i], ratio)
+    })
+}
+
+/// Mixes two `u8` component sRGB colors using the Q16.16 `ratio`.
+///
+/// This is the fixed-point equivalent of
+/// [`mix_srgb_u8()`](crate::mix_srgb_u8).
+#[inline]
+pub fn mix_srgb_u8(srgb_a: &[u8; 3], srgb_b: &[u8; 3], ratio: i32) -> [u8; 3] {
+    latent_to_srgb_u8(&mix_latent(
+        &srgb_u8_to_latent(srgb_a),
+        &srgb_u8_to_latent(srgb_b),
+        ratio,
+    ))
+}
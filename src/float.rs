@@ -0,0 +1,35 @@
+//! Transcendental functions.
+//!
+//! With the `deterministic` feature these are computed in software by
+//! [`libm`](https://docs.rs/libm/), so results are the same on every
+//! platform. Otherwise they go through `num-traits`, which uses the
+//! platform's implementation where its `std` feature is enabled and `libm`
+//! elsewhere.
+
+/// Returns `x` raised to the power of `y`.
+#[cfg(not(feature = "deterministic"))]
+#[inline]
+pub(crate) fn powf(x: f32, y: f32) -> f32 {
+    num_traits::Float::powf(x, y)
+}
+
+/// Returns `x` raised to the power of `y`.
+#[cfg(feature = "deterministic")]
+#[inline]
+pub(crate) fn powf(x: f32, y: f32) -> f32 {
+    libm::powf(x, y)
+}
+
+/// Returns `e` raised to the power of `x`.
+#[cfg(all(feature = "pigment", not(feature = "deterministic")))]
+#[inline]
+pub(crate) fn exp(x: f32) -> f32 {
+    num_traits::Float::exp(x)
+}
+
+/// Returns `e` raised to the power of `x`.
+#[cfg(all(feature = "pigment", feature = "deterministic"))]
+#[inline]
+pub(crate) fn exp(x: f32) -> f32 {
+    libm::expf(x)
+}
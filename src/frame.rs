@@ -0,0 +1,140 @@
+use crate::mix_srgb_u8;
+use num_traits::cast::AsPrimitive;
+
+#[inline]
+fn blend_pixel(dst: &mut [u8], src: &[u8], ratio: f32) {
+    if ratio <= 0.0 {
+        return;
+    }
+
+    let rgb = if ratio >= 1.0 {
+        [src[0], src[1], src[2]]
+    } else {
+        mix_srgb_u8(&[dst[0], dst[1], dst[2]], &[src[0], src[1], src[2]], ratio)
+    };
+
+    dst[..3].copy_from_slice(&rgb);
+    dst[3] =
+        (dst[3] as f32 + (u8::MAX - dst[3]) as f32 * ratio.min(1.0) + 0.5) as _;
+}
+
+/// Mixes an `u8` component RGBA frame, `src`, into another one, `dst`, in
+/// place.
+///
+/// The color components are assumed to be in encoded sRGB (gamma 2.2) with
+/// separate (straight) alpha.
+///
+/// For every pixel the mixing ratio is `ratio` scaled by the alpha of the
+/// `src` pixel. The alpha of `dst` is composited using *source over*.
+///
+/// This function does not allocate and is meant to be called once per frame.
+///
+/// # Panics
+///
+/// If `dst` and `src` have different lengths or if their length is not a
+/// multiple of four.
+pub fn blend_into<T>(dst: &mut [u8], src: &[u8], ratio: T)
+where
+    T: AsPrimitive<f32>,
+{
+    assert_eq!(dst.len(), src.len(), "frames must have the same size");
+    assert_eq!(dst.len() % 4, 0, "frames must be RGBA");
+
+    let ratio: f32 = ratio.as_();
+
+    dst.chunks_exact_mut(4)
+        .zip(src.chunks_exact(4))
+        .for_each(|(dst, src)| {
+            blend_pixel(dst, src, ratio * src[3] as f32 / u8::MAX as f32)
+        });
+}
+
+/// Mixes an `u8` component RGBA frame, `src`, into another one, `dst`, in
+/// place, using a per-pixel `mask`.
+///
+/// This works like [`blend_into()`] but the mixing ratio of each pixel is
+/// taken from the `u8` `mask`, which has one entry per pixel. A mask value of
+/// `0` leaves `dst` untouched and `255` mixes in `src` fully.
+///
+/// # Panics
+///
+/// If `dst` and `src` have different lengths, if their length is not a
+/// multiple of four or if `mask` does not have one entry per pixel.
+pub fn blend_into_masked(dst: &mut [u8], src: &[u8], mask: &[u8]) {
+    assert_eq!(dst.len(), src.len(), "frames must have the same size");
+    assert_eq!(dst.len() % 4, 0, "frames must be RGBA");
+    assert_eq!(
+        dst.len() / 4,
+        mask.len(),
+        "mask must have one entry per pixel"
+    );
+
+    dst.chunks_exact_mut(4)
+        .zip(src.chunks_exact(4))
+        .zip(mask.iter())
+        .for_each(|((dst, src), &mask)| {
+            blend_pixel(
+                dst,
+                src,
+                (mask as f32 / u8::MAX as f32)
+                    * (src[3] as f32 / u8::MAX as f32),
+            )
+        });
+}
+
+/// Splits a `0RGB` pixel into its components.
+#[inline]
+fn unpack_0rgb(pixel: u32) -> [u8; 3] {
+    [(pixel >> 16) as u8, (pixel >> 8) as u8, pixel as u8]
+}
+
+/// Packs components into a `0RGB` pixel.
+#[inline]
+fn pack_0rgb(rgb: [u8; 3]) -> u32 {
+    (rgb[0] as u32) << 16 | (rgb[1] as u32) << 8 | rgb[2] as u32
+}
+
+/// Mixes two `0RGB` pixels, as used by e.g.
+/// [softbuffer](https://docs.rs/softbuffer/) and
+/// [minifb](https://docs.rs/minifb/).
+///
+/// Each `u32` holds encoded sRGB (gamma 2.2) with red in bits 16–23, green
+/// in bits 8–15 and blue in bits 0–7. The upper eight bits are ignored and
+/// zero in the result.
+#[inline]
+pub fn mix_0rgb_u32<T>(a: u32, b: u32, ratio: T) -> u32
+where
+    T: AsPrimitive<f32>,
+{
+    pack_0rgb(mix_srgb_u8(&unpack_0rgb(a), &unpack_0rgb(b), ratio.as_()))
+}
+
+/// Mixes a `0RGB` framebuffer, `src`, into another one, `dst`, in place.
+///
+/// See [`mix_0rgb_u32()`] for the pixel format. Pass the buffer of a
+/// `softbuffer::Buffer` as `dst` to draw straight into a window.
+///
+/// This function does not allocate and is meant to be called once per frame.
+///
+/// # Panics
+///
+/// If `dst` and `src` have different lengths.
+pub fn blend_into_0rgb<T>(dst: &mut [u32], src: &[u32], ratio: T)
+where
+    T: AsPrimitive<f32>,
+{
+    assert_eq!(dst.len(), src.len(), "frames must have the same size");
+
+    let ratio: f32 = ratio.as_();
+    if ratio <= 0.0 {
+        return;
+    }
+
+    dst.iter_mut().zip(src).for_each(|(dst, &src)| {
+        *dst = if ratio >= 1.0 {
+            src & 0x00ff_ffff
+        } else {
+            mix_0rgb_u32(*dst, src, ratio)
+        }
+    });
+}
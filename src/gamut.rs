@@ -0,0 +1,135 @@
+//! Sampling of the colors a set of paints can mix.
+use crate::{metrics::linear_srgb_to_oklab, Palette, Pigment};
+use alloc::{vec, vec::Vec};
+use core::fmt::{self, Write};
+
+/// A color reachable by mixing a set of paints, see [`sample_gamut()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GamutPoint {
+    /// The position in [Oklab](https://bottosson.github.io/posts/oklab/):
+    /// lightness, then the a and b axes.
+    pub oklab: [f32; 3],
+    /// The color as `u8` component encoded sRGB (gamma 2.2).
+    pub srgb: [u8; 3],
+}
+
+/// Samples the colors that can be mixed from `paints`.
+///
+/// Every mix of the paints with weights that are multiples of `1 / steps`,
+/// summing to `1`, is sampled, i.e. the simplex of the paint latents is
+/// covered evenly. The number of points is the binomial coefficient of `steps + n - 1` over
+/// `n - 1` for `n` paints, e.g. 5151 for three paints and `100` steps, but
+/// 4598126 for six.
+///
+/// Write the points with [`write_gamut_csv()`] or [`write_gamut_ply()`] to
+/// show what a palette can and can not reach.
+///
+/// ```
+/// # use pigment_mixing::{sample_gamut, Pigment};
+/// let paints = [
+///     Pigment::from_srgb_u8(254, 236, 0),
+///     Pigment::from_srgb_u8(25, 0, 89),
+///     Pigment::from_srgb_u8(255, 255, 255),
+/// ];
+///
+/// let points = sample_gamut(&paints, 10);
+/// assert_eq!(66, points.len());
+/// ```
+pub fn sample_gamut(paints: &[Pigment], steps: usize) -> Vec<GamutPoint> {
+    let mut points = Vec::new();
+    if paints.is_empty() {
+        return points;
+    }
+
+    // Enumerate all ways to split `steps` parts among the paints, in
+    // lexicographic order. The last paint gets the remainder.
+    let (n, steps) = (paints.len(), steps.max(1));
+    let mut parts = vec![0; n];
+    parts[n - 1] = steps;
+
+    loop {
+        let pigment = parts
+            .iter()
+            .zip(paints)
+            .filter(|(&parts, _)| 0 < parts)
+            .map(|(&parts, &paint)| paint * (parts as f32 / steps as f32))
+            .reduce(|a, b| a + b)
+            .expect("the parts sum to `steps`");
+
+        points.push(GamutPoint {
+            oklab: linear_srgb_to_oklab(pigment.into()),
+            srgb: pigment.into(),
+        });
+
+        // Give one more part to the last paint, before the remainder, that
+        // can still take one and reset the paints after it.
+        let Some(i) = (0..n - 1)
+            .rev()
+            .find(|&i| parts[..=i].iter().sum::<usize>() < steps)
+        else {
+            break;
+        };
+        parts[i] += 1;
+        parts[i + 1..].iter_mut().for_each(|p| *p = 0);
+        parts[n - 1] = steps - parts[..n - 1].iter().sum::<usize>();
+    }
+
+    points
+}
+
+impl Palette {
+    /// Samples the colors that can be mixed from the entries of the palette.
+    ///
+    /// See [`sample_gamut()`].
+    pub fn sample_gamut(&self, steps: usize) -> Vec<GamutPoint> {
+        sample_gamut(&self.pigments(), steps)
+    }
+}
+
+/// Writes `points` as CSV with the columns `L,a,b,r,g,b` and a header row.
+///
+/// `r`, `g` and `b` are `u8` component encoded sRGB (gamma 2.2).
+pub fn write_gamut_csv<W: Write>(
+    points: &[GamutPoint],
+    out: &mut W,
+) -> fmt::Result {
+    out.write_str("L,a,b,r,g,b\n")?;
+    points.iter().try_for_each(|point| {
+        let ([l, a, b], [red, green, blue]) = (point.oklab, point.srgb);
+        writeln!(out, "{},{},{},{},{},{}", l, a, b, red, green, blue)
+    })
+}
+
+/// Writes `points` as an ASCII [PLY](https://paulbourke.net/dataformats/ply/)
+/// point cloud with vertex colors.
+///
+/// The vertex positions are the Oklab coordinates with the lightness as `y`,
+/// i.e. `x = a`, `y = L`, `z = b`. This opens upright in most 3D viewers.
+///
+/// ```
+/// # use pigment_mixing::{sample_gamut, write_gamut_ply, Pigment};
+/// let paints = [
+///     Pigment::from_srgb_u8(254, 236, 0),
+///     Pigment::from_srgb_u8(25, 0, 89),
+/// ];
+///
+/// let mut ply = String::new();
+/// write_gamut_ply(&sample_gamut(&paints, 32), &mut ply).unwrap();
+/// ```
+pub fn write_gamut_ply<W: Write>(
+    points: &[GamutPoint],
+    out: &mut W,
+) -> fmt::Result {
+    write!(
+        out,
+        "ply\nformat ascii 1.0\nelement vertex {}\n\
+         property float x\nproperty float y\nproperty float z\n\
+         property uchar red\nproperty uchar green\nproperty uchar blue\n\
+         end_header\n",
+        points.len()
+    )?;
+    points.iter().try_for_each(|point| {
+        let ([l, a, b], [red, green, blue]) = (point.oklab, point.srgb);
+        writeln!(out, "{} {} {} {} {} {}", a, l, b, red, green, blue)
+    })
+}
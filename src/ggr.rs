@@ -0,0 +1,170 @@
+use crate::{sample_position, Easing, Error, Gradient, GradientStop, Pigment};
+use alloc::string::String;
+use core::fmt::Write;
+
+// GIMP segment blending functions.
+const BLEND_LINEAR: u32 = 0;
+const BLEND_CURVED: u32 = 1;
+const BLEND_SINE: u32 = 2;
+const BLEND_SPHERE_INCREASING: u32 = 3;
+const BLEND_SPHERE_DECREASING: u32 = 4;
+const BLEND_STEP: u32 = 5;
+
+impl Gradient {
+    /// Reads a gradient from the contents of a GIMP gradient (`.ggr`) file.
+    ///
+    /// Every GIMP segment becomes a stop at its left end. A segment whose
+    /// right color differs from the left color of the next segment adds a
+    /// second stop to form a hard edge. The middle of each segment becomes
+    /// the [midpoint](GradientStop::midpoint) of its stop.
+    ///
+    /// Blending functions are mapped to the closest [`Easing`]. *Sine*
+    /// becomes [`Smoothstep`](Easing::Smoothstep) and the two *sphere*
+    /// variants become [`CubicOut`](Easing::CubicOut) and
+    /// [`CubicIn`](Easing::CubicIn). *Curved* is treated as linear as the
+    /// midpoint already captures most of its shape.
+    ///
+    /// Colors are read as encoded sRGB. The color space of a segment (RGB or
+    /// HSV) and alpha are ignored. All colors are interpolated as pigments.
+    pub fn from_ggr(ggr: &str) -> Result<Self, Error> {
+        let mut lines = ggr
+            .lines()
+            .enumerate()
+            .map(|(number, line)| (number + 1, line.trim()));
+
+        match lines.next() {
+            Some((_, "GIMP Gradient")) => (),
+            _ => {
+                return Err(Error::InvalidFormat(
+                    "missing GIMP Gradient header",
+                ))
+            }
+        }
+
+        let (line, count) = lines
+            .by_ref()
+            .find(|(_, line)| !line.starts_with("Name:"))
+            .ok_or(Error::UnexpectedEof)?;
+        let count: usize = count.parse().map_err(|_| Error::Parse {
+            line,
+            reason: "expected the number of segments",
+        })?;
+
+        let mut gradient = Gradient::new();
+        let mut previous_right: Option<Pigment> = None;
+
+        for index in 0..count {
+            let (line, segment) = lines.next().ok_or(Error::UnexpectedEof)?;
+
+            let mut values = segment.split_whitespace().map(str::parse::<f32>);
+            let mut next = || -> Result<f32, Error> {
+                values.next().and_then(Result::ok).ok_or(Error::Parse {
+                    line,
+                    reason: "malformed segment",
+                })
+            };
+
+            let (left, middle, right) = (next()?, next()?, next()?);
+            let left_pigment = Pigment::from_srgb(next()?, next()?, next()?);
+            next()?; // alpha
+            let right_pigment = Pigment::from_srgb(next()?, next()?, next()?);
+            next()?; // alpha
+            let blend = next()? as u32;
+
+            if let Some(previous_right) = previous_right {
+                if previous_right != left_pigment {
+                    gradient.add_stop(left, previous_right);
+                }
+            }
+
+            let width = right - left;
+            let midpoint = if width > 0.0 {
+                (middle - left) / width
+            } else {
+                0.5
+            };
+
+            if BLEND_STEP == blend {
+                gradient.add_stop(left, left_pigment);
+                gradient.add_stop(middle, left_pigment);
+                gradient.add_stop(middle, right_pigment);
+            } else {
+                let easing = match blend {
+                    BLEND_LINEAR | BLEND_CURVED => Easing::Linear,
+                    BLEND_SINE => Easing::Smoothstep,
+                    BLEND_SPHERE_INCREASING => Easing::CubicOut,
+                    BLEND_SPHERE_DECREASING => Easing::CubicIn,
+                    _ => {
+                        return Err(Error::Parse {
+                            line,
+                            reason: "unknown blending function",
+                        })
+                    }
+                };
+
+                gradient.insert_stop(
+                    GradientStop::new(left, left_pigment)
+                        .with_easing(easing)
+                        .with_midpoint(midpoint),
+                );
+            }
+
+            if index + 1 == count {
+                gradient.add_stop(right, right_pigment);
+            }
+            previous_right = Some(right_pigment);
+        }
+
+        Ok(gradient)
+    }
+
+    /// Writes the gradient as the contents of a GIMP gradient (`.ggr`) file.
+    ///
+    /// GIMP interpolates in RGB. To get close to the pigment mix the gradient
+    /// is densified to `n_samples` evenly spaced stops that are connected by
+    /// linear segments. Colors are written as encoded sRGB, clamped to
+    /// `0.0..=1.0`.
+    ///
+    /// # Panics
+    ///
+    /// If the gradient has no stops.
+    pub fn to_ggr(&self, name: &str, n_samples: usize) -> String {
+        let n_samples = n_samples.max(2);
+        let mut ggr = String::new();
+
+        let _ = writeln!(ggr, "GIMP Gradient");
+        let _ = writeln!(ggr, "Name: {}", name.lines().next().unwrap_or(""));
+        let _ = writeln!(ggr, "{}", n_samples - 1);
+
+        let mut left: Option<(f32, [f32; 3])> = None;
+        for (i, pigment) in self.samples(n_samples).enumerate() {
+            let position = sample_position(i, n_samples);
+            let right = pigment.to_srgb().map(|c| c.clamp(0.0, 1.0));
+
+            if let Some((left_position, left)) = left {
+                let _ = write!(
+                    ggr,
+                    "{:.6} {:.6} {:.6} ",
+                    left_position,
+                    0.5 * (left_position + position),
+                    position,
+                );
+                let _ = write!(
+                    ggr,
+                    "{:.6} {:.6} {:.6} 1.000000 ",
+                    left[0], left[1], left[2]
+                );
+                let _ = write!(
+                    ggr,
+                    "{:.6} {:.6} {:.6} 1.000000 ",
+                    right[0], right[1], right[2]
+                );
+                let _ = writeln!(ggr, "{} 0", BLEND_LINEAR);
+            }
+
+            left = Some((position, right));
+        }
+
+        ggr
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{
+    float::{exp, powf},
+    Pigment,
+};
+
+/// How quickly a glaze builds up to its masstone with thickness. Glazes
+/// scatter little, so this is small compared to absorption.
+const GLAZE_SCATTERING: f32 = 0.25;
+/// The lowest transmittance of a unit layer. No real paint absorbs a channel
+/// completely and a transmittance of zero would make even the thinnest layer
+/// opaque.
+const MIN_TRANSMITTANCE: f32 = 0.01;
+
+/// Applies a thin transparent layer of `glaze_pigment` over `base`.
+///
+/// Unlike [`Pigment::from_mix()`] this is asymmetric: a yellow glaze over blue
+/// is not the same as a blue glaze over yellow.
+///
+/// `thickness` is the thickness of the layer relative to a layer that absorbs
+/// like the glaze's masstone once. `0.0` returns `base`, `1.0` is a typical
+/// single glaze and large values approach the masstone of `glaze_pigment`.
+///
+/// # Model
+///
+/// This approximates the two-layer Kubelka–Munk solution in two steps. The
+/// glaze's masstone, in linear sRGB, is taken as its transmittance for light
+/// passing the layer in and out again, so the base is filtered by it raised
+/// to the power of `thickness` (Beer–Lambert). The small amount of light the
+/// glaze scatters back itself is accounted for by mixing the filtered base
+/// with the glaze in latent space, weighted by
+/// 1 − e<sup>−`thickness` · 0.25</sup>.
+///
+/// ```
+/// # use pigment_mixing::{glaze, Pigment};
+/// let yellow = Pigment::from_srgb_u8(254, 236, 0);
+/// let blue = Pigment::from_srgb_u8(0, 33, 133);
+///
+/// let yellow_over_blue = glaze(&blue, &yellow, 1.0);
+/// let blue_over_yellow = glaze(&yellow, &blue, 1.0);
+///
+/// assert_ne!(yellow_over_blue, blue_over_yellow);
+/// assert_eq!(blue, glaze(&blue, &yellow, 0.0));
+/// ```
+pub fn glaze(
+    base: &Pigment,
+    glaze_pigment: &Pigment,
+    thickness: f32,
+) -> Pigment {
+    if thickness <= 0.0 {
+        return *base;
+    }
+
+    let base_srgb: [f32; 3] = (*base).into();
+    let glaze_srgb: [f32; 3] = (*glaze_pigment).into();
+
+    let transmittance = |channel: usize| {
+        powf(glaze_srgb[channel].clamp(MIN_TRANSMITTANCE, 1.0), thickness)
+    };
+
+    let filtered = Pigment::from_linear_srgb(
+        base_srgb[0] * transmittance(0),
+        base_srgb[1] * transmittance(1),
+        base_srgb[2] * transmittance(2),
+    );
+
+    Pigment::from_mix(
+        filtered,
+        *glaze_pigment,
+        1.0 - exp(-thickness * GLAZE_SCATTERING),
+    )
+}
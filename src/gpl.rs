@@ -0,0 +1,81 @@
+use crate::{Error, Palette, Pigment};
+use alloc::string::String;
+use core::fmt::Write;
+
+impl Palette {
+    /// Reads a palette from the contents of a GIMP palette (`.gpl`) file.
+    ///
+    /// The palette name and column count are ignored.
+    pub fn from_gpl(gpl: &str) -> Result<Self, Error> {
+        let mut lines = gpl
+            .lines()
+            .enumerate()
+            .map(|(number, line)| (number + 1, line.trim()));
+
+        match lines.next() {
+            Some((_, "GIMP Palette")) => (),
+            _ => {
+                return Err(Error::InvalidFormat("missing GIMP Palette header"))
+            }
+        }
+
+        let mut palette = Palette::new();
+
+        for (line, text) in lines {
+            if text.is_empty()
+                || text.starts_with('#')
+                || text.starts_with("Name:")
+                || text.starts_with("Columns:")
+            {
+                continue;
+            }
+
+            let mut rest = text;
+            let mut next = || -> Result<u8, Error> {
+                let field;
+                (field, rest) = split_field(rest);
+                field.parse().map_err(|_| Error::Parse {
+                    line,
+                    reason: "expected three components from 0 to 255",
+                })
+            };
+
+            let (r, g, b) = (next()?, next()?, next()?);
+            let name = rest.trim();
+
+            palette.push(name, Pigment::from_srgb_u8(r, g, b));
+        }
+
+        Ok(palette)
+    }
+
+    /// Writes the palette as the contents of a GIMP palette (`.gpl`) file.
+    pub fn to_gpl(&self, name: &str) -> String {
+        let mut gpl = String::new();
+
+        let _ = writeln!(gpl, "GIMP Palette");
+        let _ = writeln!(gpl, "Name: {}", name.lines().next().unwrap_or(""));
+        let _ = writeln!(gpl, "#");
+
+        self.iter().for_each(|entry| {
+            let srgb: [u8; 3] = entry.pigment.into();
+            let _ = writeln!(
+                gpl,
+                "{:3} {:3} {:3}\t{}",
+                srgb[0],
+                srgb[1],
+                srgb[2],
+                entry.name.lines().next().unwrap_or("")
+            );
+        });
+
+        gpl
+    }
+}
+
+/// Splits off the first whitespace delimited field.
+#[inline]
+fn split_field(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+    text.split_at(text.find(char::is_whitespace).unwrap_or(text.len()))
+}
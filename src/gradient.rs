@@ -0,0 +1,616 @@
+use crate::{clamp, sample_position, Pigment};
+#[cfg(feature = "image")]
+use alloc::vec;
+use alloc::{string::String, sync::Arc, vec::Vec};
+use core::fmt::{self, Write};
+
+/// An easing curve that remaps the local position between two stops of a
+/// [`Gradient`].
+///
+/// All curves map `0.0` to `0.0` and `1.0` to `1.0`.
+#[derive(Clone, Default)]
+pub enum Easing {
+    /// No remapping.
+    #[default]
+    Linear,
+    /// Hermite interpolation, `3t² - 2t³`.
+    Smoothstep,
+    /// Starts slow, `t³`.
+    CubicIn,
+    /// Ends slow, `1 - (1 - t)³`.
+    CubicOut,
+    /// Starts and ends slow.
+    CubicInOut,
+    /// A user-supplied curve.
+    ///
+    /// ```
+    /// # use pigment_mixing::Easing;
+    /// # use std::sync::Arc;
+    /// let steps = 4.0;
+    /// let easing =
+    ///     Easing::Custom(Arc::new(move |t| (t * steps).floor() / steps));
+    ///
+    /// assert_eq!(0.5, easing.apply(0.6));
+    /// ```
+    Custom(Arc<dyn Fn(f32) -> f32 + Send + Sync>),
+}
+
+impl Easing {
+    /// Applies the easing curve to `t`, which is expected to be in
+    /// `0.0..=1.0`.
+    #[inline]
+    pub fn apply(&self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::Smoothstep => t * t * (3.0 - 2.0 * t),
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => {
+                let t = 1.0 - t;
+                1.0 - t * t * t
+            }
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    let t = -2.0 * t + 2.0;
+                    1.0 - 0.5 * t * t * t
+                }
+            }
+            Easing::Custom(function) => function(t),
+        }
+    }
+}
+
+/// Custom curves compare equal if they share the same closure.
+impl PartialEq for Easing {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Easing::Custom(a), Easing::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => {
+                core::mem::discriminant(self) == core::mem::discriminant(other)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Easing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Easing::Linear => f.write_str("Linear"),
+            Easing::Smoothstep => f.write_str("Smoothstep"),
+            Easing::CubicIn => f.write_str("CubicIn"),
+            Easing::CubicOut => f.write_str("CubicOut"),
+            Easing::CubicInOut => f.write_str("CubicInOut"),
+            Easing::Custom(function) => f
+                .debug_tuple("Custom")
+                .field(&Arc::as_ptr(function))
+                .finish(),
+        }
+    }
+}
+
+/// A color stop of a [`Gradient`].
+///
+/// Besides its own color a stop holds the parameters of the segment that
+/// starts at it and ends at the next stop.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GradientStop {
+    /// The position of the stop along the gradient. Usually in `0.0..=1.0`.
+    pub position: f32,
+    /// The color at this stop.
+    pub pigment: Pigment,
+    /// The easing of the segment towards the next stop.
+    pub easing: Easing,
+    midpoint: f32,
+}
+
+impl GradientStop {
+    /// Constructs a `GradientStop` at the given `position`.
+    #[inline]
+    pub fn new(position: f32, pigment: Pigment) -> Self {
+        Self {
+            position,
+            pigment,
+            easing: Easing::default(),
+            midpoint: 0.5,
+        }
+    }
+
+    /// Sets the easing of the segment towards the next stop.
+    #[inline]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Sets the midpoint of the segment towards the next stop.
+    ///
+    /// Moving the midpoint towards `0.0` makes the color of the next stop
+    /// dominate the segment. Moving it towards `1.0` makes the color of this
+    /// stop dominate. At `0.0` the segment starts with a hard edge from the
+    /// color of this stop to the 50/50 mix.
+    ///
+    /// `midpoint` is clamped to `0.0..=1.0`.
+    ///
+    /// ```
+    /// # use pigment_mixing::{Gradient, GradientStop, Pigment};
+    /// let (yellow, blue) = (
+    ///     Pigment::from_srgb_u8(252, 211, 0),
+    ///     Pigment::from_srgb_u8(0, 0, 96),
+    /// );
+    ///
+    /// let mut gradient = Gradient::new().with_stop(1.0, blue);
+    /// gradient.insert_stop(GradientStop::new(0.0, yellow).with_midpoint(0.0));
+    ///
+    /// // Exactly at a stop, its color is returned.
+    /// assert_eq!(yellow, gradient.sample(0.0));
+    /// ```
+    #[inline]
+    pub fn with_midpoint(mut self, midpoint: f32) -> Self {
+        self.midpoint = clamp(midpoint, 0.0, 1.0);
+        self
+    }
+
+    /// Returns the local position, in `0.0..=1.0`, of the 50/50 mix in the
+    /// segment towards the next stop.
+    ///
+    /// The default is `0.5`, i.e. halfway between the two stops.
+    #[inline]
+    pub fn midpoint(&self) -> f32 {
+        self.midpoint
+    }
+
+    /// Maps the local position `t` in the segment towards the next stop to a
+    /// mixing ratio.
+    ///
+    /// The midpoint warp is applied first and the easing second.
+    #[inline]
+    fn warp(&self, t: f32) -> f32 {
+        let m = self.midpoint;
+        let t = if t <= m {
+            if m > 0.0 {
+                0.5 * t / m
+            } else {
+                0.0
+            }
+        } else if m < 1.0 {
+            0.5 + 0.5 * (t - m) / (1.0 - m)
+        } else {
+            1.0
+        };
+
+        self.easing.apply(t)
+    }
+}
+
+/// A gradient made of any number of positioned color stops.
+///
+/// Colors between two stops are interpolated as pigments, i.e. in the latent
+/// space of Mixbox.
+///
+/// ```
+/// # use pigment_mixing::{Gradient, Pigment};
+/// let gradient = Gradient::new()
+///     .with_stop(0.0, Pigment::from_srgb_u8(252, 211, 0))
+///     .with_stop(0.5, Pigment::from_srgb_u8(201, 37, 44))
+///     .with_stop(1.0, Pigment::from_srgb_u8(0, 0, 96));
+///
+/// let orange: [u8; 3] = gradient.sample(0.25).into();
+///
+/// let mut ramp = [[0.0f32; 3]; 16];
+/// gradient.fill(&mut ramp);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Gradient {
+    stops: Vec<GradientStop>,
+}
+
+impl Gradient {
+    /// Constructs an empty `Gradient`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a stop at `position` and returns the gradient.
+    #[inline]
+    pub fn with_stop(mut self, position: f32, pigment: Pigment) -> Self {
+        self.add_stop(position, pigment);
+        self
+    }
+
+    /// Adds a stop at `position`.
+    ///
+    /// Stops are kept sorted by position. A stop added at the same position
+    /// as an existing one is inserted after it. This allows hard edges.
+    #[inline]
+    pub fn add_stop(&mut self, position: f32, pigment: Pigment) {
+        self.insert_stop(GradientStop::new(position, pigment));
+    }
+
+    /// Inserts `stop`, keeping the stops sorted by position.
+    ///
+    /// See [`add_stop()`](Gradient::add_stop).
+    pub fn insert_stop(&mut self, stop: GradientStop) {
+        let index = self
+            .stops
+            .partition_point(|other| other.position <= stop.position);
+        self.stops.insert(index, stop);
+    }
+
+    /// Returns the stops of the gradient, sorted by position.
+    #[inline]
+    pub fn stops(&self) -> &[GradientStop] {
+        &self.stops
+    }
+
+    /// Returns `true` if the gradient has no stops.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.stops.is_empty()
+    }
+
+    /// Returns the number of stops.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.stops.len()
+    }
+
+    /// Samples the gradient at position `t`.
+    ///
+    /// Positions before the first or after the last stop return the color of
+    /// that stop.
+    ///
+    /// # Panics
+    ///
+    /// If the gradient has no stops.
+    #[inline]
+    pub fn sample(&self, t: f32) -> Pigment {
+        assert!(!self.stops.is_empty(), "gradient has no stops");
+
+        let index = self.stops.partition_point(|stop| stop.position <= t);
+
+        if index == 0 {
+            return self.stops[0].pigment;
+        } else if index == self.stops.len() {
+            return self.stops[index - 1].pigment;
+        }
+
+        let a = &self.stops[index - 1];
+        let b = &self.stops[index];
+
+        let width = b.position - a.position;
+        let ratio = if width > 0.0 {
+            (t - a.position) / width
+        } else {
+            0.0
+        };
+
+        Pigment::from_mix(a.pigment, b.pigment, a.warp(ratio))
+    }
+
+    /// Returns an iterator over `n` evenly spaced samples covering
+    /// `0.0..=1.0`.
+    ///
+    /// # Panics
+    ///
+    /// If the gradient has no stops.
+    pub fn samples(&self, n: usize) -> impl Iterator<Item = Pigment> + '_ {
+        (0..n).map(move |i| self.sample(sample_position(i, n)))
+    }
+
+    /// Fills `out` with evenly spaced samples covering `0.0..=1.0`.
+    ///
+    /// Anything a [`Pigment`] can be converted into works as the element
+    /// type. E.g. `[u8; 3]` for encoded sRGB or `[f32; 3]` for linear sRGB.
+    ///
+    /// This does not allocate.
+    ///
+    /// # Panics
+    ///
+    /// If the gradient has no stops.
+    pub fn fill<C>(&self, out: &mut [C])
+    where
+        C: From<Pigment>,
+    {
+        let n = out.len();
+        out.iter_mut()
+            .enumerate()
+            .for_each(|(i, c)| *c = self.sample(sample_position(i, n)).into());
+    }
+
+    /// Returns a CSS `linear-gradient()` approximating this gradient.
+    ///
+    /// Browsers interpolate gradients in sRGB or Oklab. To get close to the
+    /// pigment mix the gradient is densified to `n_samples` evenly spaced
+    /// stops. 16 – 32 samples are usually indistinguishable from the real
+    /// thing. Fewer than two samples are raised to two, the ends.
+    ///
+    /// The direction is left at the CSS default (top to bottom). Use
+    /// [`to_css_stops()`](Gradient::to_css_stops) to build gradients with
+    /// other directions or shapes.
+    ///
+    /// # Panics
+    ///
+    /// If the gradient has no stops.
+    pub fn to_css(&self, n_samples: usize) -> String {
+        let mut css = String::new();
+        // Writing to a String can not fail.
+        let _ = self.write_css(&mut css, n_samples);
+
+        css
+    }
+
+    /// Writes [`to_css()`](Gradient::to_css) to `out`.
+    ///
+    /// This does not allocate, e.g. when `out` is a fixed capacity string.
+    ///
+    /// # Panics
+    ///
+    /// If the gradient has no stops.
+    pub fn write_css<W: Write>(
+        &self,
+        out: &mut W,
+        n_samples: usize,
+    ) -> fmt::Result {
+        out.write_str("linear-gradient(")?;
+        self.write_css_stops(out, n_samples)?;
+        out.write_char(')')
+    }
+
+    /// Returns `n_samples` evenly spaced stops as a CSS color stop list, e.g.
+    /// `#fcd300 0%, #c9252c 50%, #000060 100%`.
+    ///
+    /// Fewer than two samples are raised to two, the ends.
+    ///
+    /// ```
+    /// # use pigment_mixing::{Gradient, Pigment};
+    /// let gradient = Gradient::new()
+    ///     .with_stop(0.0, Pigment::from_srgb_u8(252, 211, 0))
+    ///     .with_stop(1.0, Pigment::from_srgb_u8(0, 0, 96));
+    ///
+    /// assert_eq!("#fcd300 0%, #000060 100%", gradient.to_css_stops(0));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the gradient has no stops.
+    pub fn to_css_stops(&self, n_samples: usize) -> String {
+        let mut css = String::new();
+        // Writing to a String can not fail.
+        let _ = self.write_css_stops(&mut css, n_samples);
+
+        css
+    }
+
+    /// Writes [`to_css_stops()`](Gradient::to_css_stops) to `out`.
+    ///
+    /// This does not allocate, e.g. when `out` is a fixed capacity string.
+    ///
+    /// # Panics
+    ///
+    /// If the gradient has no stops.
+    pub fn write_css_stops<W: Write>(
+        &self,
+        out: &mut W,
+        n_samples: usize,
+    ) -> fmt::Result {
+        let n_samples = n_samples.max(2);
+        self.samples(n_samples)
+            .enumerate()
+            .try_for_each(|(i, pigment)| {
+                let srgb: [u8; 3] = pigment.into();
+                let percent =
+                    (sample_position(i, n_samples) * 10000.0).round() / 100.0;
+
+                if 0 != i {
+                    out.write_str(", ")?;
+                }
+                write!(
+                    out,
+                    "#{:02x}{:02x}{:02x} {}%",
+                    srgb[0], srgb[1], srgb[2], percent
+                )
+            })
+    }
+
+    /// Returns an SVG `<linearGradient>` element approximating this gradient.
+    ///
+    /// The gradient is densified to `n_samples` evenly spaced stops. See
+    /// [`to_css()`](Gradient::to_css) for why this is needed. Fewer than two
+    /// samples are raised to two, the ends.
+    ///
+    /// The element runs left to right and can be referenced as
+    /// `fill="url(#id)"`.
+    ///
+    /// ```
+    /// # use pigment_mixing::{Gradient, Pigment};
+    /// let gradient = Gradient::new()
+    ///     .with_stop(0.0, Pigment::from_srgb_u8(252, 211, 0))
+    ///     .with_stop(1.0, Pigment::from_srgb_u8(0, 0, 96));
+    ///
+    /// let svg = gradient.to_svg_linear("ramp", 1);
+    /// assert_eq!(2, svg.matches("<stop ").count());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the gradient has no stops.
+    pub fn to_svg_linear(&self, id: &str, n_samples: usize) -> String {
+        self.to_svg_element("linearGradient", id, n_samples)
+    }
+
+    /// Returns an SVG `<radialGradient>` element approximating this gradient.
+    ///
+    /// Position `0.0` is at the center. See
+    /// [`to_svg_linear()`](Gradient::to_svg_linear).
+    ///
+    /// # Panics
+    ///
+    /// If the gradient has no stops.
+    pub fn to_svg_radial(&self, id: &str, n_samples: usize) -> String {
+        self.to_svg_element("radialGradient", id, n_samples)
+    }
+
+    fn to_svg_element(&self, tag: &str, id: &str, n_samples: usize) -> String {
+        let n_samples = n_samples.max(2);
+        let mut svg = String::new();
+
+        let _ = writeln!(svg, "<{} id=\"{}\">", tag, XmlEscaped(id));
+        for (i, pigment) in self.samples(n_samples).enumerate() {
+            let srgb: [u8; 3] = pigment.into();
+            let offset =
+                (sample_position(i, n_samples) * 10000.0).round() / 10000.0;
+
+            let _ = writeln!(
+                svg,
+                "  <stop offset=\"{}\" stop-color=\"#{:02x}{:02x}{:02x}\"/>",
+                offset, srgb[0], srgb[1], srgb[2]
+            );
+        }
+        let _ = write!(svg, "</{}>", tag);
+
+        svg
+    }
+}
+
+#[cfg(feature = "image")]
+impl Gradient {
+    /// Renders the gradient as a `width` × `height` ramp.
+    ///
+    /// # Panics
+    ///
+    /// If the gradient has no stops.
+    ///
+    /// ```
+    /// # use pigment_mixing::{Gradient, GradientImageOptions, Pigment};
+    /// let gradient = Gradient::new()
+    ///     .with_stop(0.0, Pigment::from_srgb_u8(252, 211, 0))
+    ///     .with_stop(1.0, Pigment::from_srgb_u8(0, 0, 96));
+    ///
+    /// let image = gradient.to_image(256, 32, &GradientImageOptions::default());
+    /// ```
+    pub fn to_image(
+        &self,
+        width: u32,
+        height: u32,
+        options: &GradientImageOptions,
+    ) -> image::RgbImage {
+        let (length, across) = match options.orientation {
+            GradientOrientation::Horizontal => (width, height),
+            GradientOrientation::Vertical => (height, width),
+        };
+
+        let mut ramp = vec![[0u8; 3]; length as usize];
+        self.fill(&mut ramp);
+
+        let mut image = image::RgbImage::from_fn(width, height, |x, y| {
+            image::Rgb(
+                ramp[match options.orientation {
+                    GradientOrientation::Horizontal => x,
+                    GradientOrientation::Vertical => y,
+                } as usize],
+            )
+        });
+
+        if options.mark_stops && 0 < length && 0 < across {
+            // Ticks a quarter of the ramp long, in black or white, whichever
+            // contrasts with the ramp.
+            for stop in &self.stops {
+                let along = (stop.position.clamp(0.0, 1.0)
+                    * (length - 1) as f32
+                    + 0.5) as u32;
+                let [r, g, b] = ramp[along as usize].map(|c| c as f32);
+                let tick = if 127.5 < 0.2126 * r + 0.7152 * g + 0.0722 * b {
+                    image::Rgb([0, 0, 0])
+                } else {
+                    image::Rgb([255, 255, 255])
+                };
+
+                for across in 0..(across / 4).max(1) {
+                    match options.orientation {
+                        GradientOrientation::Horizontal => {
+                            image.put_pixel(along, across, tick)
+                        }
+                        GradientOrientation::Vertical => {
+                            image.put_pixel(across, along, tick)
+                        }
+                    }
+                }
+            }
+        }
+
+        image
+    }
+
+    /// Renders the gradient like [`to_image()`](Gradient::to_image) and
+    /// writes it as a PNG to `path`.
+    ///
+    /// # Panics
+    ///
+    /// If the gradient has no stops.
+    #[cfg(feature = "png")]
+    pub fn to_png(
+        &self,
+        path: impl AsRef<std::path::Path>,
+        width: u32,
+        height: u32,
+        options: &GradientImageOptions,
+    ) -> image::ImageResult<()> {
+        self.to_image(width, height, options)
+            .save_with_format(path, image::ImageFormat::Png)
+    }
+}
+
+/// The direction a [`Gradient`] rendered by
+/// [`Gradient::to_image()`] runs in.
+#[cfg(feature = "image")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GradientOrientation {
+    /// Left to right.
+    #[default]
+    Horizontal,
+    /// Top to bottom.
+    Vertical,
+}
+
+/// Options for [`Gradient::to_image()`].
+#[cfg(feature = "image")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GradientImageOptions {
+    /// The direction the gradient runs in.
+    pub orientation: GradientOrientation,
+    /// Marks the position of each stop with a tick along the top, or left,
+    /// edge.
+    ///
+    /// This crate does not render text. Use
+    /// [`Gradient::stops()`] to draw labels at the ticks with the text
+    /// renderer of your choice.
+    pub mark_stops: bool,
+}
+
+/// Escapes the characters that are not allowed in XML attribute values.
+struct XmlEscaped<'a>(&'a str);
+
+impl fmt::Display for XmlEscaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.chars().try_for_each(|c| match c {
+            '&' => f.write_str("&amp;"),
+            '<' => f.write_str("&lt;"),
+            '>' => f.write_str("&gt;"),
+            '"' => f.write_str("&quot;"),
+            c => f.write_char(c),
+        })
+    }
+}
+
+impl FromIterator<GradientStop> for Gradient {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = GradientStop>,
+    {
+        let mut gradient = Gradient::new();
+        iter.into_iter().for_each(|stop| gradient.insert_stop(stop));
+        gradient
+    }
+}
@@ -0,0 +1,207 @@
+//! Color harmonies computed with the pigment model.
+use crate::{metrics::linear_srgb_to_oklab, solve::best_ratio, Pigment};
+use num_traits::Float;
+
+/// The number of coarse wheel positions tried when matching a color.
+const WHEEL_STEPS: usize = 36;
+/// The number of grays tried when matching a color.
+const GRAY_STEPS: usize = 8;
+/// The number of grays tried when solving for a complement.
+const COMPLEMENT_STEPS: usize = 8;
+/// The smallest step, in linear sRGB, of the search for a complement.
+const COMPLEMENT_TOLERANCE: f32 = 1.0e-3;
+
+/// Returns the pure color at `turns` around the painter's wheel.
+///
+/// `0.0` is yellow, `1 / 3` red and `2 / 3` blue.
+fn wheel(turns: f32) -> Pigment {
+    let primaries = [
+        Pigment::from_srgb_u8(254, 236, 0),
+        Pigment::from_srgb_u8(255, 39, 2),
+        Pigment::from_srgb_u8(25, 0, 89),
+    ];
+
+    let t = (turns - Float::floor(turns)) * 3.0;
+    let i = (t as usize).min(2);
+
+    Pigment::from_mix(primaries[i], primaries[(i + 1) % 3], t - i as f32)
+}
+
+/// Returns the gray with linear sRGB value `level`.
+#[inline]
+fn gray(level: f32) -> Pigment {
+    Pigment::from_linear_srgb(level, level, level)
+}
+
+/// A color matched as the pure wheel color at `turns`, mixed with `ratio` of
+/// the gray at `level`.
+#[derive(Clone, Copy, Debug)]
+struct WheelMatch {
+    turns: f32,
+    level: f32,
+    ratio: f32,
+}
+
+impl WheelMatch {
+    /// Matches `pigment` against tints, tones and shades of the wheel.
+    fn new(pigment: &Pigment) -> Self {
+        let fit = |turns: f32| {
+            (0..=GRAY_STEPS)
+                .map(|i| {
+                    let level = i as f32 / GRAY_STEPS as f32;
+                    let (ratio, delta_e) =
+                        best_ratio(&wheel(turns), &gray(level), pigment);
+                    (
+                        Self {
+                            turns,
+                            level,
+                            ratio,
+                        },
+                        delta_e,
+                    )
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .expect("there is at least one gray")
+        };
+
+        // Coarse search around the wheel, then refine in steps of a degree.
+        let step = 1.0 / WHEEL_STEPS as f32;
+        let (coarse, _) = (0..WHEEL_STEPS)
+            .map(|i| fit(i as f32 * step))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("there is at least one wheel position");
+
+        let (best, _) = (-5..=5)
+            .map(|degrees| fit(coarse.turns + degrees as f32 / 360.0))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("there is at least one wheel position");
+
+        best
+    }
+
+    /// Returns the same mix at `offset` turns around the wheel.
+    #[inline]
+    fn pigment(&self, offset: f32) -> Pigment {
+        Pigment::from_mix(
+            wheel(self.turns + offset),
+            gray(self.level),
+            self.ratio,
+        )
+    }
+}
+
+/// Returns the complement of `pigment`: the color that mixes with it, in
+/// equal parts, to a neutral gray.
+///
+/// Strong paints, like a cadmium yellow, dominate every equal parts mix. For
+/// those the color whose mix gets closest to neutral is returned.
+///
+/// ```
+/// # use pigment_mixing::{complement, Pigment};
+/// let blue = Pigment::from_srgb_u8(0, 0, 96);
+/// let orange = complement(&blue);
+///
+/// let [r, g, b]: [u8; 3] = Pigment::from_mix(blue, orange, 0.5).into();
+/// assert!(r.abs_diff(g) < 8 && g.abs_diff(b) < 8);
+/// ```
+pub fn complement(pigment: &Pigment) -> Pigment {
+    // Returns the squared Oklab chroma of the mix of `pigment` with `rgb`.
+    let chroma = |rgb: &[f32; 3]| {
+        let [_, a, b] = linear_srgb_to_oklab(
+            Pigment::from_mix(*pigment, Pigment::from(*rgb), 0.5).into(),
+        );
+        a * a + b * b
+    };
+
+    // Compass search for the color closest to `start` whose mix with
+    // `pigment` has no chroma.
+    let search = |start: [f32; 3]| {
+        let mut rgb = start.map(|c| c.clamp(0.0, 1.0));
+        let mut error = chroma(&rgb);
+        let mut step = 0.125;
+        while COMPLEMENT_TOLERANCE < step {
+            let improved = (0..6).find_map(|direction| {
+                let mut candidate = rgb;
+                let c = &mut candidate[direction / 2];
+                *c = (*c + if 0 == direction % 2 { step } else { -step })
+                    .clamp(0.0, 1.0);
+
+                let candidate_error = chroma(&candidate);
+                (candidate_error < error)
+                    .then_some((candidate, candidate_error))
+            });
+
+            match improved {
+                Some(improved) => (rgb, error) = improved,
+                None => step *= 0.5,
+            }
+        }
+
+        (rgb, error)
+    };
+
+    // In latent space the complement is `2 · gray - pigment`. That is rarely
+    // a valid latent, so it is only used as a starting point, for a range of
+    // grays.
+    let (rgb, _) = (0..=COMPLEMENT_STEPS)
+        .map(|i| {
+            let level = i as f32 / COMPLEMENT_STEPS as f32;
+            search((gray(level) * 2.0 + *pigment * -1.0).into())
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .expect("there is at least one gray");
+
+    Pigment::from(rgb)
+}
+
+/// Returns `pigment` followed by the two colors 150° away from it on the
+/// painter's wheel, i.e. either side of its complement.
+///
+/// See [`triadic()`] for how the wheel is used.
+pub fn split_complementary(pigment: &Pigment) -> [Pigment; 3] {
+    let matched = WheelMatch::new(pigment);
+    [
+        *pigment,
+        matched.pigment(150.0 / 360.0),
+        matched.pigment(210.0 / 360.0),
+    ]
+}
+
+/// Returns `pigment` followed by the two colors a third of the painter's
+/// wheel away from it.
+///
+/// Painters build harmonies on a wheel mixed from three primaries, not on the
+/// hue circle of HSV. On such a wheel green sits opposite red and violet
+/// opposite yellow. The wheel used here is mixed from a cadmium yellow, a
+/// cadmium red and ultramarine blue.
+///
+/// `pigment` is first matched as a wheel color mixed with a gray, i.e. a
+/// tint, tone or shade of a pure hue. The harmony is the same mix of the
+/// wheel colors at the harmonic positions.
+///
+/// ```
+/// # use pigment_mixing::{triadic, Pigment};
+/// let yellow = Pigment::from_srgb_u8(254, 236, 0);
+/// let [yellow, red, blue] = triadic(&yellow);
+/// ```
+pub fn triadic(pigment: &Pigment) -> [Pigment; 3] {
+    let matched = WheelMatch::new(pigment);
+    [
+        *pigment,
+        matched.pigment(1.0 / 3.0),
+        matched.pigment(2.0 / 3.0),
+    ]
+}
+
+/// Returns `pigment` between its two neighbors `degrees` away from it on the
+/// painter's wheel. `30` is a common choice.
+///
+/// See [`triadic()`] for how the wheel is used.
+pub fn analogous(pigment: &Pigment, degrees: f32) -> [Pigment; 3] {
+    let matched = WheelMatch::new(pigment);
+    [
+        matched.pigment(-degrees / 360.0),
+        *pigment,
+        matched.pigment(degrees / 360.0),
+    ]
+}
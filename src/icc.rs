@@ -0,0 +1,135 @@
+//! Mixing of colors tagged with an ICC profile, via
+//! [`qcms`](https://docs.rs/qcms/).
+use crate::{mix_srgb_u8, Error, Pigment};
+use qcms::{DataType, Intent, Profile, Transform};
+
+/// Mixes `u8` component RGB colors in the color space of an ICC profile.
+///
+/// Colors are converted to encoded sRGB (gamma 2.2) before mixing and back to
+/// the profile's color space afterwards. Colors outside the sRGB gamut are
+/// clipped.
+///
+/// ```no_run
+/// # use pigment_mixing::IccMixer;
+/// let profile = std::fs::read("AdobeRGB1998.icc").unwrap();
+/// let mixer = IccMixer::new(&profile).unwrap();
+///
+/// let green = mixer.mix_rgb_u8(&[252, 211, 0], &[0, 0, 96], 0.5);
+/// ```
+pub struct IccMixer {
+    to_srgb: Transform,
+    from_srgb: Transform,
+}
+
+impl IccMixer {
+    /// Constructs a mixer for RGB colors in the color space of the ICC
+    /// profile in `icc_profile`, using the perceptual rendering intent.
+    ///
+    /// Returns [`Error::InvalidFormat`] if the profile can not be parsed or
+    /// is not an RGB profile.
+    pub fn new(icc_profile: &[u8]) -> Result<Self, Error> {
+        Self::with_intent(icc_profile, Intent::Perceptual)
+    }
+
+    /// Constructs a mixer like [`new()`](IccMixer::new) with the given
+    /// rendering `intent`.
+    pub fn with_intent(
+        icc_profile: &[u8],
+        intent: Intent,
+    ) -> Result<Self, Error> {
+        let mut profile = Profile::new_from_slice(icc_profile, false)
+            .ok_or(Error::InvalidFormat("unsupported ICC profile"))?;
+        let mut srgb = Profile::new_sRGB();
+        profile.precache_output_transform();
+        srgb.precache_output_transform();
+
+        let transform = |input: &Profile, output: &Profile| {
+            Transform::new(input, output, DataType::RGB8, intent).ok_or(
+                Error::InvalidFormat("ICC profile is not an RGB profile"),
+            )
+        };
+
+        Ok(Self {
+            to_srgb: transform(&profile, &srgb)?,
+            from_srgb: transform(&srgb, &profile)?,
+        })
+    }
+
+    /// Converts a color to encoded sRGB (gamma 2.2).
+    #[inline]
+    pub fn to_srgb_u8(&self, rgb: &[u8; 3]) -> [u8; 3] {
+        let mut srgb = *rgb;
+        self.to_srgb.apply(&mut srgb);
+        srgb
+    }
+
+    /// Converts an encoded sRGB (gamma 2.2) color to the color space of the
+    /// profile.
+    #[inline]
+    pub fn from_srgb_u8(&self, srgb: &[u8; 3]) -> [u8; 3] {
+        let mut rgb = *srgb;
+        self.from_srgb.apply(&mut rgb);
+        rgb
+    }
+
+    /// Converts a color to a `Pigment`.
+    #[inline]
+    pub fn to_pigment(&self, rgb: &[u8; 3]) -> Pigment {
+        let [r, g, b] = self.to_srgb_u8(rgb);
+        Pigment::from_srgb_u8(r, g, b)
+    }
+
+    /// Converts a `Pigment` to a color in the color space of the profile.
+    #[inline]
+    pub fn from_pigment(&self, pigment: Pigment) -> [u8; 3] {
+        self.from_srgb_u8(&pigment.into())
+    }
+
+    /// Mixes two colors.
+    #[inline]
+    pub fn mix_rgb_u8(
+        &self,
+        rgb_a: &[u8; 3],
+        rgb_b: &[u8; 3],
+        ratio: f32,
+    ) -> [u8; 3] {
+        self.from_srgb_u8(&mix_srgb_u8(
+            &self.to_srgb_u8(rgb_a),
+            &self.to_srgb_u8(rgb_b),
+            ratio,
+        ))
+    }
+
+    /// Mixes two RGB frames pixel by pixel into `out`.
+    ///
+    /// This does not allocate.
+    ///
+    /// # Panics
+    ///
+    /// If the frames have different lengths or if their length is not a
+    /// multiple of three.
+    pub fn mix_rgb_u8_into(
+        &self,
+        rgb_a: &[u8],
+        rgb_b: &[u8],
+        ratio: f32,
+        out: &mut [u8],
+    ) {
+        assert_eq!(rgb_a.len(), rgb_b.len(), "frames must have the same size");
+        assert_eq!(rgb_a.len(), out.len(), "frames must have the same size");
+        assert_eq!(rgb_a.len() % 3, 0, "frames must be RGB");
+
+        out.copy_from_slice(rgb_a);
+        self.to_srgb.apply(out);
+
+        out.chunks_exact_mut(3).zip(rgb_b.chunks_exact(3)).for_each(
+            |(out, rgb_b)| {
+                let srgb_a = [out[0], out[1], out[2]];
+                let srgb_b = self.to_srgb_u8(&[rgb_b[0], rgb_b[1], rgb_b[2]]);
+                out.copy_from_slice(&mix_srgb_u8(&srgb_a, &srgb_b, ratio));
+            },
+        );
+
+        self.from_srgb.apply(out);
+    }
+}
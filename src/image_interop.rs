@@ -0,0 +1,119 @@
+//! Mixing of any [`image::Pixel`] type.
+//!
+//! Subpixels are normalized by their
+//! [`DEFAULT_MAX_VALUE`](image::Primitive::DEFAULT_MAX_VALUE) and treated as
+//! encoded sRGB (gamma 2.2), regardless of whether they are `u8`, `u16` or
+//! `f32`. This matches what `image` does when converting between them.
+use crate::Pigment;
+use alloc::vec::Vec;
+use core::ops::Deref;
+use image::{ImageBuffer, Pixel, Primitive};
+use num_traits::NumCast;
+
+/// Rec. 709 luma coefficients, as used by `image` for RGB to luma.
+const LUMA: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+/// Returns `value` normalized to `0.0..=1.0`.
+#[inline]
+fn normalize<S: Primitive>(value: S) -> f32 {
+    let max: f32 = NumCast::from(S::DEFAULT_MAX_VALUE).unwrap_or(1.0);
+    NumCast::from(value).unwrap_or(0.0) / max
+}
+
+/// Returns the subpixel closest to the normalized `value`.
+#[inline]
+fn denormalize<S: Primitive>(value: f32) -> S {
+    let max: f32 = NumCast::from(S::DEFAULT_MAX_VALUE).unwrap_or(1.0);
+    let value = value.clamp(0.0, 1.0) * max;
+
+    // Integer subpixels are rounded, floating point ones are kept as is.
+    NumCast::from(if 1.0 == max { value } else { value + 0.5 })
+        .unwrap_or(S::DEFAULT_MIN_VALUE)
+}
+
+/// Converts any pixel to a `Pigment`.
+///
+/// Luma pixels are treated as gray. Alpha is ignored.
+#[inline]
+pub fn pixel_to_pigment<P: Pixel>(pixel: &P) -> Pigment {
+    let [r, g, b] = pixel.to_rgb().0.map(normalize);
+    Pigment::from_srgb(r, g, b)
+}
+
+/// Writes the color of `pigment` to the color channels of `pixel`, leaving
+/// alpha untouched.
+///
+/// Luma pixels receive the Rec. 709 luma of the color.
+#[inline]
+pub fn write_pigment_to_pixel<P: Pixel>(pigment: Pigment, pixel: &mut P) {
+    let srgb = pigment.to_srgb();
+    let channels = pixel.channels_mut();
+
+    if P::CHANNEL_COUNT < 3 {
+        channels[0] =
+            denormalize(srgb.iter().zip(LUMA.iter()).map(|(c, w)| c * w).sum());
+    } else {
+        channels
+            .iter_mut()
+            .zip(srgb.iter())
+            .for_each(|(channel, &c)| *channel = denormalize(c));
+    }
+}
+
+/// Mixes two pixels of any type.
+///
+/// The color is mixed as pigment. Alpha, if present, is interpolated
+/// linearly.
+///
+/// ```
+/// # use pigment_mixing::mix_pixel;
+/// use image::{Rgb, Rgba};
+///
+/// let green = mix_pixel(&Rgb([252u8, 211, 0]), &Rgb([0, 0, 96]), 0.5);
+///
+/// let translucent_green = mix_pixel(
+///     &Rgba([0.99f32, 0.83, 0.0, 1.0]),
+///     &Rgba([0.0, 0.0, 0.38, 0.0]),
+///     0.5,
+/// );
+/// ```
+#[inline]
+pub fn mix_pixel<P: Pixel>(a: &P, b: &P, ratio: f32) -> P {
+    let ratio = ratio.clamp(0.0, 1.0);
+    let mut mixed = a.map2(b, |a, b| {
+        denormalize(normalize(a) * (1.0 - ratio) + normalize(b) * ratio)
+    });
+    write_pigment_to_pixel(
+        Pigment::from_mix(pixel_to_pigment(a), pixel_to_pigment(b), ratio),
+        &mut mixed,
+    );
+
+    mixed
+}
+
+/// Mixes two images of any pixel type, pixel by pixel.
+///
+/// See [`mix_pixel()`].
+///
+/// # Panics
+///
+/// If the images have different dimensions.
+pub fn mix_images<P, C>(
+    a: &ImageBuffer<P, C>,
+    b: &ImageBuffer<P, C>,
+    ratio: f32,
+) -> ImageBuffer<P, Vec<P::Subpixel>>
+where
+    P: Pixel,
+    C: Deref<Target = [P::Subpixel]>,
+{
+    assert_eq!(
+        a.dimensions(),
+        b.dimensions(),
+        "images must have the same size"
+    );
+
+    ImageBuffer::from_fn(a.width(), a.height(), |x, y| {
+        mix_pixel(a.get_pixel(x, y), b.get_pixel(x, y), ratio)
+    })
+}
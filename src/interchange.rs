@@ -0,0 +1,305 @@
+//! Serde support for the interchange format described in the crate
+//! documentation.
+use crate::{parse_hex, pigment::PIGMENT_LEN, Pigment};
+use alloc::{format, string::String};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(any(feature = "gradient", feature = "palette"))]
+use alloc::vec::Vec;
+
+#[cfg(feature = "gradient")]
+use crate::{Easing, Gradient, GradientStop};
+
+#[cfg(feature = "palette")]
+use crate::{Palette, PaletteEntry};
+
+/// The latent model of this build, written next to latents.
+///
+/// Latents of different models describe different colors, so they are only
+/// used when read by a build with the same model.
+const LATENT_MODEL: &str = if cfg!(feature = "deterministic") {
+    "pigment-mixing-fixed"
+} else if cfg!(feature = "pure-rust") {
+    "pigment-mixing"
+} else {
+    "mixbox"
+};
+
+/// A color as it appears in documents.
+///
+/// All representations are written. When reading, `srgb` is the source of
+/// truth unless `model` matches the model of this build, in which case
+/// `latent` is used for a lossless round trip.
+#[derive(Serialize, Deserialize)]
+struct ColorDocument {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    srgb: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    latent: Option<[f32; PIGMENT_LEN]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+}
+
+impl From<&Pigment> for ColorDocument {
+    fn from(pigment: &Pigment) -> Self {
+        let srgb: [u8; 3] = (*pigment).into();
+        Self {
+            srgb: Some(format!(
+                "#{:02x}{:02x}{:02x}",
+                srgb[0], srgb[1], srgb[2]
+            )),
+            latent: Some((*pigment).into()),
+            model: Some(LATENT_MODEL.into()),
+        }
+    }
+}
+
+impl ColorDocument {
+    fn into_pigment<E: de::Error>(self) -> Result<Pigment, E> {
+        let same_model = Some(LATENT_MODEL) == self.model.as_deref();
+        match (self.latent, self.srgb) {
+            (Some(latent), Some(_)) if same_model => Ok(Pigment::from(latent)),
+            (Some(latent), None) => Ok(Pigment::from(latent)),
+            (_, Some(srgb)) => {
+                let srgb = parse_hex(&srgb).ok_or_else(|| {
+                    E::custom(format!("invalid sRGB hex color '{}'", srgb))
+                })?;
+                Ok(Pigment::from_srgb_u8(srgb[0], srgb[1], srgb[2]))
+            }
+            (None, None) => Err(E::custom("color needs `srgb` or `latent`")),
+        }
+    }
+}
+
+/// Serializes as a color document with `srgb` and `latent` keys.
+impl Serialize for Pigment {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        ColorDocument::from(self).serialize(serializer)
+    }
+}
+
+/// Deserializes from a color document with `srgb` and/or `latent` keys.
+impl<'de> Deserialize<'de> for Pigment {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        ColorDocument::deserialize(deserializer)?.into_pigment()
+    }
+}
+
+#[cfg(feature = "palette")]
+#[derive(Serialize, Deserialize)]
+struct PaletteEntryDocument {
+    #[serde(default)]
+    name: String,
+    #[serde(flatten)]
+    color: ColorDocument,
+}
+
+#[cfg(feature = "palette")]
+#[derive(Serialize, Deserialize)]
+struct PaletteDocument {
+    colors: Vec<PaletteEntryDocument>,
+}
+
+#[cfg(feature = "palette")]
+impl Serialize for Palette {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        PaletteDocument {
+            colors: self
+                .iter()
+                .map(|entry| PaletteEntryDocument {
+                    name: entry.name.clone(),
+                    color: ColorDocument::from(&entry.pigment),
+                })
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "palette")]
+impl<'de> Deserialize<'de> for Palette {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        PaletteDocument::deserialize(deserializer)?
+            .colors
+            .into_iter()
+            .map(|entry| {
+                Ok(PaletteEntry::new(
+                    entry.name,
+                    entry.color.into_pigment::<D::Error>()?,
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "gradient")]
+#[derive(Serialize, Deserialize)]
+struct GradientStopDocument {
+    position: f32,
+    #[serde(flatten)]
+    color: ColorDocument,
+    #[serde(default, skip_serializing_if = "is_linear")]
+    easing: EasingDocument,
+    #[serde(default = "default_midpoint")]
+    midpoint: f32,
+}
+
+#[cfg(feature = "gradient")]
+#[derive(Serialize, Deserialize)]
+struct GradientDocument {
+    stops: Vec<GradientStopDocument>,
+}
+
+#[cfg(feature = "gradient")]
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum EasingDocument {
+    #[default]
+    Linear,
+    Smoothstep,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+}
+
+#[cfg(feature = "gradient")]
+fn is_linear(easing: &EasingDocument) -> bool {
+    EasingDocument::Linear == *easing
+}
+
+#[cfg(feature = "gradient")]
+fn default_midpoint() -> f32 {
+    0.5
+}
+
+#[cfg(feature = "gradient")]
+impl From<EasingDocument> for Easing {
+    fn from(easing: EasingDocument) -> Self {
+        match easing {
+            EasingDocument::Linear => Easing::Linear,
+            EasingDocument::Smoothstep => Easing::Smoothstep,
+            EasingDocument::CubicIn => Easing::CubicIn,
+            EasingDocument::CubicOut => Easing::CubicOut,
+            EasingDocument::CubicInOut => Easing::CubicInOut,
+        }
+    }
+}
+
+/// Serializes the stops.
+///
+/// # Errors
+///
+/// If any stop uses [`Easing::Custom`] as functions can not be serialized.
+#[cfg(feature = "gradient")]
+impl Serialize for Gradient {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        use serde::ser::Error;
+
+        GradientDocument {
+            stops: self
+                .stops()
+                .iter()
+                .map(|stop| {
+                    Ok(GradientStopDocument {
+                        position: stop.position,
+                        color: ColorDocument::from(&stop.pigment),
+                        easing: match stop.easing {
+                            Easing::Linear => EasingDocument::Linear,
+                            Easing::Smoothstep => EasingDocument::Smoothstep,
+                            Easing::CubicIn => EasingDocument::CubicIn,
+                            Easing::CubicOut => EasingDocument::CubicOut,
+                            Easing::CubicInOut => EasingDocument::CubicInOut,
+                            Easing::Custom(_) => {
+                                return Err(S::Error::custom(
+                                    "custom easing can not be serialized",
+                                ))
+                            }
+                        },
+                        midpoint: stop.midpoint(),
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "gradient")]
+impl<'de> Deserialize<'de> for Gradient {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        GradientDocument::deserialize(deserializer)?
+            .stops
+            .into_iter()
+            .map(|stop| {
+                Ok(GradientStop::new(
+                    stop.position,
+                    stop.color.into_pigment::<D::Error>()?,
+                )
+                .with_easing(stop.easing.into())
+                .with_midpoint(stop.midpoint))
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "json")]
+macro_rules! impl_json {
+    ($type:ty) => {
+        impl $type {
+            /// Reads from a JSON document. See the
+            /// [crate documentation](crate#interchange-format) for the schema.
+            pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+                serde_json::from_str(json)
+            }
+
+            /// Writes a pretty-printed JSON document. See the
+            /// [crate documentation](crate#interchange-format) for the schema.
+            pub fn to_json(&self) -> Result<String, serde_json::Error> {
+                serde_json::to_string_pretty(self)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "toml")]
+macro_rules! impl_toml {
+    ($type:ty) => {
+        impl $type {
+            /// Reads from a TOML document. See the
+            /// [crate documentation](crate#interchange-format) for the schema.
+            pub fn from_toml(toml: &str) -> Result<Self, toml::de::Error> {
+                toml::from_str(toml)
+            }
+
+            /// Writes a TOML document. See the
+            /// [crate documentation](crate#interchange-format) for the schema.
+            pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+                toml::to_string(self)
+            }
+        }
+    };
+}
+
+#[cfg(all(feature = "json", feature = "palette"))]
+impl_json!(Palette);
+#[cfg(all(feature = "json", feature = "gradient"))]
+impl_json!(Gradient);
+#[cfg(all(feature = "toml", feature = "palette"))]
+impl_toml!(Palette);
+#[cfg(all(feature = "toml", feature = "gradient"))]
+impl_toml!(Gradient);
@@ -0,0 +1,137 @@
+use crate::{
+    spectral::{km_reflectance, reflectance_to_linear_srgb},
+    Pigment, Spectrum, SPECTRUM_LEN,
+};
+
+/// A pigment described by its Kubelka–Munk absorption and scattering
+/// coefficients.
+///
+/// Unlike [`Pigment`], whose latent representation is tied to the pigments
+/// Mixbox was trained on, this can describe any paint from measured data.
+///
+/// ```
+/// # use pigment_mixing::{km_mix, KmPigment, SPECTRUM_LEN};
+/// // A yellow that absorbs short wavelengths and a white that scatters.
+/// let mut yellow = [0.05; SPECTRUM_LEN];
+/// yellow[..12].fill(4.0);
+///
+/// let yellow = KmPigment::new(yellow, [1.0; SPECTRUM_LEN]);
+/// let white = KmPigment::new([0.01; SPECTRUM_LEN], [4.0; SPECTRUM_LEN]);
+///
+/// let tint = km_mix(&[(yellow, 1.0), (white, 3.0)]);
+/// let [r, g, b] = tint.to_linear_srgb();
+///
+/// assert!(b < r && b < g);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KmPigment {
+    /// The absorption coefficient, *K*, per wavelength.
+    pub absorption: Spectrum,
+    /// The scattering coefficient, *S*, per wavelength.
+    pub scattering: Spectrum,
+}
+
+impl KmPigment {
+    /// Constructs a `KmPigment` from its absorption, *K*, and scattering, *S*,
+    /// spectra.
+    #[inline]
+    pub fn new(absorption: Spectrum, scattering: Spectrum) -> Self {
+        Self {
+            absorption,
+            scattering,
+        }
+    }
+
+    /// Constructs a `KmPigment` from its *K*/*S* ratio spectrum.
+    ///
+    /// This is the single-constant Kubelka–Munk model; the scattering is set
+    /// to `1`. It is good enough for paints whose scattering is dominated by a
+    /// common white or extender.
+    #[inline]
+    pub fn from_k_over_s(k_over_s: Spectrum) -> Self {
+        Self {
+            absorption: k_over_s,
+            scattering: [1.0; SPECTRUM_LEN],
+        }
+    }
+
+    /// Constructs a `KmPigment` from the measured reflectance of an opaque
+    /// layer (a masstone).
+    ///
+    /// This inverts the Kubelka–Munk equation, *K*/*S* = (1 − *R*)² / 2*R*,
+    /// using the single-constant model. See
+    /// [`from_k_over_s()`](KmPigment::from_k_over_s).
+    pub fn from_reflectance(reflectance: &Spectrum) -> Self {
+        let mut k_over_s = [0.0; SPECTRUM_LEN];
+        k_over_s.iter_mut().zip(reflectance).for_each(|(ks, &r)| {
+            let r = r.clamp(1.0e-4, 1.0);
+            *ks = (1.0 - r) * (1.0 - r) / (2.0 * r);
+        });
+
+        Self::from_k_over_s(k_over_s)
+    }
+
+    /// Returns the reflectance of an opaque layer of the pigment.
+    pub fn reflectance(&self) -> Spectrum {
+        let mut reflectance = [0.0; SPECTRUM_LEN];
+        reflectance
+            .iter_mut()
+            .zip(self.absorption.iter().zip(&self.scattering))
+            .for_each(|(r, (&k, &s))| *r = km_reflectance(k, s));
+
+        reflectance
+    }
+
+    /// Returns the linear sRGB color of an opaque layer of the pigment under
+    /// D65.
+    #[inline]
+    pub fn to_linear_srgb(&self) -> [f32; 3] {
+        reflectance_to_linear_srgb(&self.reflectance())
+    }
+}
+
+/// Converts to a [`Pigment`] via linear sRGB.
+///
+/// This loses the spectral information. Mix `KmPigment`s with [`km_mix()`]
+/// before converting.
+impl From<KmPigment> for Pigment {
+    #[inline]
+    fn from(pigment: KmPigment) -> Self {
+        Pigment::from(pigment.to_linear_srgb())
+    }
+}
+
+/// Mixes pigments by concentration after Kubelka–Munk.
+///
+/// The absorption and scattering of the mixture are the concentration
+/// weighted sums of those of its components. Concentrations are relative;
+/// they are normalized to sum to `1`.
+///
+/// Returns a pigment that neither absorbs nor scatters if `components` is
+/// empty or all concentrations are zero.
+pub fn km_mix(components: &[(KmPigment, f32)]) -> KmPigment {
+    let total: f32 = components
+        .iter()
+        .map(|(_, concentration)| concentration.max(0.0))
+        .sum();
+
+    let mut mix = KmPigment::new([0.0; SPECTRUM_LEN], [0.0; SPECTRUM_LEN]);
+    if total <= 0.0 {
+        return mix;
+    }
+
+    for (pigment, concentration) in components {
+        let c = concentration.max(0.0) / total;
+
+        mix.absorption
+            .iter_mut()
+            .zip(&pigment.absorption)
+            .for_each(|(mix, k)| *mix += c * k);
+        mix.scattering
+            .iter_mut()
+            .zip(&pigment.scattering)
+            .for_each(|(mix, s)| *mix += c * s);
+    }
+
+    mix
+}
@@ -0,0 +1,131 @@
+//! Conversion between colors and raw Mixbox latents.
+//!
+//! For code that keeps its own latent buffers instead of `Pigment`s, e.g. to
+//! upload them to the GPU. Mixing latents component-wise linearly mixes the
+//! colors.
+use crate::{mixbox, transfer};
+
+pub use mixbox::LATENT_LEN;
+
+/// Converts a linear sRGB color to its latent.
+///
+/// Components outside `0.0..=1.0` are clamped.
+///
+/// ```
+/// # use pigment_mixing::{latent_to_linear_srgb, linear_srgb_to_latent};
+/// let latent = linear_srgb_to_latent([0.5, 0.25, 0.0]);
+/// let [r, g, b] = latent_to_linear_srgb(&latent);
+///
+/// assert!((r - 0.5).abs() < 0.001 && (g - 0.25).abs() < 0.001 && b < 0.001);
+/// ```
+#[inline]
+pub fn linear_srgb_to_latent(rgb: [f32; 3]) -> [f32; LATENT_LEN] {
+    mixbox::rgb_to_latent(rgb)
+}
+
+/// Converts a latent to a linear sRGB color.
+#[inline]
+pub fn latent_to_linear_srgb(latent: &[f32; LATENT_LEN]) -> [f32; 3] {
+    mixbox::latent_to_rgb(latent)
+}
+
+/// Converts an [`f32`] component encoded sRGB (gamma 2.2) color to its
+/// latent.
+///
+/// Components outside `0.0..=1.0` are clamped.
+#[inline]
+pub fn srgb_to_latent(srgb: [f32; 3]) -> [f32; LATENT_LEN] {
+    mixbox::rgb_to_latent(transfer::decode_srgb(srgb))
+}
+
+/// Converts a latent to an [`f32`] component encoded sRGB (gamma 2.2) color.
+#[inline]
+pub fn latent_to_srgb(latent: &[f32; LATENT_LEN]) -> [f32; 3] {
+    transfer::encode_srgb(mixbox::latent_to_rgb(latent))
+}
+
+/// Converts a slice of linear sRGB colors to latents with
+/// [`linear_srgb_to_latent()`].
+///
+/// # Panics
+///
+/// If `rgb` and `latents` have different lengths.
+pub fn linear_srgb_to_latent_into(
+    rgb: &[[f32; 3]],
+    latents: &mut [[f32; LATENT_LEN]],
+) {
+    assert_eq!(rgb.len(), latents.len(), "slices must have the same length");
+
+    latents
+        .iter_mut()
+        .zip(rgb)
+        .for_each(|(latent, rgb)| *latent = linear_srgb_to_latent(*rgb));
+}
+
+/// Converts a slice of latents to linear sRGB colors with
+/// [`latent_to_linear_srgb()`].
+///
+/// # Panics
+///
+/// If `latents` and `rgb` have different lengths.
+pub fn latent_to_linear_srgb_into(
+    latents: &[[f32; LATENT_LEN]],
+    rgb: &mut [[f32; 3]],
+) {
+    assert_eq!(latents.len(), rgb.len(), "slices must have the same length");
+
+    rgb.iter_mut()
+        .zip(latents)
+        .for_each(|(rgb, latent)| *rgb = latent_to_linear_srgb(latent));
+}
+
+/// Converts a slice of [`f32`] component encoded sRGB (gamma 2.2) colors to
+/// latents with [`srgb_to_latent()`].
+///
+/// ```
+/// # use pigment_mixing::{srgb_to_latent_into, LATENT_LEN};
+/// let srgb = [[1.0, 0.0, 0.0], [0.0, 0.0, 1.0]];
+/// let mut latents = [[0.0; LATENT_LEN]; 2];
+///
+/// srgb_to_latent_into(&srgb, &mut latents);
+/// ```
+///
+/// # Panics
+///
+/// If `srgb` and `latents` have different lengths.
+pub fn srgb_to_latent_into(
+    srgb: &[[f32; 3]],
+    latents: &mut [[f32; LATENT_LEN]],
+) {
+    assert_eq!(
+        srgb.len(),
+        latents.len(),
+        "slices must have the same length"
+    );
+
+    latents
+        .iter_mut()
+        .zip(srgb)
+        .for_each(|(latent, srgb)| *latent = srgb_to_latent(*srgb));
+}
+
+/// Converts a slice of latents to [`f32`] component encoded sRGB (gamma 2.2)
+/// colors with [`latent_to_srgb()`].
+///
+/// # Panics
+///
+/// If `latents` and `srgb` have different lengths.
+pub fn latent_to_srgb_into(
+    latents: &[[f32; LATENT_LEN]],
+    srgb: &mut [[f32; 3]],
+) {
+    assert_eq!(
+        latents.len(),
+        srgb.len(),
+        "slices must have the same length"
+    );
+
+    srgb.iter_mut()
+        .zip(latents)
+        .for_each(|(srgb, latent)| *srgb = latent_to_srgb(latent));
+}
@@ -0,0 +1,248 @@
+use crate::{delta_e_2000, pigment::PIGMENT_LEN, sample_position, Pigment};
+use alloc::{vec, vec::Vec};
+
+/// A precomputed table mapping encoded sRGB (gamma 2.2) to [`Pigment`]s.
+///
+/// Looking a color up trilinearly interpolates the latents of the eight
+/// surrounding grid points. This is much faster than
+/// [`Pigment::from_srgb()`] and trades a small loss of accuracy for it.
+///
+/// The table holds `resolution³` latents of 28 bytes each, e.g. 7 MiB for a
+/// resolution of `64`. Use [`accuracy()`](LatentLut::accuracy) to pick a
+/// resolution.
+///
+/// ```
+/// # use pigment_mixing::{LatentLut, Pigment};
+/// let lut = LatentLut::new(17);
+///
+/// let yellow = lut.pigment_from_srgb_u8(252, 211, 0);
+/// let blue = lut.pigment_from_srgb_u8(0, 0, 96);
+/// let green: [u8; 3] = Pigment::from_mix(yellow, blue, 0.5).into();
+/// ```
+#[derive(Clone, Debug)]
+pub struct LatentLut {
+    resolution: usize,
+    latents: Vec<[f32; PIGMENT_LEN]>,
+}
+
+/// The accuracy of a [`LatentLut`] compared to the exact conversion, as
+/// returned by [`LatentLut::accuracy()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LatentLutAccuracy {
+    /// The largest ΔE 2000 found.
+    pub max_delta_e: f32,
+    /// The mean ΔE 2000.
+    pub mean_delta_e: f32,
+}
+
+impl LatentLut {
+    /// Builds a table with `resolution` grid points per axis.
+    ///
+    /// With the `rayon` feature, [`new_parallel()`](LatentLut::new_parallel)
+    /// builds it on all cores.
+    ///
+    /// # Panics
+    ///
+    /// If `resolution` is less than `2`.
+    pub fn new(resolution: usize) -> Self {
+        Self::with_progress(resolution, |_| {})
+    }
+
+    /// Builds a table like [`new()`](LatentLut::new), calling `progress` with
+    /// the fraction of the table done after each slice of it.
+    ///
+    /// ```
+    /// # use pigment_mixing::LatentLut;
+    /// let lut = LatentLut::with_progress(17, |done| {
+    ///     println!("{:.0}%", 100.0 * done);
+    /// });
+    /// ```
+    pub fn with_progress(
+        resolution: usize,
+        mut progress: impl FnMut(f32),
+    ) -> Self {
+        assert!(1 < resolution, "a LUT needs at least two points per axis");
+
+        let n = resolution;
+        let mut latents = vec![[0.0; PIGMENT_LEN]; n * n * n];
+        latents
+            .chunks_exact_mut(n * n)
+            .enumerate()
+            .for_each(|(r, slice)| {
+                fill_slice(r, n, slice);
+                progress((r + 1) as f32 / n as f32);
+            });
+
+        Self {
+            resolution,
+            latents,
+        }
+    }
+
+    /// Builds a table like [`new()`](LatentLut::new) on all cores, using
+    /// [`rayon`](https://docs.rs/rayon/).
+    ///
+    /// # Panics
+    ///
+    /// If `resolution` is less than `2`.
+    #[cfg(feature = "rayon")]
+    pub fn new_parallel(resolution: usize) -> Self {
+        Self::new_parallel_with_progress(resolution, |_| {})
+    }
+
+    /// Builds a table like [`new_parallel()`](LatentLut::new_parallel),
+    /// calling `progress` with the fraction of the table done after each
+    /// slice of it.
+    ///
+    /// `progress` is called from the worker threads, so fractions may arrive
+    /// out of order.
+    #[cfg(feature = "rayon")]
+    pub fn new_parallel_with_progress(
+        resolution: usize,
+        progress: impl Fn(f32) + Sync,
+    ) -> Self {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        use rayon::prelude::*;
+
+        assert!(1 < resolution, "a LUT needs at least two points per axis");
+
+        let n = resolution;
+        let done = AtomicUsize::new(0);
+        let mut latents = vec![[0.0; PIGMENT_LEN]; n * n * n];
+        latents.par_chunks_exact_mut(n * n).enumerate().for_each(
+            |(r, slice)| {
+                fill_slice(r, n, slice);
+                let done = done.fetch_add(1, Ordering::Relaxed) + 1;
+                progress(done as f32 / n as f32);
+            },
+        );
+
+        Self {
+            resolution,
+            latents,
+        }
+    }
+
+    /// Returns the number of grid points per axis.
+    #[inline]
+    pub fn resolution(&self) -> usize {
+        self.resolution
+    }
+
+    /// Returns the size of the table in bytes.
+    #[inline]
+    pub fn size_in_bytes(&self) -> usize {
+        self.latents.len() * core::mem::size_of::<[f32; PIGMENT_LEN]>()
+    }
+
+    /// Converts an [`f32`] component encoded sRGB (gamma 2.2) color to a
+    /// `Pigment`.
+    ///
+    /// Components are clamped to `0.0..=1.0`.
+    #[inline]
+    pub fn pigment_from_srgb(&self, r: f32, g: f32, b: f32) -> Pigment {
+        let n = self.resolution;
+        let cell = |x: f32| {
+            let x = x.clamp(0.0, 1.0) * (n - 1) as f32;
+            let i = (x as usize).min(n - 2);
+            (i, x - i as f32)
+        };
+        let ((r, tr), (g, tg), (b, tb)) = (cell(r), cell(g), cell(b));
+
+        let mut latent = [0.0f32; PIGMENT_LEN];
+        for (dr, wr) in [(0, 1.0 - tr), (1, tr)] {
+            for (dg, wg) in [(0, 1.0 - tg), (1, tg)] {
+                for (db, wb) in [(0, 1.0 - tb), (1, tb)] {
+                    let sample =
+                        &self.latents[((r + dr) * n + g + dg) * n + b + db];
+                    let weight = wr * wg * wb;
+                    latent.iter_mut().zip(sample).for_each(
+                        |(latent, sample)| *latent += weight * sample,
+                    );
+                }
+            }
+        }
+
+        latent.into()
+    }
+
+    /// Converts a [`u8`] component encoded sRGB (gamma 2.2) color to a
+    /// `Pigment`.
+    #[inline]
+    pub fn pigment_from_srgb_u8(&self, r: u8, g: u8, b: u8) -> Pigment {
+        self.pigment_from_srgb(
+            r as f32 / u8::MAX as f32,
+            g as f32 / u8::MAX as f32,
+            b as f32 / u8::MAX as f32,
+        )
+    }
+
+    /// Mixes two `u8` component sRGB colors like
+    /// [`mix_srgb_u8()`](crate::mix_srgb_u8), looking both up in the table.
+    #[inline]
+    pub fn mix_srgb_u8(
+        &self,
+        srgb_a: &[u8; 3],
+        srgb_b: &[u8; 3],
+        ratio: f32,
+    ) -> [u8; 3] {
+        Pigment::from_mix(
+            self.pigment_from_srgb_u8(srgb_a[0], srgb_a[1], srgb_a[2]),
+            self.pigment_from_srgb_u8(srgb_b[0], srgb_b[1], srgb_b[2]),
+            ratio,
+        )
+        .into()
+    }
+
+    /// Compares the table to [`Pigment::from_srgb()`] at `samples³` colors
+    /// evenly spread over the sRGB cube.
+    ///
+    /// Colors are compared after converting the pigments back to sRGB.
+    /// Samples that do not fall on grid points of the table are the ones
+    /// that show its error, so pick `samples - 1` coprime to
+    /// `resolution - 1`, e.g. `2 * resolution`.
+    ///
+    /// ```
+    /// # use pigment_mixing::LatentLut;
+    /// let lut = LatentLut::new(17);
+    /// let accuracy = lut.accuracy(34);
+    ///
+    /// assert!(accuracy.mean_delta_e <= accuracy.max_delta_e);
+    /// ```
+    pub fn accuracy(&self, samples: usize) -> LatentLutAccuracy {
+        let n = samples.max(2);
+        let (max_delta_e, sum_delta_e) = (0..n * n * n)
+            .map(|index| {
+                let (r, g, b) = (
+                    sample_position(index / (n * n), n),
+                    sample_position(index / n % n, n),
+                    sample_position(index % n, n),
+                );
+                delta_e_2000(
+                    &Pigment::from_srgb(r, g, b),
+                    &self.pigment_from_srgb(r, g, b),
+                )
+            })
+            .fold((0.0f32, 0.0f32), |(max, sum), delta_e| {
+                (max.max(delta_e), sum + delta_e)
+            });
+
+        LatentLutAccuracy {
+            max_delta_e,
+            mean_delta_e: sum_delta_e / (n * n * n) as f32,
+        }
+    }
+}
+
+/// Fills `slice` with the latents of the grid points with red index `r` of a
+/// table with `n` grid points per axis.
+fn fill_slice(r: usize, n: usize, slice: &mut [[f32; PIGMENT_LEN]]) {
+    slice.iter_mut().enumerate().for_each(|(index, latent)| {
+        *latent = Pigment::from_srgb(
+            sample_position(r, n),
+            sample_position(index / n, n),
+            sample_position(index % n, n),
+        )
+        .into();
+    });
+}
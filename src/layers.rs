@@ -0,0 +1,194 @@
+use crate::{BlendMode, Error, Pigment, PigmentBuffer};
+use alloc::{vec, vec::Vec};
+
+/// A layer of a [`LayerStack`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layer {
+    /// The paint.
+    pub buffer: PigmentBuffer,
+    /// The coverage of every pixel, from `0.0` to `1.0`, in row-major order.
+    ///
+    /// Holds one value per pixel of `buffer`.
+    pub alpha: Vec<f32>,
+    /// The opacity of the whole layer, from `0.0` to `1.0`.
+    pub opacity: f32,
+    /// How the layer is combined with the layers below.
+    pub blend_mode: BlendMode,
+}
+
+impl Layer {
+    /// Constructs a fully covering, opaque layer that [mixes](BlendMode::Mix)
+    /// with the layers below.
+    pub fn new(buffer: PigmentBuffer) -> Self {
+        let len = buffer.pixels().len();
+
+        Self {
+            buffer,
+            alpha: vec![1.0; len],
+            opacity: 1.0,
+            blend_mode: BlendMode::default(),
+        }
+    }
+
+    /// Sets the coverage of every pixel and returns the layer.
+    ///
+    /// # Panics
+    ///
+    /// If `alpha` does not hold one value per pixel.
+    pub fn with_alpha(mut self, alpha: Vec<f32>) -> Self {
+        assert_eq!(self.alpha.len(), alpha.len(), "one value per pixel");
+        self.alpha = alpha;
+        self
+    }
+
+    /// Sets the opacity and returns the layer.
+    #[inline]
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the blend mode and returns the layer.
+    #[inline]
+    pub fn with_blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.blend_mode = blend_mode;
+        self
+    }
+}
+
+/// A stack of [`Layer`]s composited in latent space.
+///
+/// Compositing pigments rather than RGB colors avoids the gray fringes that
+/// appear where layers of complementary colors overlap.
+///
+/// ```
+/// # use pigment_mixing::{BlendMode, Layer, LayerStack, Pigment, PigmentBuffer};
+/// let yellow = PigmentBuffer::new(32, 32, Pigment::from_srgb_u8(254, 236, 0));
+/// let blue = PigmentBuffer::new(32, 32, Pigment::from_srgb_u8(0, 33, 133));
+///
+/// let stack = LayerStack::new(32, 32, Pigment::from_srgb_u8(255, 255, 255))
+///     .with_layer(Layer::new(yellow))
+///     .with_layer(
+///         Layer::new(blue)
+///             .with_opacity(0.5)
+///             .with_blend_mode(BlendMode::Glaze),
+///     );
+///
+/// let rgb = stack.to_srgb_u8().unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerStack {
+    width: usize,
+    height: usize,
+    background: Pigment,
+    layers: Vec<Layer>,
+}
+
+impl LayerStack {
+    /// Constructs an empty `width` × `height` stack over `background`.
+    pub fn new(width: usize, height: usize, background: Pigment) -> Self {
+        Self {
+            width,
+            height,
+            background,
+            layers: Vec::new(),
+        }
+    }
+
+    /// Adds `layer` on top and returns the stack.
+    ///
+    /// # Panics
+    ///
+    /// If the layer's size differs from the stack's.
+    #[inline]
+    pub fn with_layer(mut self, layer: Layer) -> Self {
+        self.push(layer);
+        self
+    }
+
+    /// Adds `layer` on top.
+    ///
+    /// # Panics
+    ///
+    /// If the layer's size differs from the stack's.
+    pub fn push(&mut self, layer: Layer) {
+        assert!(
+            layer.buffer.width() == self.width
+                && layer.buffer.height() == self.height,
+            "layer has the wrong size"
+        );
+        self.layers.push(layer);
+    }
+
+    /// Returns the layers, bottom first.
+    #[inline]
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    /// Returns the layers, bottom first.
+    ///
+    /// Layers changed through this must keep the size of the stack and one
+    /// alpha value per pixel or [`flatten()`](LayerStack::flatten) fails.
+    #[inline]
+    pub fn layers_mut(&mut self) -> &mut [Layer] {
+        &mut self.layers
+    }
+
+    /// Composites all layers, bottom to top, into a single buffer.
+    ///
+    /// Returns [`Error::SizeMismatch`] if a layer differs in size from the
+    /// stack or its alpha does not hold one value per pixel.
+    pub fn flatten(&self) -> Result<PigmentBuffer, Error> {
+        for layer in &self.layers {
+            if layer.buffer.width() != self.width
+                || layer.buffer.height() != self.height
+            {
+                return Err(Error::SizeMismatch("layer has the wrong size"));
+            }
+            if layer.alpha.len() != layer.buffer.pixels().len() {
+                return Err(Error::SizeMismatch("alpha has the wrong size"));
+            }
+        }
+
+        let mut result =
+            PigmentBuffer::new(self.width, self.height, self.background);
+
+        for layer in &self.layers {
+            result
+                .pixels_mut()
+                .iter_mut()
+                .zip(layer.buffer.pixels().iter().zip(&layer.alpha))
+                .for_each(|(base, (&paint, &alpha))| {
+                    let amount = (alpha * layer.opacity).clamp(0.0, 1.0);
+                    if 0.0 < amount {
+                        *base = layer.blend_mode.blend(base, &paint, amount);
+                    }
+                });
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the flattened stack as `u8` component encoded sRGB (gamma 2.2)
+    /// pixels in row-major order with three bytes per pixel.
+    ///
+    /// Fails like [`flatten()`](LayerStack::flatten).
+    #[inline]
+    pub fn to_srgb_u8(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.flatten()?.to_srgb_u8())
+    }
+
+    /// Returns the flattened stack as an [`image::RgbImage`].
+    ///
+    /// Fails like [`flatten()`](LayerStack::flatten).
+    #[cfg(feature = "image")]
+    pub fn to_rgb_image(&self) -> Result<image::RgbImage, Error> {
+        Ok(image::RgbImage::from_raw(
+            self.width as _,
+            self.height as _,
+            self.to_srgb_u8()?,
+        )
+        .expect("buffer size matches the image dimensions"))
+    }
+}
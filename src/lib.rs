@@ -1,4 +1,5 @@
 #![no_std]
+#![cfg_attr(feature = "safe", forbid(unsafe_code))]
 //! High level Rust wrapper around [Mixbox](https://scrtwpns.com/mixbox).
 //!
 //! <p align="center">
@@ -8,6 +9,41 @@
 //! This crate uses the `mixbox-sys` wrapper around the [Mixbox C++ reference
 //! implementation](https://github.com/scrtwpns/pigment-mixing).
 //!
+//! With the `pure-rust` feature an approximation of the Mixbox model written
+//! in Rust is used instead, which needs neither a C++ toolchain nor
+//! `libclang`. Disable the default `cpp` feature to drop `mixbox-sys`
+//! entirely.
+//!
+//! This is not a port of Mixbox. It shares the polynomial that gives the
+//! color of a mix of the four primary pigments, but the table mapping RGB to
+//! pigment concentrations is fitted by the build script with projected
+//! gradient descent. Colors round-trip exactly and mixes behave like paint,
+//! but they differ from the C++ implementation, which this crate does not
+//! measure against. Use `cpp` where results must match other Mixbox
+//! implementations. The `mixbox_reference` example writes mixes as CSV, to
+//! compare both.
+//!
+//! The table is embedded compressed and decompressed on first use, which
+//! needs `std`. Enable `uncompressed-lut` to trade about 90 KiB of binary
+//! size for no work on first use and no `std`. With `runtime-lut` a table can additionally be loaded from a
+//! file shipped with the application, see [`load_lut()`]. Until one is
+//! loaded, the embedded table is used.
+//!
+//! The `fixed-point` feature adds the [`fixed`] module, a Q16.16 integer
+//! implementation for targets without an FPU. It implies `uncompressed-lut`,
+//! so it needs neither `std` nor a heap, e.g. on `thumbv6m-none-eabi`.
+//!
+//! The `deterministic` feature makes mixing bit-identical on every platform,
+//! e.g. for lockstep networking or replays. The latent model then runs on
+//! the fixed-point implementation and transcendental functions are computed
+//! in software by [`libm`](https://docs.rs/libm/). Rust never contracts
+//! floating point operations into FMAs and the remaining ones are correctly
+//! rounded IEEE 754 operations evaluated in a fixed order. Color difference
+//! metrics like [`delta_e_2000()`] are not covered.
+//!
+//! The `safe` feature implies `pure-rust` and builds the crate with
+//! `#![forbid(unsafe_code)]`.
+//!
 //! Mixbox treats colors as if they were made of actual real-world pigments.
 //! It uses the It uses the
 //! [Kubelka-Munk theory](https://en.wikipedia.org/wiki/Kubelka-Munk_theory)
@@ -38,9 +74,8 @@
 //! colors at once using arbitrary weights:
 //!
 //! ```
+//! # #[cfg(feature = "pigment")] {
 //! # use pigment_mixing::Pigment;
-//! use colstodian::{Color, LinearSrgb, Scene};
-//!
 //! // Define three colors as pigments
 //! let bright_yellow_pigment = Pigment::from_srgb_u8(252, 211, 0);
 //! let medium_red_pigment = Pigment::from_srgb_u8(201, 37, 44);
@@ -54,8 +89,51 @@
 //!     + weight * medium_red_pigment
 //!     + weight * deep_blue_pigment;
 //!
-//! // Convert the pigment back to an sRGB color.
-//! let linear_srgb_result: Color<LinearSrgb, Scene> = result.into();
+//! // Convert the pigment back to a linear sRGB color.
+//! let linear_srgb_result: [f32; 3] = result.into();
+//! # }
+//! ```
+//!
+//! With the `colstodian` feature a [`Pigment`] also converts to and from a
+//! `colstodian` `Color<LinearSrgb, Scene>`.
+//!
+//! ## Interchange Format
+//!
+//! With the `serde` feature [`Pigment`], [`Palette`] and [`Gradient`]
+//! implement `Serialize` and `Deserialize`. The `json` and `toml` features
+//! add `from_json()`/`to_json()` and `from_toml()`/`to_toml()` helpers.
+//!
+//! A color is written with three keys. `srgb` is an encoded sRGB hex string
+//! for humans and other tools. `latent` holds the seven latent values of the
+//! pigment mixture for lossless round trips and `model` names the latent
+//! model that wrote them, as latents differ between e.g. the `cpp` and
+//! `pure-rust` features. When reading, either `srgb` or `latent` is enough.
+//! If both are present, `latent` is only used if `model` matches the model of
+//! the reading build and `srgb` otherwise.
+//!
+//! A palette is a list of named colors:
+//!
+//! ```toml
+//! [[colors]]
+//! name = "Cadmium Yellow"
+//! srgb = "#feec00"
+//!
+//! [[colors]]
+//! name = "Ultramarine"
+//! srgb = "#190059"
+//! ```
+//!
+//! A gradient is a list of stops. `easing` (`linear`, `smoothstep`,
+//! `cubic-in`, `cubic-out` or `cubic-in-out`) and `midpoint` are optional and
+//! describe the segment towards the next stop:
+//!
+//! ```json
+//! {
+//!   "stops": [
+//!     { "position": 0.0, "srgb": "#fcd300", "easing": "smoothstep" },
+//!     { "position": 1.0, "srgb": "#000060" }
+//!   ]
+//! }
 //! ```
 //!
 //! ## Notes on Color
@@ -86,8 +164,27 @@
 //! `sRGB` and do decoding (linearization) befor mixing and encoding before
 //! returning.
 //!
-//! All color conversion is done via the excellent
-//! [`colstodian`](https://github.com/termhn/colstodian) crate.
+//! The core only needs plain `[f32; 3]` arrays and tuples. Conversions to
+//! and from the excellent [`colstodian`](https://github.com/termhn/colstodian)
+//! crate's types are behind the `colstodian` feature, which is off by
+//! default.
+//!
+//! ## Performance
+//!
+//! With the `cpp` feature every conversion is an opaque call into the C++
+//! library. With `pure-rust` the whole per-pixel path, from the `mix_`
+//! functions and [`Pigment`] down to the table lookup, is `#[inline]`,
+//! monomorphic and does not allocate, so it inlines into the caller's loop
+//! and can be vectorized there. Converting colors to [`Pigment`]s once and
+//! mixing those is cheaper still, as mixing pigments is a plain lerp.
+//!
+//! Per-frame work can be done without touching the heap. The `mix_`
+//! functions, [`Pigment`], [`gradient_into_slice()`], the dithering
+//! functions and, once constructed, a `Quantizer` never allocate. Where a
+//! convenient function returns a `Vec` or `String` there is an `_into` or
+//! `write_` variant writing to a caller provided buffer instead, e.g.
+//! `Gradient::fill()`, `Gradient::write_css()`, `mixing_chart_into()` and
+//! `solve_recipe_into()`.
 //!
 //! ## License
 //!
@@ -115,20 +212,346 @@
 //! submitted for inclusion in the work by you, as defined in the `Apache-2.0`
 //! license, shall be licensed as above, without any additional terms or
 //! conditions.
-use colstodian::{kolor::Vec3, Color, Display, EncodedSrgb, LinearSrgb};
-use core::mem::MaybeUninit;
-use mixbox_sys::mixbox_lerp_srgb32f;
+#[cfg(feature = "colstodian")]
+use colstodian::{kolor::Vec3, Color, LinearSrgb};
 use num_traits::cast::AsPrimitive;
 
+#[cfg(any(
+    feature = "alloc",
+    all(feature = "pure-rust", not(feature = "uncompressed-lut"))
+))]
+extern crate alloc;
+
+#[cfg(not(any(feature = "cpp", feature = "pure-rust")))]
+compile_error!("either the `cpp` or the `pure-rust` feature must be enabled");
+
+#[cfg(all(
+    feature = "safe",
+    any(
+        feature = "capi",
+        feature = "node",
+        feature = "python",
+        feature = "wasm"
+    )
+))]
+compile_error!("the `safe` feature can not be combined with language bindings");
+
+#[cfg(any(
+    all(feature = "pure-rust", not(feature = "uncompressed-lut")),
+    feature = "node",
+    feature = "png",
+    feature = "python",
+    feature = "runtime-lut"
+))]
+extern crate std;
+
+mod error;
+pub use error::*;
+
+mod latent;
+pub use latent::*;
+
+mod mixbox;
+mod model;
+mod range;
+#[cfg(feature = "runtime-lut")]
+pub use mixbox::{is_lut_loaded, load_lut};
+pub use model::*;
+pub use range::*;
+mod float;
+#[cfg(any(
+    all(feature = "pure-rust", not(feature = "uncompressed-lut")),
+    feature = "runtime-lut"
+))]
+mod lut_codec;
+#[cfg(feature = "pure-rust")]
+mod mixbox_lut;
+#[cfg(all(feature = "pure-rust", not(feature = "deterministic")))]
+mod polynomial;
+mod transfer;
+
+#[cfg(feature = "gradient")]
+mod gradient;
+#[cfg(feature = "gradient")]
+pub use gradient::*;
+
+#[cfg(feature = "gradient")]
+mod ggr;
+
+#[cfg(feature = "pigment")]
+mod harmony;
+#[cfg(feature = "pigment")]
+pub use harmony::*;
+
+#[cfg(feature = "pigment")]
+mod metrics;
+#[cfg(feature = "pigment")]
+pub use metrics::*;
+
+#[cfg(feature = "pigment")]
+mod mixer;
+#[cfg(feature = "pigment")]
+pub use mixer::*;
+
+#[cfg(feature = "pigment")]
+mod compare;
+#[cfg(feature = "pigment")]
+pub use compare::*;
+
+#[cfg(feature = "spectral")]
+mod km;
+#[cfg(feature = "spectral")]
+pub use km::*;
+
+#[cfg(feature = "spectral")]
+mod spectral;
+#[cfg(feature = "spectral")]
+pub use spectral::*;
+
+#[cfg(feature = "pigment")]
+mod adaptation;
+#[cfg(feature = "pigment")]
+pub use adaptation::*;
+
+#[cfg(feature = "pigment")]
+mod blend;
+#[cfg(feature = "pigment")]
+pub use blend::*;
+
+#[cfg(feature = "pigment")]
+mod glaze;
+#[cfg(feature = "pigment")]
+pub use glaze::*;
+
+#[cfg(feature = "pigment")]
+mod noise;
+
+#[cfg(feature = "pigment")]
+mod tonemap;
+#[cfg(feature = "pigment")]
+pub use tonemap::*;
+
+#[cfg(feature = "pigment")]
+mod opacity;
+#[cfg(feature = "pigment")]
+pub use opacity::*;
+
+#[cfg(feature = "pigment")]
+mod solve;
+#[cfg(feature = "pigment")]
+pub use solve::*;
+
+#[cfg(feature = "pigment")]
+mod streak;
+#[cfg(feature = "pigment")]
+pub use streak::*;
+
+#[cfg(feature = "pigment")]
+mod substrate;
+#[cfg(feature = "pigment")]
+pub use substrate::*;
+
+#[cfg(feature = "palette")]
+mod palette;
+#[cfg(feature = "palette")]
+pub use palette::*;
+
+#[cfg(feature = "paints")]
+mod paints;
+
+#[cfg(feature = "palette")]
+mod gamut;
+#[cfg(feature = "palette")]
+pub use gamut::*;
+
+#[cfg(feature = "palette")]
+mod recipe;
+#[cfg(feature = "palette")]
+pub use recipe::*;
+
+#[cfg(feature = "palette")]
+mod reduce;
+#[cfg(feature = "palette")]
+pub use reduce::*;
+
+#[cfg(feature = "serde")]
+mod interchange;
+
+#[cfg(feature = "palette")]
+mod chart;
+#[cfg(feature = "palette")]
+pub use chart::*;
+
+#[cfg(feature = "clut")]
+mod clut;
+#[cfg(feature = "clut")]
+pub use clut::*;
+
+#[cfg(feature = "latent-lut")]
+mod latent_lut;
+#[cfg(feature = "latent-lut")]
+pub use latent_lut::*;
+
+#[cfg(feature = "mix-table")]
+mod mix_table;
+#[cfg(feature = "mix-table")]
+pub use mix_table::*;
+
+#[cfg(feature = "canvas")]
+mod brush;
+#[cfg(feature = "canvas")]
+pub use brush::*;
+
+#[cfg(feature = "canvas")]
+mod buffer;
+#[cfg(feature = "canvas")]
+pub use buffer::*;
+
+#[cfg(feature = "canvas")]
+mod canvas;
+#[cfg(feature = "canvas")]
+pub use canvas::*;
+
+#[cfg(feature = "canvas")]
+mod granulation;
+#[cfg(feature = "canvas")]
+pub use granulation::*;
+
+#[cfg(feature = "canvas")]
+mod layers;
+#[cfg(feature = "canvas")]
+pub use layers::*;
+
+#[cfg(feature = "canvas")]
+mod smudge;
+#[cfg(feature = "canvas")]
+pub use smudge::*;
+
+#[cfg(feature = "ansi")]
+mod ansi;
+#[cfg(feature = "ansi")]
+pub use ansi::*;
+
+#[cfg(feature = "css")]
+mod css;
+#[cfg(feature = "css")]
+pub use css::*;
+
+#[cfg(feature = "palette")]
+mod aco;
+#[cfg(feature = "palette")]
+mod ase;
+#[cfg(feature = "palette")]
+mod bytes;
+#[cfg(feature = "palette")]
+mod gpl;
+
+#[cfg(feature = "approx")]
+mod approx_interop;
+
+#[cfg(feature = "bevy")]
+mod bevy_interop;
+#[cfg(feature = "bevy")]
+pub use bevy_interop::*;
+
+#[cfg(feature = "egui")]
+mod egui_interop;
+#[cfg(feature = "egui")]
+pub use egui_interop::*;
+
+#[cfg(feature = "icc")]
+mod icc;
+#[cfg(feature = "icc")]
+pub use icc::*;
+
+#[cfg(feature = "codegen")]
+mod codegen;
+#[cfg(feature = "codegen")]
+pub use codegen::*;
+
+#[cfg(feature = "image")]
+mod image_interop;
+#[cfg(feature = "image")]
+pub use image_interop::*;
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_interop;
+#[cfg(feature = "nalgebra")]
+pub use nalgebra_interop::*;
+
+#[cfg(feature = "rgb")]
+mod rgb_interop;
+#[cfg(feature = "rgb")]
+pub use rgb_interop::*;
+
+#[cfg(any(feature = "glam", feature = "mint"))]
+mod vector_interop;
+#[cfg(any(feature = "glam", feature = "mint"))]
+pub use vector_interop::*;
+
+#[cfg(feature = "tiny-skia")]
+mod tiny_skia_interop;
+#[cfg(feature = "tiny-skia")]
+pub use tiny_skia_interop::*;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "capi")]
+pub mod capi;
+
+#[cfg(feature = "fixed-point")]
+pub mod fixed;
+
+#[cfg(feature = "node")]
+mod node;
+
+#[cfg(feature = "python")]
+mod python;
+
 #[cfg(feature = "pigment")]
 mod pigment;
 #[cfg(feature = "pigment")]
 pub use pigment::*;
 
+#[cfg(feature = "pigment")]
+mod animator;
+#[cfg(feature = "pigment")]
+pub use animator::*;
+
+#[cfg(feature = "pigment")]
+mod mix_iter;
+#[cfg(feature = "pigment")]
+pub use mix_iter::*;
+
+mod frame;
+pub use frame::*;
+
 mod quantize;
 pub use quantize::*;
 
+mod video;
+pub use video::*;
+
 /// Mixes two linear sRGB colors.
+///
+/// Components outside `0.0..=1.0` are clamped. Use
+/// [`mix_linear_srgb_f32_with()`] for other policies.
+#[inline]
+pub fn mix_linear_srgb_f32<T>(
+    srgb_a: &[f32; 3],
+    srgb_b: &[f32; 3],
+    ratio: T,
+) -> [f32; 3]
+where
+    T: AsPrimitive<f32>,
+{
+    mixbox::lerp_rgb(srgb_a, srgb_b, ratio.as_())
+}
+
+/// Mixes two linear sRGB [`Color`]s.
+#[cfg(feature = "colstodian")]
+#[inline]
 pub fn mix_linear_srgb<T, St>(
     srgb_a: &Color<LinearSrgb, St>,
     srgb_b: &Color<LinearSrgb, St>,
@@ -137,24 +560,13 @@ pub fn mix_linear_srgb<T, St>(
 where
     T: AsPrimitive<f32>,
 {
-    let mut color = MaybeUninit::<Vec3>::uninit();
-    let color_ptr = color.as_mut_ptr().cast::<f32>();
-
-    Color::from_raw(unsafe {
-        mixbox_lerp_srgb32f(
-            srgb_a.raw[0],
-            srgb_a.raw[1],
-            srgb_a.raw[2],
-            srgb_b.raw[0],
-            srgb_b.raw[1],
-            srgb_b.raw[2],
-            ratio.as_(),
-            color_ptr as _,
-            color_ptr.offset(1) as _,
-            color_ptr.offset(2) as _,
-        );
-        color.assume_init()
-    })
+    let [r, g, b] = mix_linear_srgb_f32(
+        &[srgb_a.raw[0], srgb_a.raw[1], srgb_a.raw[2]],
+        &[srgb_b.raw[0], srgb_b.raw[1], srgb_b.raw[2]],
+        ratio,
+    );
+
+    Color::from_raw(Vec3::new(r, g, b))
 }
 
 /// Mixes two `u8` component sRGB colors.
@@ -170,19 +582,16 @@ where
     T: AsPrimitive<f32>,
     f32: From<T>,
 {
-    let a_linear: Color<LinearSrgb, _> =
-        Color::<EncodedSrgb, _>::from_u8(*srgb_a).linearize();
-
-    let b_linear: Color<LinearSrgb, _> =
-        Color::<EncodedSrgb, _>::from_u8(*srgb_b).linearize();
+    let a_linear = transfer::decode_srgb_u8(*srgb_a);
+    let b_linear = transfer::decode_srgb_u8(*srgb_b);
 
-    let result = mix_linear_srgb(&a_linear, &b_linear, ratio)
-        .convert_to::<EncodedSrgb>();
+    let result =
+        transfer::encode_srgb(mix_linear_srgb_f32(&a_linear, &b_linear, ratio));
 
     [
-        (result.raw[0] * u8::MAX as f32 + 0.5) as _,
-        (result.raw[1] * u8::MAX as f32 + 0.5) as _,
-        (result.raw[2] * u8::MAX as f32 + 0.5) as _,
+        (result[0] * u8::MAX as f32 + 0.5) as _,
+        (result[1] * u8::MAX as f32 + 0.5) as _,
+        (result[2] * u8::MAX as f32 + 0.5) as _,
     ]
 }
 
@@ -193,39 +602,31 @@ where
 /// Colors will be linearized internally before mixing.
 ///
 /// The output is in sRGB with an encoded gamma of 2.2.
-/// It is quantized from [`f32`] to [`u8`] using  an error diffusion dither with
-/// an amplitude of 0.5.
+/// It is quantized from [`f32`] to [`u8`] using a random dither with the
+/// given `amplitude`, drawn per channel or shared as selected by `noise`. Use
+/// [`dither_srgb_u8_into()`] for error diffusion over whole buffers.
 #[inline]
-pub fn mix_srgb_u8_dither<T>(
+pub fn mix_srgb_u8_dither<T, R>(
     srgb_a: &[u8; 3],
     srgb_b: &[u8; 3],
     ratio: T,
-    rng: &mut Rng,
+    noise: DitherNoise,
+    amplitude: DitherAmplitude,
+    rng: &mut R,
 ) -> [u8; 3]
 where
     T: AsPrimitive<f32>,
     f32: From<T>,
+    R: DitherRng + ?Sized,
 {
-    let a_linear: Color<LinearSrgb, _> =
-        Color::<EncodedSrgb, _>::from_u8(*srgb_a).linearize();
-
-    let b_linear: Color<LinearSrgb, _> =
-        Color::<EncodedSrgb, _>::from_u8(*srgb_b).linearize();
-
-    let result = mix_linear_srgb(&a_linear, &b_linear, ratio)
-        .convert_to::<EncodedSrgb>();
-
-    // Quantize the result to u8 using an error diffusion dither
-    // with an amplitude of 0.5 to avoid artifacts.
-    let (r, g, b) = quantize_triplet(
-        (result.raw[0], result.raw[1], result.raw[2]),
-        u8::MAX as _, // one
-        0.0,          // min
-        u8::MAX as _, // max
-        rng,
-    );
+    let a_linear = transfer::decode_srgb_u8(*srgb_a);
+    let b_linear = transfer::decode_srgb_u8(*srgb_b);
+
+    let result =
+        transfer::encode_srgb(mix_linear_srgb_f32(&a_linear, &b_linear, ratio));
 
-    [r as _, g as _, b as _]
+    // Quantize the result to u8 using a random dither to avoid artifacts.
+    quantize_triplet(result, noise, amplitude, rng)
 }
 
 /// Mixes two `u16` component sRGB colors.
@@ -243,73 +644,49 @@ where
     T: AsPrimitive<f32>,
     f32: From<T>,
 {
-    let a_linear: Color<LinearSrgb, _> = Color::<EncodedSrgb, Display>::new(
-        srgb_a[0] as f32 / u16::MAX as f32,
-        srgb_a[1] as f32 / u16::MAX as f32,
-        srgb_a[2] as f32 / u16::MAX as f32,
-    )
-    .linearize();
-    let b_linear: Color<LinearSrgb, _> = Color::<EncodedSrgb, Display>::new(
-        srgb_b[0] as f32 / u16::MAX as f32,
-        srgb_b[1] as f32 / u16::MAX as f32,
-        srgb_b[2] as f32 / u16::MAX as f32,
-    )
-    .linearize();
+    let a_linear = transfer::decode_srgb_u16(*srgb_a);
+    let b_linear = transfer::decode_srgb_u16(*srgb_b);
 
-    let result = mix_linear_srgb(&a_linear, &b_linear, ratio)
-        .convert_to::<EncodedSrgb>();
+    let result =
+        transfer::encode_srgb(mix_linear_srgb_f32(&a_linear, &b_linear, ratio));
 
     [
-        (result.raw[0] * u16::MAX as f32 + 0.5) as _,
-        (result.raw[1] * u16::MAX as f32 + 0.5) as _,
-        (result.raw[2] * u16::MAX as f32 + 0.5) as _,
+        (result[0] * u16::MAX as f32 + 0.5) as _,
+        (result[1] * u16::MAX as f32 + 0.5) as _,
+        (result[2] * u16::MAX as f32 + 0.5) as _,
     ]
 }
 
-/// Mixes two `u8` component sRGB colors and dithers the result.
+/// Mixes two `u16` component sRGB colors and dithers the result.
 ///
 /// The colors are assumed to be in encoded sRGB (gamma 2.2).
 ///
-/// The output is quantizes the with an error diffusion dither with an amplitude
-/// of 0.5 is in sRGB with an encoded gamma of 2.2.
+/// The output is quantized with a random dither with the given `amplitude`,
+/// drawn per channel or shared as selected by `noise`, and is in sRGB with an
+/// encoded gamma of 2.2. Use [`dither_srgb_u16_into()`] for error diffusion
+/// over whole buffers.
 #[inline]
-pub fn mix_srgb_u16_dither<T>(
-    srgb_a: &[u8; 3],
-    srgb_b: &[u8; 3],
+pub fn mix_srgb_u16_dither<T, R>(
+    srgb_a: &[u16; 3],
+    srgb_b: &[u16; 3],
     ratio: T,
-    rng: &mut Rng,
-) -> [u8; 3]
+    noise: DitherNoise,
+    amplitude: DitherAmplitude,
+    rng: &mut R,
+) -> [u16; 3]
 where
     T: AsPrimitive<f32>,
     f32: From<T>,
+    R: DitherRng + ?Sized,
 {
-    let a_linear: Color<LinearSrgb, _> = Color::<EncodedSrgb, Display>::new(
-        srgb_a[0] as f32 / u16::MAX as f32,
-        srgb_a[1] as f32 / u16::MAX as f32,
-        srgb_a[2] as f32 / u16::MAX as f32,
-    )
-    .linearize();
-    let b_linear: Color<LinearSrgb, _> = Color::<EncodedSrgb, Display>::new(
-        srgb_b[0] as f32 / u16::MAX as f32,
-        srgb_b[1] as f32 / u16::MAX as f32,
-        srgb_b[2] as f32 / u16::MAX as f32,
-    )
-    .linearize();
-
-    let result = mix_linear_srgb(&a_linear, &b_linear, ratio)
-        .convert_to::<EncodedSrgb>();
-
-    // Quantize the result to u16 using an error diffusion dither
-    // with an amplitude of 0.5 to avoid artifacts.
-    let (r, g, b) = quantize_triplet(
-        (result.raw[0], result.raw[1], result.raw[2]),
-        u16::MAX as _, // one
-        0.0,           // min
-        u16::MAX as _, // max
-        rng,
-    );
+    let a_linear = transfer::decode_srgb_u16(*srgb_a);
+    let b_linear = transfer::decode_srgb_u16(*srgb_b);
+
+    let result =
+        transfer::encode_srgb(mix_linear_srgb_f32(&a_linear, &b_linear, ratio));
 
-    [r as _, g as _, b as _]
+    // Quantize the result to u16 using a random dither to avoid artifacts.
+    quantize_triplet(result, noise, amplitude, rng)
 }
 
 /// Mixes two `u16` component linear sRGB colors.
@@ -327,67 +704,186 @@ where
     T: AsPrimitive<f32>,
     f32: From<T>,
 {
-    let a_linear = Color::<LinearSrgb, Display>::new(
-        srgb_a[0] as f32 / u16::MAX as f32,
-        srgb_a[1] as f32 / u16::MAX as f32,
-        srgb_a[2] as f32 / u16::MAX as f32,
-    );
-    let b_linear = Color::<LinearSrgb, Display>::new(
-        srgb_b[0] as f32 / u16::MAX as f32,
-        srgb_b[1] as f32 / u16::MAX as f32,
-        srgb_b[2] as f32 / u16::MAX as f32,
-    );
+    let a_linear = srgb_a.map(|c| c as f32 / u16::MAX as f32);
+    let b_linear = srgb_b.map(|c| c as f32 / u16::MAX as f32);
 
-    let result = mix_linear_srgb(&a_linear, &b_linear, ratio);
+    let result = mix_linear_srgb_f32(&a_linear, &b_linear, ratio);
 
     [
-        (result.raw[0] * u16::MAX as f32 + 0.5) as _,
-        (result.raw[1] * u16::MAX as f32 + 0.5) as _,
-        (result.raw[2] * u16::MAX as f32 + 0.5) as _,
+        (result[0] * u16::MAX as f32 + 0.5) as _,
+        (result[1] * u16::MAX as f32 + 0.5) as _,
+        (result[2] * u16::MAX as f32 + 0.5) as _,
     ]
 }
 
-/// Mixes two `u8` component linear sRGB colors and dithers the result.
+/// Mixes two `u16` component linear sRGB colors and dithers the result.
 ///
 /// The colors are assumed to be in linear sRGB (gamma 1.0).
 ///
-/// The output is quantizes the with an error diffusion dither with an amplitude
-/// of 0.5 is in sRGB with an encoded gamma of 2.2.
+/// The output is quantized with a random dither with the given `amplitude`,
+/// drawn per channel or shared as selected by `noise`, and is in sRGB with an
+/// encoded gamma of 2.2. Use [`dither_srgb_u16_into()`] for error diffusion
+/// over whole buffers.
+#[inline]
+pub fn mix_linear_srgb_u16_dither<T, R>(
+    srgb_a: &[u16; 3],
+    srgb_b: &[u16; 3],
+    ratio: T,
+    noise: DitherNoise,
+    amplitude: DitherAmplitude,
+    rng: &mut R,
+) -> [u16; 3]
+where
+    T: AsPrimitive<f32>,
+    f32: From<T>,
+    R: DitherRng + ?Sized,
+{
+    let a_linear = srgb_a.map(|c| c as f32 / u16::MAX as f32);
+    let b_linear = srgb_b.map(|c| c as f32 / u16::MAX as f32);
+
+    let result = mix_linear_srgb_f32(&a_linear, &b_linear, ratio);
+
+    // Quantize the result to u16 using a random dither to avoid artifacts.
+    quantize_triplet(result, noise, amplitude, rng)
+}
+
+/// Mixes two `u8` component sRGB colors and dithers the result with an
+/// internal RNG.
+///
+/// Like [`mix_srgb_u8_dither()`] with the default [`DitherNoise`] and
+/// [`DitherAmplitude`], for when threading an RNG through is a hassle. The
+/// output is not reproducible. Use the explicit RNG variant with a seeded
+/// [`Rng`] for that.
+#[cfg(target_has_atomic = "64")]
 #[inline]
-pub fn mix_linear_srgb_u16_dither<T>(
+pub fn mix_srgb_u8_dither_auto<T>(
     srgb_a: &[u8; 3],
     srgb_b: &[u8; 3],
     ratio: T,
-    rng: &mut Rng,
 ) -> [u8; 3]
 where
     T: AsPrimitive<f32>,
     f32: From<T>,
 {
-    let a_linear = Color::<LinearSrgb, Display>::new(
-        srgb_a[0] as f32 / u16::MAX as f32,
-        srgb_a[1] as f32 / u16::MAX as f32,
-        srgb_a[2] as f32 / u16::MAX as f32,
-    );
-    let b_linear = Color::<LinearSrgb, Display>::new(
-        srgb_b[0] as f32 / u16::MAX as f32,
-        srgb_b[1] as f32 / u16::MAX as f32,
-        srgb_b[2] as f32 / u16::MAX as f32,
-    );
+    mix_srgb_u8_dither(
+        srgb_a,
+        srgb_b,
+        ratio,
+        DitherNoise::default(),
+        DitherAmplitude::default(),
+        &mut GlobalRng,
+    )
+}
 
-    let result = mix_linear_srgb(&a_linear, &b_linear, ratio);
+/// Mixes two `u16` component sRGB colors and dithers the result with an
+/// internal RNG.
+///
+/// See [`mix_srgb_u8_dither_auto()`].
+#[cfg(target_has_atomic = "64")]
+#[inline]
+pub fn mix_srgb_u16_dither_auto<T>(
+    srgb_a: &[u16; 3],
+    srgb_b: &[u16; 3],
+    ratio: T,
+) -> [u16; 3]
+where
+    T: AsPrimitive<f32>,
+    f32: From<T>,
+{
+    mix_srgb_u16_dither(
+        srgb_a,
+        srgb_b,
+        ratio,
+        DitherNoise::default(),
+        DitherAmplitude::default(),
+        &mut GlobalRng,
+    )
+}
 
-    // Quantize the result to u16 using an error diffusion dither
-    // with an amplitude of 0.5 to avoid artifacts.
-    let (r, g, b) = quantize_triplet(
-        (result.raw[0], result.raw[1], result.raw[2]),
-        u16::MAX as _, // one
-        0.0,           // min
-        u16::MAX as _, // max
-        rng,
-    );
+/// Mixes two `u16` component linear sRGB colors and dithers the result with
+/// an internal RNG.
+///
+/// See [`mix_srgb_u8_dither_auto()`].
+#[cfg(target_has_atomic = "64")]
+#[inline]
+pub fn mix_linear_srgb_u16_dither_auto<T>(
+    srgb_a: &[u16; 3],
+    srgb_b: &[u16; 3],
+    ratio: T,
+) -> [u16; 3]
+where
+    T: AsPrimitive<f32>,
+    f32: From<T>,
+{
+    mix_linear_srgb_u16_dither(
+        srgb_a,
+        srgb_b,
+        ratio,
+        DitherNoise::default(),
+        DitherAmplitude::default(),
+        &mut GlobalRng,
+    )
+}
+
+/// Writes a gradient between two `u8` component sRGB colors into `out`.
+///
+/// The colors are assumed to be in encoded sRGB (gamma 2.2).
+///
+/// The first and last element of `out` receive `srgb_a` and `srgb_b`
+/// respectively. The elements in between are evenly spaced mixes of the two.
+///
+/// This does not allocate. E.g. `out` can be the DMA buffer of an LED strip.
+pub fn gradient_into_slice(
+    srgb_a: &[u8; 3],
+    srgb_b: &[u8; 3],
+    out: &mut [[u8; 3]],
+) {
+    let a_linear = transfer::decode_srgb_u8(*srgb_a);
+    let b_linear = transfer::decode_srgb_u8(*srgb_b);
 
-    [r as _, g as _, b as _]
+    let n = out.len();
+    out.iter_mut().enumerate().for_each(|(i, srgb)| {
+        let result = transfer::encode_srgb(mix_linear_srgb_f32(
+            &a_linear,
+            &b_linear,
+            sample_position(i, n),
+        ));
+
+        *srgb = [
+            (result[0] * u8::MAX as f32 + 0.5) as _,
+            (result[1] * u8::MAX as f32 + 0.5) as _,
+            (result[2] * u8::MAX as f32 + 0.5) as _,
+        ];
+    });
+}
+
+/// Parses `#rrggbb` or `rrggbb`.
+#[cfg(any(feature = "serde", feature = "wasm", feature = "node"))]
+pub(crate) fn parse_hex(hex: &str) -> Option<[u8; 3]> {
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if 6 != hex.len() || !hex.is_ascii() {
+        return None;
+    }
+    let component = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+
+    Some([component(0)?, component(2)?, component(4)?])
+}
+
+/// Formats `#rrggbb`.
+#[cfg(any(feature = "wasm", feature = "node"))]
+pub(crate) fn to_hex(srgb: [u8; 3]) -> alloc::string::String {
+    alloc::format!("#{:02x}{:02x}{:02x}", srgb[0], srgb[1], srgb[2])
+}
+
+/// Returns the position of the `i`th of `n` evenly spaced samples in
+/// `0.0..=1.0`.
+#[inline]
+pub(crate) fn sample_position(i: usize, n: usize) -> f32 {
+    if n < 2 {
+        0.0
+    } else {
+        i as f32 / (n - 1) as f32
+    }
 }
 
 #[inline]
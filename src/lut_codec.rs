@@ -0,0 +1,151 @@
+//! The compressed storage format of the table of the pure Rust Mixbox model.
+//!
+//! This module is shared with the build script, which writes the table.
+//!
+//! The table starts with [`MAGIC`]. It is quantized to `u16` and stored in
+//! `r`, `g`, `b` order. Each
+//! value is predicted from its already decoded neighbors in the RGB cube and
+//! only the residual of that prediction is stored, zigzag encoded as an
+//! [LEB128](https://en.wikipedia.org/wiki/LEB128) varint. Residuals are tiny
+//! as the table is smooth, so almost all of them fit in a single byte.
+use crate::mixbox_lut::LUT_RESOLUTION;
+use alloc::vec::Vec;
+
+/// The number of samples in the table.
+pub(crate) const LUT_LEN: usize =
+    LUT_RESOLUTION * LUT_RESOLUTION * LUT_RESOLUTION;
+
+/// The bytes a compressed table starts with. The last one is the version of
+/// the format.
+pub(crate) const MAGIC: &[u8] = b"MIXBXLU\x01";
+
+/// Returns the prediction of the sample at `index` from its already decoded
+/// neighbors in `lut`.
+///
+/// This is exact for tables that are trilinear within a cell.
+pub(crate) fn predict(lut: &[[u16; 3]], index: usize) -> [i32; 3] {
+    let n = LUT_RESOLUTION;
+    let (r, g, b) = (index / (n * n), index / n % n, index % n);
+
+    let mut prediction = [0i32; 3];
+    for (dr, dg, db) in [
+        (1, 0, 0),
+        (0, 1, 0),
+        (0, 0, 1),
+        (1, 1, 0),
+        (1, 0, 1),
+        (0, 1, 1),
+        (1, 1, 1),
+    ] {
+        if r < dr || g < dg || b < db {
+            continue;
+        }
+        let neighbor = lut[((r - dr) * n + g - dg) * n + b - db];
+        // Inclusion–exclusion over the corners of the cell behind `index`.
+        let sign = if 0 == (dr + dg + db) % 2 { -1 } else { 1 };
+        prediction
+            .iter_mut()
+            .zip(neighbor)
+            .for_each(|(prediction, c)| *prediction += sign * c as i32);
+    }
+
+    prediction
+}
+
+/// Reads a varint of at most 32 bits from `bytes`.
+///
+/// Returns `None` if `bytes` ends early or the varint is longer.
+fn read_varint<'a>(bytes: &mut impl Iterator<Item = &'a u8>) -> Option<u32> {
+    let mut value = 0u32;
+    for shift in (0..32).step_by(7) {
+        let byte = *bytes.next()?;
+        // The fifth byte holds the top four bits and must end the varint.
+        if 28 == shift && 0x0f < byte {
+            return None;
+        }
+        value |= ((byte & 0x7f) as u32) << shift;
+        if 0 == byte & 0x80 {
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+/// Decodes the compressed, quantized table in `bytes`.
+///
+/// Returns `None` if `bytes` is truncated or malformed.
+pub(crate) fn decode(bytes: &[u8]) -> Option<Vec<[u16; 3]>> {
+    let mut bytes = bytes.strip_prefix(MAGIC)?.iter();
+    let mut lut = Vec::with_capacity(LUT_LEN);
+
+    for index in 0..LUT_LEN {
+        let prediction = predict(&lut, index);
+        let mut sample = [0u16; 3];
+        for (sample, prediction) in sample.iter_mut().zip(prediction) {
+            let zigzag = read_varint(&mut bytes)?;
+            let residual = (zigzag >> 1) as i32 ^ -((zigzag & 1) as i32);
+            *sample = u16::try_from(prediction.checked_add(residual)?).ok()?;
+        }
+        lut.push(sample);
+    }
+
+    bytes.next().is_none().then_some(lut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// Returns a table whose samples are all `0`, followed by `tail`.
+    fn zeros(tail: &[u8]) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.resize(MAGIC.len() + 3 * LUT_LEN, 0);
+        bytes.extend_from_slice(tail);
+
+        bytes
+    }
+
+    #[test]
+    fn decodes_a_table() {
+        assert_eq!(Some(vec![[0; 3]; LUT_LEN]), decode(&zeros(&[])));
+    }
+
+    #[test]
+    fn rejects_the_wrong_length() {
+        let bytes = zeros(&[]);
+
+        assert_eq!(None, decode(&bytes[..bytes.len() - 1]));
+        assert_eq!(None, decode(&zeros(&[0])));
+        assert_eq!(None, decode(&bytes[1..]));
+    }
+
+    #[test]
+    fn rejects_long_varints() {
+        let mut bytes = zeros(&[]);
+        // Five bytes with the continuation bit set, then the end.
+        bytes.splice(MAGIC.len()..MAGIC.len() + 1, [0x80; 5]);
+        assert_eq!(None, decode(&bytes));
+
+        // A fifth byte with bits beyond 32.
+        let mut bytes = zeros(&[]);
+        bytes.splice(
+            MAGIC.len()..MAGIC.len() + 1,
+            [0x80, 0x80, 0x80, 0x80, 0x10],
+        );
+        assert_eq!(None, decode(&bytes));
+    }
+
+    #[test]
+    fn rejects_overflowing_residuals() {
+        let mut bytes = zeros(&[]);
+        // The first red is `u16::MAX`, zigzag encoded.
+        bytes.splice(MAGIC.len()..MAGIC.len() + 1, [0xfe, 0xff, 0x07]);
+        // The second red is predicted as the first, plus `i32::MAX`.
+        let second = MAGIC.len() + 3 + 2;
+        bytes.splice(second..second + 1, [0xfe, 0xff, 0xff, 0xff, 0x0f]);
+
+        assert_eq!(None, decode(&bytes));
+    }
+}
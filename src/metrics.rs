@@ -0,0 +1,181 @@
+//! Perceptual color difference metrics.
+use crate::Pigment;
+use num_traits::Float;
+
+/// Converts linear sRGB to Oklab.
+pub(crate) fn linear_srgb_to_oklab(srgb: [f32; 3]) -> [f32; 3] {
+    let [r, g, b] = srgb;
+
+    let l = 0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b;
+    let m = 0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b;
+    let s = 0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b;
+
+    let (l, m, s) = (Float::cbrt(l), Float::cbrt(m), Float::cbrt(s));
+
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+/// Converts Oklab to linear sRGB.
+pub(crate) fn oklab_to_linear_srgb(lab: [f32; 3]) -> [f32; 3] {
+    let [l, a, b] = lab;
+
+    let l_ = l + 0.396_337_78 * a + 0.215_803_76 * b;
+    let m_ = l - 0.105_561_35 * a - 0.063_854_17 * b;
+    let s_ = l - 0.089_484_18 * a - 1.291_485_5 * b;
+
+    let (l, m, s) = (l_ * l_ * l_, m_ * m_ * m_, s_ * s_ * s_);
+
+    [
+        4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+        -1.268_438 * l + 2.609_757_4 * m - 0.341_319_4 * s,
+        -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+    ]
+}
+
+/// Converts linear sRGB to CIELAB with a D65 white point.
+pub(crate) fn linear_srgb_to_lab(srgb: [f32; 3]) -> [f32; 3] {
+    let [r, g, b] = srgb;
+
+    // Linear sRGB -> XYZ, normalized by the D65 white point.
+    let x = (0.412_390_8 * r + 0.357_584_33 * g + 0.180_480_8 * b) / 0.950_47;
+    let y = 0.212_639 * r + 0.715_168_65 * g + 0.072_192_32 * b;
+    let z = (0.019_330_818 * r + 0.119_194_78 * g + 0.950_532_1 * b) / 1.088_83;
+
+    let f = |t: f32| {
+        if t > 0.008_856_452 {
+            Float::cbrt(t)
+        } else {
+            7.787_037 * t + 4.0 / 29.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// Returns the Euclidean distance of two linear sRGB colors in
+/// [Oklab](https://bottosson.github.io/posts/oklab/), scaled by `100`.
+///
+/// The scale makes the result comparable to [`delta_e_2000_linear_srgb()`]. A
+/// difference below about `2` is hard to notice for most people.
+pub fn delta_e_ok_linear_srgb(a: [f32; 3], b: [f32; 3]) -> f32 {
+    let a = linear_srgb_to_oklab(a);
+    let b = linear_srgb_to_oklab(b);
+
+    let (dl, da, db) = (a[0] - b[0], a[1] - b[1], a[2] - b[2]);
+
+    100.0 * Float::sqrt(dl * dl + da * da + db * db)
+}
+
+/// Returns the Euclidean distance of two pigments in Oklab, scaled by `100`.
+///
+/// This is the metric used by the solvers and lookups of this crate. See
+/// [`delta_e_ok_linear_srgb()`].
+///
+/// ```
+/// # use pigment_mixing::{delta_e_ok, Pigment};
+/// let a = Pigment::from_srgb_u8(254, 236, 0);
+///
+/// assert_eq!(0.0, delta_e_ok(&a, &a));
+/// ```
+pub fn delta_e_ok(a: &Pigment, b: &Pigment) -> f32 {
+    delta_e_ok_linear_srgb((*a).into(), (*b).into())
+}
+
+/// Returns the
+/// [CIEDE2000](https://en.wikipedia.org/wiki/Color_difference#CIEDE2000)
+/// difference of two linear sRGB colors.
+pub fn delta_e_2000_linear_srgb(a: [f32; 3], b: [f32; 3]) -> f32 {
+    let [l1, a1, b1] = linear_srgb_to_lab(a);
+    let [l2, a2, b2] = linear_srgb_to_lab(b);
+
+    let pow7 = |x: f32| Float::powi(x, 7);
+    let twenty_five_pow7 = 6_103_515_625.0f32;
+
+    let c_mean = 0.5 * (Float::hypot(a1, b1) + Float::hypot(a2, b2));
+    let g = 0.5
+        * (1.0 - Float::sqrt(pow7(c_mean) / (pow7(c_mean) + twenty_five_pow7)));
+
+    let (a1, a2) = ((1.0 + g) * a1, (1.0 + g) * a2);
+    let (c1, c2) = (Float::hypot(a1, b1), Float::hypot(a2, b2));
+
+    let hue = |b: f32, a: f32| {
+        if 0.0 == a && 0.0 == b {
+            0.0
+        } else {
+            let hue = Float::to_degrees(Float::atan2(b, a));
+            if hue < 0.0 {
+                hue + 360.0
+            } else {
+                hue
+            }
+        }
+    };
+    let (h1, h2) = (hue(b1, a1), hue(b2, a2));
+
+    let dl = l2 - l1;
+    let dc = c2 - c1;
+    let dh = if 0.0 == c1 * c2 {
+        0.0
+    } else if Float::abs(h2 - h1) <= 180.0 {
+        h2 - h1
+    } else if h2 <= h1 {
+        h2 - h1 + 360.0
+    } else {
+        h2 - h1 - 360.0
+    };
+    let dh =
+        2.0 * Float::sqrt(c1 * c2) * Float::sin(Float::to_radians(0.5 * dh));
+
+    let l_mean = 0.5 * (l1 + l2);
+    let c_mean = 0.5 * (c1 + c2);
+    let h_mean = if 0.0 == c1 * c2 {
+        h1 + h2
+    } else if Float::abs(h1 - h2) <= 180.0 {
+        0.5 * (h1 + h2)
+    } else if h1 + h2 < 360.0 {
+        0.5 * (h1 + h2 + 360.0)
+    } else {
+        0.5 * (h1 + h2 - 360.0)
+    };
+
+    let t = 1.0 - 0.17 * Float::cos(Float::to_radians(h_mean - 30.0))
+        + 0.24 * Float::cos(Float::to_radians(2.0 * h_mean))
+        + 0.32 * Float::cos(Float::to_radians(3.0 * h_mean + 6.0))
+        - 0.20 * Float::cos(Float::to_radians(4.0 * h_mean - 63.0));
+
+    let l_mean_50 = (l_mean - 50.0) * (l_mean - 50.0);
+    let sl = 1.0 + 0.015 * l_mean_50 / Float::sqrt(20.0 + l_mean_50);
+    let sc = 1.0 + 0.045 * c_mean;
+    let sh = 1.0 + 0.015 * c_mean * t;
+
+    let h_offset = (h_mean - 275.0) / 25.0;
+    let rt = -2.0
+        * Float::sqrt(pow7(c_mean) / (pow7(c_mean) + twenty_five_pow7))
+        * Float::sin(Float::to_radians(
+            60.0 * Float::exp(-h_offset * h_offset),
+        ));
+
+    let (l, c, h) = (dl / sl, dc / sc, dh / sh);
+
+    Float::sqrt(l * l + c * c + h * h + rt * c * h)
+}
+
+/// Returns the
+/// [CIEDE2000](https://en.wikipedia.org/wiki/Color_difference#CIEDE2000)
+/// difference of two pigments.
+///
+/// ```
+/// # use pigment_mixing::{delta_e_2000, Pigment};
+/// let black = Pigment::from_srgb_u8(0, 0, 0);
+/// let white = Pigment::from_srgb_u8(255, 255, 255);
+///
+/// assert!((delta_e_2000(&black, &white) - 100.0).abs() < 1.0);
+/// ```
+pub fn delta_e_2000(a: &Pigment, b: &Pigment) -> f32 {
+    delta_e_2000_linear_srgb((*a).into(), (*b).into())
+}
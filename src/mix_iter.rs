@@ -0,0 +1,272 @@
+//! Iterator adaptors for mixing streams of [`Pigment`]s.
+use crate::{sample_position, Pigment};
+use core::{borrow::Borrow, iter::FusedIterator, ops::Range};
+
+/// Adaptors for mixing the [`Pigment`]s of an iterator.
+///
+/// Implemented for every iterator over `Pigment`s or references to them.
+/// None of the adaptors allocate.
+///
+/// ```
+/// # use pigment_mixing::{MixExt, Pigment};
+/// let colors = [
+///     Pigment::from_srgb_u8(254, 236, 0),
+///     Pigment::from_srgb_u8(25, 0, 89),
+///     Pigment::from_srgb_u8(255, 255, 255),
+/// ];
+///
+/// // Yellow & blue, blue & white.
+/// let between: Vec<[u8; 3]> =
+///     colors.iter().mix_pairs(0.5).map(Into::into).collect();
+/// assert_eq!(2, between.len());
+///
+/// let all = colors.iter().accumulate_pigment().unwrap();
+/// ```
+pub trait MixExt: Iterator + Sized
+where
+    Self::Item: Borrow<Pigment>,
+{
+    /// Mixes each item with the next one using `ratio`.
+    ///
+    /// Yields one item less than the iterator.
+    #[inline]
+    fn mix_pairs(self, ratio: f32) -> MixPairs<Self> {
+        MixPairs {
+            iter: self,
+            previous: None,
+            ratio,
+        }
+    }
+
+    /// Mixes each item with the corresponding one of `other`, using the
+    /// corresponding ratio of `ratios`.
+    ///
+    /// Stops when any of the three runs out. Use
+    /// [`core::iter::repeat()`] for a constant ratio.
+    ///
+    /// ```
+    /// # use pigment_mixing::{MixExt, Pigment};
+    /// let yellow = Pigment::from_srgb_u8(254, 236, 0);
+    /// let blue = Pigment::from_srgb_u8(25, 0, 89);
+    ///
+    /// let ramp = core::iter::repeat(yellow).mix_with(
+    ///     core::iter::repeat(blue),
+    ///     (0..8).map(|i| i as f32 / 7.0),
+    /// );
+    /// assert_eq!(8, ramp.count());
+    /// ```
+    #[inline]
+    fn mix_with<J, R>(
+        self,
+        other: J,
+        ratios: R,
+    ) -> MixWith<Self, J::IntoIter, R::IntoIter>
+    where
+        J: IntoIterator,
+        J::Item: Borrow<Pigment>,
+        R: IntoIterator<Item = f32>,
+    {
+        MixWith {
+            a: self,
+            b: other.into_iter(),
+            ratios: ratios.into_iter(),
+        }
+    }
+
+    /// Mixes all items in equal parts.
+    ///
+    /// Returns `None` if the iterator is empty.
+    #[inline]
+    fn accumulate_pigment(self) -> Option<Pigment> {
+        let (sum, count) =
+            self.fold((None, 0usize), |(sum, count), pigment| {
+                let pigment = *pigment.borrow();
+                (
+                    Some(match sum {
+                        Some(sum) => sum + pigment,
+                        None => pigment,
+                    }),
+                    count + 1,
+                )
+            });
+
+        sum.map(|sum| sum * (1.0 / count as f32))
+    }
+}
+
+impl<I> MixExt for I
+where
+    I: Iterator,
+    I::Item: Borrow<Pigment>,
+{
+}
+
+/// An iterator mixing consecutive items, see [`MixExt::mix_pairs()`].
+#[derive(Clone, Debug)]
+pub struct MixPairs<I> {
+    iter: I,
+    previous: Option<Pigment>,
+    ratio: f32,
+}
+
+impl<I> Iterator for MixPairs<I>
+where
+    I: Iterator,
+    I::Item: Borrow<Pigment>,
+{
+    type Item = Pigment;
+
+    #[inline]
+    fn next(&mut self) -> Option<Pigment> {
+        let previous = match self.previous {
+            Some(previous) => previous,
+            None => *self.iter.next()?.borrow(),
+        };
+        let next = *self.iter.next()?.borrow();
+        self.previous = Some(next);
+
+        Some(Pigment::from_mix(previous, next, self.ratio))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        match self.previous {
+            Some(_) => (lower, upper),
+            None => {
+                (lower.saturating_sub(1), upper.map(|u| u.saturating_sub(1)))
+            }
+        }
+    }
+}
+
+/// An iterator mixing the items of two iterators, see
+/// [`MixExt::mix_with()`].
+#[derive(Clone, Debug)]
+pub struct MixWith<I, J, R> {
+    a: I,
+    b: J,
+    ratios: R,
+}
+
+impl<I, J, R> Iterator for MixWith<I, J, R>
+where
+    I: Iterator,
+    I::Item: Borrow<Pigment>,
+    J: Iterator,
+    J::Item: Borrow<Pigment>,
+    R: Iterator<Item = f32>,
+{
+    type Item = Pigment;
+
+    #[inline]
+    fn next(&mut self) -> Option<Pigment> {
+        let a = self.a.next()?;
+        let b = self.b.next()?;
+        let ratio = self.ratios.next()?;
+
+        Some(Pigment::from_mix(*a.borrow(), *b.borrow(), ratio))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        [self.b.size_hint(), self.ratios.size_hint()]
+            .into_iter()
+            .fold(
+                self.a.size_hint(),
+                |(lower, upper), (other_lower, other_upper)| {
+                    (
+                        lower.min(other_lower),
+                        match (upper, other_upper) {
+                            (Some(a), Some(b)) => Some(a.min(b)),
+                            (a, b) => a.or(b),
+                        },
+                    )
+                },
+            )
+    }
+}
+
+/// An iterator over `steps` evenly spaced mixes from one [`Pigment`] to
+/// another, both ends included.
+///
+/// The mixes are computed lazily from the latents of the two ends, so a
+/// gradient can be streamed, e.g. to an LED strip, without a buffer and
+/// without converting the ends again for every step.
+///
+/// ```
+/// # use pigment_mixing::{MixIter, Pigment};
+/// let yellow = Pigment::from_srgb_u8(254, 236, 0);
+/// let blue = Pigment::from_srgb_u8(25, 0, 89);
+///
+/// let mut ramp = MixIter::new(yellow, blue, 16);
+/// assert_eq!(16, ramp.len());
+/// assert_eq!(Some(blue), ramp.next_back());
+///
+/// for pigment in ramp {
+///     let srgb: [u8; 3] = pigment.into();
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct MixIter {
+    a: Pigment,
+    b: Pigment,
+    steps: usize,
+    remaining: Range<usize>,
+}
+
+impl MixIter {
+    /// Constructs an iterator over `steps` mixes from `a` to `b`.
+    ///
+    /// A single step yields `a`.
+    #[inline]
+    pub fn new(a: Pigment, b: Pigment, steps: usize) -> Self {
+        Self {
+            a,
+            b,
+            steps,
+            remaining: 0..steps,
+        }
+    }
+
+    /// Returns the mix of the `i`th step.
+    #[inline]
+    fn step(&self, i: usize) -> Pigment {
+        Pigment::from_mix(self.a, self.b, sample_position(i, self.steps))
+    }
+}
+
+impl Iterator for MixIter {
+    type Item = Pigment;
+
+    #[inline]
+    fn next(&mut self) -> Option<Pigment> {
+        self.remaining.next().map(|i| self.step(i))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Pigment> {
+        self.remaining.nth(n).map(|i| self.step(i))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.remaining.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for MixIter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Pigment> {
+        self.remaining.next_back().map(|i| self.step(i))
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Pigment> {
+        self.remaining.nth_back(n).map(|i| self.step(i))
+    }
+}
+
+impl ExactSizeIterator for MixIter {}
+
+impl FusedIterator for MixIter {}
@@ -0,0 +1,119 @@
+use crate::{clamp, gradient_into_slice, mix_srgb_u8};
+use alloc::{vec, vec::Vec};
+
+/// A precomputed table of mixes of two fixed `u8` component encoded sRGB
+/// (gamma 2.2) colors.
+///
+/// For mixing the same two colors over and over at varying ratios, e.g. text
+/// anti-aliased over a painted background or particles fading out. Looking
+/// a mix up is a single index into the table.
+///
+/// The table holds `levels` evenly spaced mixes, both colors included, of
+/// three bytes each. Ratios are rounded to the nearest level. With `256`
+/// levels, every `u8` coverage of [`mix_u8()`](MixTable::mix_u8) has its
+/// own entry and the table matches [`mix_srgb_u8()`] exactly. Fewer levels
+/// use less memory and band more, see [`max_error()`](MixTable::max_error).
+///
+/// ```
+/// # use pigment_mixing::MixTable;
+/// let text = MixTable::new(&[0, 0, 96], &[252, 211, 0], 256);
+///
+/// // A glyph's coverage mask over the yellow background.
+/// let coverage = [0u8, 64, 255, 128];
+/// let pixels = coverage.map(|coverage| text.mix_u8(255 - coverage));
+///
+/// assert_eq!(text.mix(0.0), pixels[2]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MixTable {
+    srgb_a: [u8; 3],
+    srgb_b: [u8; 3],
+    mixes: Vec<[u8; 3]>,
+}
+
+impl MixTable {
+    /// Builds a table of `levels` mixes from `srgb_a` to `srgb_b`.
+    ///
+    /// # Panics
+    ///
+    /// If `levels` is less than `2`.
+    pub fn new(srgb_a: &[u8; 3], srgb_b: &[u8; 3], levels: usize) -> Self {
+        assert!(1 < levels, "a mix table needs at least two levels");
+
+        let mut mixes = vec![[0; 3]; levels];
+        gradient_into_slice(srgb_a, srgb_b, &mut mixes);
+
+        Self {
+            srgb_a: *srgb_a,
+            srgb_b: *srgb_b,
+            mixes,
+        }
+    }
+
+    /// Returns the number of mixes in the table.
+    #[inline]
+    pub fn levels(&self) -> usize {
+        self.mixes.len()
+    }
+
+    /// Returns the size of the table in bytes.
+    #[inline]
+    pub fn size_in_bytes(&self) -> usize {
+        self.mixes.len() * core::mem::size_of::<[u8; 3]>()
+    }
+
+    /// Returns the mix at the level closest to `ratio`.
+    ///
+    /// `ratio` is clamped to `0.0..=1.0`.
+    #[inline]
+    pub fn mix(&self, ratio: f32) -> [u8; 3] {
+        let n = self.mixes.len();
+        let level = clamp(ratio, 0.0, 1.0) * (n - 1) as f32 + 0.5;
+
+        self.mixes[(level as usize).min(n - 1)]
+    }
+
+    /// Returns the mix at the level closest to `ratio / 255`.
+    #[inline]
+    pub fn mix_u8(&self, ratio: u8) -> [u8; 3] {
+        let n = self.mixes.len();
+        let max = u8::MAX as usize;
+
+        self.mixes[(ratio as usize * (n - 1) + max / 2) / max]
+    }
+
+    /// Returns the largest difference of a component between
+    /// [`mix_u8()`](MixTable::mix_u8) and [`mix_srgb_u8()`] over all `256`
+    /// ratios.
+    ///
+    /// The error depends on the colors as much as on the number of levels.
+    /// Pigment mixes change fastest near the ends, so the lookup can be off
+    /// by much more than `255 / levels` there. E.g. yellow and deep blue are
+    /// off by about `40` with `16` levels and about `16` with `64`.
+    ///
+    /// ```
+    /// # use pigment_mixing::MixTable;
+    /// let (a, b) = (&[0, 0, 96], &[252, 211, 0]);
+    ///
+    /// assert_eq!(0, MixTable::new(a, b, 256).max_error());
+    /// assert!(MixTable::new(a, b, 64).max_error() < 24);
+    /// ```
+    pub fn max_error(&self) -> u8 {
+        (0..=u8::MAX)
+            .map(|ratio| {
+                let exact = mix_srgb_u8(
+                    &self.srgb_a,
+                    &self.srgb_b,
+                    ratio as f32 / u8::MAX as f32,
+                );
+                let table = self.mix_u8(ratio);
+
+                (0..3)
+                    .map(|i| exact[i].abs_diff(table[i]))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
@@ -0,0 +1,404 @@
+//! The Mixbox latent model.
+//!
+//! A latent holds the concentrations of four primary pigments, followed by
+//! the RGB residual between the color and the mix of those pigments. Mixing
+//! latents linearly mixes the pigments.
+//!
+//! With the `pure-rust` feature this is an approximation written in Rust.
+//! Otherwise the C++ reference implementation is called through
+//! `mixbox-sys`.
+//!
+//! The Rust implementation maps RGB to concentrations with a table the build
+//! script fits. By default the table is embedded compressed, in a little over
+//! half its size, and decompressed on first use. The `uncompressed-lut`
+//! feature, which `fixed-point` implies, embeds it as is instead, so there is
+//! no work on first use. With
+//! `runtime-lut` a table can also be loaded at runtime with [`load_lut()`],
+//! replacing the embedded one.
+//!
+//! With the `deterministic` feature the Rust implementation runs on the
+//! fixed-point model in [`fixed`](crate::fixed), so latents are bit-identical
+//! on every platform.
+
+/// The number of values in a latent.
+pub const LATENT_LEN: usize = 7;
+
+#[cfg(not(feature = "pure-rust"))]
+mod imp {
+    use super::LATENT_LEN;
+    use mixbox_sys::{
+        mixbox_latent_to_srgb32f, mixbox_lerp_srgb32f,
+        mixbox_srgb32f_to_latent, MIXBOX_NUMLATENTS,
+    };
+
+    const _: () = assert!(LATENT_LEN == MIXBOX_NUMLATENTS as usize);
+
+    #[inline]
+    pub(crate) fn rgb_to_latent(rgb: [f32; 3]) -> [f32; LATENT_LEN] {
+        let mut latent = [0.0; LATENT_LEN];
+
+        unsafe {
+            mixbox_srgb32f_to_latent(
+                rgb[0],
+                rgb[1],
+                rgb[2],
+                latent.as_mut_ptr(),
+            )
+        };
+
+        latent
+    }
+
+    #[inline]
+    pub(crate) fn latent_to_rgb(latent: &[f32; LATENT_LEN]) -> [f32; 3] {
+        let mut rgb = [0.0; 3];
+        let [out_r, out_g, out_b] = &mut rgb;
+
+        unsafe {
+            mixbox_latent_to_srgb32f(latent.as_ptr() as _, out_r, out_g, out_b)
+        };
+
+        rgb
+    }
+
+    #[inline]
+    pub(crate) fn lerp_rgb(a: &[f32; 3], b: &[f32; 3], t: f32) -> [f32; 3] {
+        let mut rgb = [0.0; 3];
+        let [out_r, out_g, out_b] = &mut rgb;
+
+        unsafe {
+            mixbox_lerp_srgb32f(
+                a[0], a[1], a[2], b[0], b[1], b[2], t, out_r, out_g, out_b,
+            )
+        };
+
+        rgb
+    }
+}
+
+/// The quantized table, decompressed on first use.
+#[cfg(all(feature = "pure-rust", not(feature = "uncompressed-lut")))]
+static EMBEDDED_LUT: std::sync::OnceLock<alloc::vec::Vec<[u16; 3]>> =
+    std::sync::OnceLock::new();
+
+/// The quantized table embedded in the binary.
+#[cfg(all(feature = "pure-rust", not(feature = "uncompressed-lut")))]
+pub(crate) struct EmbeddedLut(&'static [[u16; 3]]);
+
+/// Returns the table embedded in the binary.
+#[cfg(all(feature = "pure-rust", not(feature = "uncompressed-lut")))]
+#[inline]
+fn embedded_lut() -> EmbeddedLut {
+    EmbeddedLut(EMBEDDED_LUT.get_or_init(|| {
+        crate::lut_codec::decode(include_bytes!(concat!(
+            env!("OUT_DIR"),
+            "/mixbox_lut.bin"
+        )))
+        .expect("the embedded table is valid")
+    }))
+}
+
+#[cfg(all(feature = "pure-rust", not(feature = "uncompressed-lut")))]
+impl EmbeddedLut {
+    /// Returns the quantized sample at `index`.
+    #[inline]
+    pub(crate) fn sample(&self, index: usize) -> [u16; 3] {
+        self.0[index]
+    }
+}
+
+/// The quantized table as little endian `u16`s.
+#[cfg(feature = "uncompressed-lut")]
+static EMBEDDED_LUT: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/mixbox_lut.raw"));
+
+/// The quantized table embedded in the binary, as little endian `u16`s.
+#[cfg(feature = "uncompressed-lut")]
+pub(crate) struct EmbeddedLut(&'static [u8]);
+
+/// Returns the table embedded in the binary.
+#[cfg(feature = "uncompressed-lut")]
+#[inline]
+fn embedded_lut() -> EmbeddedLut {
+    EmbeddedLut(EMBEDDED_LUT)
+}
+
+#[cfg(feature = "uncompressed-lut")]
+impl EmbeddedLut {
+    /// Returns the quantized sample at `index`.
+    #[inline]
+    pub(crate) fn sample(&self, index: usize) -> [u16; 3] {
+        let sample = &self.0[6 * index..6 * index + 6];
+        core::array::from_fn(|i| {
+            u16::from_le_bytes([sample[2 * i], sample[2 * i + 1]])
+        })
+    }
+}
+
+/// The quantized table.
+#[cfg(all(feature = "pure-rust", not(feature = "runtime-lut")))]
+pub(crate) type Lut = EmbeddedLut;
+
+/// Calls `f` with the table.
+#[cfg(all(feature = "pure-rust", not(feature = "runtime-lut")))]
+#[inline]
+pub(crate) fn with_lut<R>(f: impl FnOnce(&Lut) -> R) -> R {
+    f(&embedded_lut())
+}
+
+/// The table loaded with [`load_lut()`].
+#[cfg(feature = "runtime-lut")]
+static LOADED_LUT: std::sync::RwLock<
+    Option<alloc::sync::Arc<alloc::vec::Vec<[u16; 3]>>>,
+> = std::sync::RwLock::new(None);
+
+/// The number of tables loaded with [`load_lut()`].
+#[cfg(feature = "runtime-lut")]
+static LOADED_LUT_GENERATION: core::sync::atomic::AtomicUsize =
+    core::sync::atomic::AtomicUsize::new(0);
+
+#[cfg(feature = "runtime-lut")]
+std::thread_local! {
+    /// The table this thread last took from [`LOADED_LUT`] and the
+    /// [`LOADED_LUT_GENERATION`] it took it at.
+    static CACHED_LUT: core::cell::RefCell<(usize, Lut)> =
+        core::cell::RefCell::new((0, Lut::Embedded(embedded_lut())));
+}
+
+/// The quantized table, either the embedded one or one loaded with
+/// [`load_lut()`].
+#[cfg(feature = "runtime-lut")]
+pub(crate) enum Lut {
+    Embedded(EmbeddedLut),
+    Loaded(alloc::sync::Arc<alloc::vec::Vec<[u16; 3]>>),
+}
+
+/// Calls `f` with the table loaded with [`load_lut()`] or, if none was
+/// loaded, the embedded one.
+///
+/// Each thread keeps the table it used last and only takes the lock when
+/// another one was loaded since.
+#[cfg(feature = "runtime-lut")]
+#[inline]
+pub(crate) fn with_lut<R>(f: impl FnOnce(&Lut) -> R) -> R {
+    CACHED_LUT.with(|cached| {
+        let mut cached = cached.borrow_mut();
+        let generation =
+            LOADED_LUT_GENERATION.load(core::sync::atomic::Ordering::Acquire);
+        if generation != cached.0 {
+            let lut = LOADED_LUT
+                .read()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .clone()
+                .map_or_else(|| Lut::Embedded(embedded_lut()), Lut::Loaded);
+            *cached = (generation, lut);
+        }
+
+        f(&cached.1)
+    })
+}
+
+#[cfg(feature = "runtime-lut")]
+impl Lut {
+    /// Returns the quantized sample at `index`.
+    #[inline]
+    pub(crate) fn sample(&self, index: usize) -> [u16; 3] {
+        match self {
+            Lut::Embedded(lut) => lut.sample(index),
+            Lut::Loaded(lut) => lut[index],
+        }
+    }
+}
+
+/// Loads the table mapping RGB to pigment concentrations of the pure Rust
+/// implementation from `bytes`.
+///
+/// Until a table is loaded, the one embedded in the binary is used, so
+/// enabling `runtime-lut` never changes the behavior of code that does not
+/// call this. This matters as Cargo unifies features across all crates in a
+/// build.
+///
+/// The table is the `mixbox_lut.bin` the build script writes. Set the
+/// `PIGMENT_MIXING_LUT_DIR` environment variable at build time to have a
+/// copy written to that directory, then ship it with the application, e.g.
+/// to update the table without rebuilding.
+///
+/// This may be called again at any time to replace the table. Conversions
+/// running meanwhile finish with the previous one.
+///
+/// Returns [`Error::InvalidFormat`](crate::Error::InvalidFormat) if `bytes`
+/// is not a table for this version of the crate.
+///
+/// ```no_run
+/// # use pigment_mixing::{load_lut, mix_srgb_u8};
+/// load_lut(&std::fs::read("assets/mixbox_lut.bin").unwrap()).unwrap();
+///
+/// let green = mix_srgb_u8(&[252, 211, 0], &[0, 0, 96], 0.5);
+/// ```
+#[cfg(feature = "runtime-lut")]
+pub fn load_lut(bytes: &[u8]) -> Result<(), crate::Error> {
+    let lut = crate::lut_codec::decode(bytes)
+        .ok_or(crate::Error::InvalidFormat("not a Mixbox LUT"))?;
+    *LOADED_LUT
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) =
+        Some(alloc::sync::Arc::new(lut));
+    LOADED_LUT_GENERATION.fetch_add(1, core::sync::atomic::Ordering::Release);
+
+    Ok(())
+}
+
+/// Returns whether a table was loaded with [`load_lut()`].
+#[cfg(feature = "runtime-lut")]
+pub fn is_lut_loaded() -> bool {
+    LOADED_LUT
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .is_some()
+}
+
+#[cfg(all(feature = "pure-rust", not(feature = "deterministic")))]
+mod imp {
+    use super::{with_lut, LATENT_LEN};
+    use crate::{mixbox_lut::LUT_RESOLUTION, polynomial::eval_polynomial};
+
+    /// Converts a quantized concentration to `0.0..=1.0`.
+    #[inline]
+    fn dequantize(value: u16) -> f32 {
+        value as f32 / u16::MAX as f32
+    }
+
+    /// Returns the concentrations for `rgb`, trilinearly interpolated from
+    /// the table.
+    #[inline]
+    fn sample_lut(rgb: [f32; 3]) -> [f32; 4] {
+        let n = LUT_RESOLUTION;
+        let cell = |x: f32| {
+            let x = x.clamp(0.0, 1.0) * (n - 1) as f32;
+            let i = (x as usize).min(n - 2);
+            (i, x - i as f32)
+        };
+        let ((r, tr), (g, tg), (b, tb)) =
+            (cell(rgb[0]), cell(rgb[1]), cell(rgb[2]));
+
+        let mut c = [0.0f32; 3];
+        with_lut(|lut| {
+            for (dr, wr) in [(0, 1.0 - tr), (1, tr)] {
+                for (dg, wg) in [(0, 1.0 - tg), (1, tg)] {
+                    for (db, wb) in [(0, 1.0 - tb), (1, tb)] {
+                        let sample =
+                            lut.sample(((r + dr) * n + g + dg) * n + b + db);
+                        let weight = wr * wg * wb;
+                        c.iter_mut().zip(sample).for_each(|(c, sample)| {
+                            *c += weight * dequantize(sample)
+                        });
+                    }
+                }
+            }
+        });
+
+        [c[0], c[1], c[2], 1.0 - c[0] - c[1] - c[2]]
+    }
+
+    #[inline]
+    pub(crate) fn rgb_to_latent(rgb: [f32; 3]) -> [f32; LATENT_LEN] {
+        let c = sample_lut(rgb);
+        let mix = eval_polynomial(&c);
+
+        [
+            c[0],
+            c[1],
+            c[2],
+            c[3],
+            rgb[0] - mix[0],
+            rgb[1] - mix[1],
+            rgb[2] - mix[2],
+        ]
+    }
+
+    #[inline]
+    pub(crate) fn latent_to_rgb(latent: &[f32; LATENT_LEN]) -> [f32; 3] {
+        let mix =
+            eval_polynomial(&[latent[0], latent[1], latent[2], latent[3]]);
+
+        [mix[0] + latent[4], mix[1] + latent[5], mix[2] + latent[6]]
+    }
+
+    #[inline]
+    pub(crate) fn lerp_rgb(a: &[f32; 3], b: &[f32; 3], t: f32) -> [f32; 3] {
+        let (a, b) = (rgb_to_latent(*a), rgb_to_latent(*b));
+
+        let mut latent = [0.0; LATENT_LEN];
+        latent
+            .iter_mut()
+            .zip(a.iter().zip(&b))
+            .for_each(|(latent, (a, b))| *latent = a + (b - a) * t);
+
+        latent_to_rgb(&latent)
+    }
+}
+
+#[cfg(feature = "deterministic")]
+mod imp {
+    use super::LATENT_LEN;
+    use crate::fixed::{self, ONE};
+
+    /// Converts `x` to Q16.16, rounding to nearest.
+    #[inline]
+    fn to_fixed(x: f32) -> i32 {
+        (x * ONE as f32 + if x < 0.0 { -0.5 } else { 0.5 }) as i32
+    }
+
+    /// Converts `x` from Q16.16. This is exact for `x` below `2^24`.
+    #[inline]
+    fn from_fixed(x: i32) -> f32 {
+        x as f32 / ONE as f32
+    }
+
+    #[inline]
+    pub(crate) fn rgb_to_latent(rgb: [f32; 3]) -> [f32; LATENT_LEN] {
+        fixed::linear_srgb_to_latent(&rgb.map(to_fixed)).map(from_fixed)
+    }
+
+    #[inline]
+    pub(crate) fn latent_to_rgb(latent: &[f32; LATENT_LEN]) -> [f32; 3] {
+        fixed::latent_to_linear_srgb(&latent.map(to_fixed)).map(from_fixed)
+    }
+
+    #[inline]
+    pub(crate) fn lerp_rgb(a: &[f32; 3], b: &[f32; 3], t: f32) -> [f32; 3] {
+        fixed::latent_to_linear_srgb(&fixed::mix_latent(
+            &fixed::linear_srgb_to_latent(&a.map(to_fixed)),
+            &fixed::linear_srgb_to_latent(&b.map(to_fixed)),
+            to_fixed(t),
+        ))
+        .map(from_fixed)
+    }
+}
+
+/// Clamps an RGB color to `0.0..=1.0`. NaN becomes `0.0`.
+#[inline]
+#[allow(clippy::manual_clamp)] // `clamp()` keeps NaN.
+pub(crate) fn clamp_rgb(rgb: [f32; 3]) -> [f32; 3] {
+    rgb.map(|c| c.max(0.0).min(1.0))
+}
+
+/// Converts an RGB color to its latent.
+///
+/// The color is clamped to `0.0..=1.0` first, so all implementations agree
+/// on out of range input.
+#[inline]
+pub(crate) fn rgb_to_latent(rgb: [f32; 3]) -> [f32; LATENT_LEN] {
+    imp::rgb_to_latent(clamp_rgb(rgb))
+}
+
+/// Converts a latent to an RGB color.
+pub(crate) use imp::latent_to_rgb;
+
+/// Mixes two RGB colors using the ratio `t`.
+///
+/// The colors are clamped to `0.0..=1.0` first.
+#[inline]
+pub(crate) fn lerp_rgb(a: &[f32; 3], b: &[f32; 3], t: f32) -> [f32; 3] {
+    imp::lerp_rgb(&clamp_rgb(*a), &clamp_rgb(*b), t)
+}
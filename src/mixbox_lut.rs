@@ -0,0 +1,9 @@
+//! The table of the pure Rust Mixbox model.
+//!
+//! This module is shared with the build script, which fits the table mapping
+//! RGB to pigment concentrations. See [`lut_codec`](crate::lut_codec) for how
+//! the table is stored.
+
+/// The number of samples along each axis of the RGB cube in the table
+/// mapping RGB to pigment concentrations.
+pub(crate) const LUT_RESOLUTION: usize = 33;
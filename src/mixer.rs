@@ -0,0 +1,95 @@
+//! Interchangeable color mixing backends.
+use crate::{
+    metrics::{linear_srgb_to_oklab, oklab_to_linear_srgb},
+    MixingModel,
+};
+
+/// Mixes two colors.
+///
+/// Unlike [`MixingModel`], this trait is object safe. Use it to switch
+/// between mixing like paint, like light and perceptually uniform at
+/// runtime, e.g. for a blend mode setting:
+///
+/// ```
+/// # use pigment_mixing::{ColorMixer, LinearSrgbLerp, Mixbox, OklabLerp};
+/// let mixers: [&dyn ColorMixer; 3] = [&Mixbox, &LinearSrgbLerp, &OklabLerp];
+///
+/// for mixer in mixers {
+///     let mix = mixer.mix_u8(&[252, 211, 0], &[0, 0, 96], 0.5);
+/// }
+/// ```
+///
+/// Every [`MixingModel`] is a `ColorMixer`.
+pub trait ColorMixer {
+    /// Mixes two linear sRGB colors using `ratio`.
+    fn mix(&self, rgb_a: &[f32; 3], rgb_b: &[f32; 3], ratio: f32) -> [f32; 3];
+
+    /// Mixes two `u8` component encoded sRGB (gamma 2.2) colors using
+    /// `ratio`.
+    ///
+    /// This is named differently from
+    /// [`MixingModel::mix_srgb_u8()`] so calls on models are not ambiguous
+    /// with both traits in scope.
+    fn mix_u8(&self, srgb_a: &[u8; 3], srgb_b: &[u8; 3], ratio: f32)
+        -> [u8; 3];
+}
+
+impl<M: MixingModel> ColorMixer for M {
+    #[inline]
+    fn mix(&self, rgb_a: &[f32; 3], rgb_b: &[f32; 3], ratio: f32) -> [f32; 3] {
+        self.mix_linear_srgb(rgb_a, rgb_b, ratio)
+    }
+
+    #[inline]
+    fn mix_u8(
+        &self,
+        srgb_a: &[u8; 3],
+        srgb_b: &[u8; 3],
+        ratio: f32,
+    ) -> [u8; 3] {
+        MixingModel::mix_srgb_u8(self, srgb_a, srgb_b, ratio)
+    }
+}
+
+/// Mixes colors like light by linearly interpolating linear sRGB.
+///
+/// This is what GPUs and most compositing software do. Yellow and blue mix
+/// to gray.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LinearSrgbLerp;
+
+impl MixingModel for LinearSrgbLerp {
+    type Latent = [f32; 3];
+
+    #[inline]
+    fn linear_srgb_to_latent(&self, rgb: &[f32; 3]) -> [f32; 3] {
+        *rgb
+    }
+
+    #[inline]
+    fn latent_to_linear_srgb(&self, latent: &[f32; 3]) -> [f32; 3] {
+        *latent
+    }
+}
+
+/// Mixes colors perceptually uniformly by linearly interpolating
+/// [Oklab](https://bottosson.github.io/posts/oklab/).
+///
+/// Mixes keep their lightness and chroma better than [`LinearSrgbLerp`] but
+/// do not behave like paint either.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct OklabLerp;
+
+impl MixingModel for OklabLerp {
+    type Latent = [f32; 3];
+
+    #[inline]
+    fn linear_srgb_to_latent(&self, rgb: &[f32; 3]) -> [f32; 3] {
+        linear_srgb_to_oklab(*rgb)
+    }
+
+    #[inline]
+    fn latent_to_linear_srgb(&self, latent: &[f32; 3]) -> [f32; 3] {
+        oklab_to_linear_srgb(*latent)
+    }
+}
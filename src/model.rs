@@ -0,0 +1,154 @@
+//! Pluggable latent mixing models.
+use crate::{mixbox, sample_position, transfer};
+
+/// A model that mixes colors by mapping them to a latent space in which
+/// mixing is linear.
+///
+/// Implement this to mix with a different pigment basis, a table fitted to
+/// your own paints or a latent of a different length, and get the `mix_`
+/// methods of this trait and `ColorMixer` for free.
+///
+/// Everything else in this crate, e.g. `Pigment`, the free `mix_` functions
+/// and the gradients, palettes and buffers built on them, always uses
+/// [`Mixbox`] and can not be switched to another model.
+///
+/// Colors are linear sRGB.
+///
+/// ```
+/// # use pigment_mixing::MixingModel;
+/// /// Mixes the RGB of the colors, i.e. like light rather than paint.
+/// struct Additive;
+///
+/// impl MixingModel for Additive {
+///     type Latent = [f32; 3];
+///
+///     fn linear_srgb_to_latent(&self, rgb: &[f32; 3]) -> [f32; 3] {
+///         *rgb
+///     }
+///
+///     fn latent_to_linear_srgb(&self, latent: &[f32; 3]) -> [f32; 3] {
+///         *latent
+///     }
+/// }
+///
+/// let gray = Additive.mix_srgb_u8(&[255, 255, 255], &[0, 0, 0], 0.5);
+/// ```
+pub trait MixingModel {
+    /// The latent representation of a color.
+    ///
+    /// Mixing latents component-wise linearly mixes the colors.
+    type Latent: Copy + AsRef<[f32]> + AsMut<[f32]>;
+
+    /// Converts a linear sRGB color to its latent.
+    fn linear_srgb_to_latent(&self, rgb: &[f32; 3]) -> Self::Latent;
+
+    /// Converts a latent to a linear sRGB color.
+    fn latent_to_linear_srgb(&self, latent: &Self::Latent) -> [f32; 3];
+
+    /// Mixes two latents using `ratio`.
+    #[inline]
+    fn mix_latent(
+        &self,
+        latent_a: &Self::Latent,
+        latent_b: &Self::Latent,
+        ratio: f32,
+    ) -> Self::Latent {
+        let mut latent = *latent_a;
+        latent
+            .as_mut()
+            .iter_mut()
+            .zip(latent_b.as_ref())
+            .for_each(|(a, b)| *a += (b - *a) * ratio);
+
+        latent
+    }
+
+    /// Mixes two linear sRGB colors using `ratio`.
+    #[inline]
+    fn mix_linear_srgb(
+        &self,
+        rgb_a: &[f32; 3],
+        rgb_b: &[f32; 3],
+        ratio: f32,
+    ) -> [f32; 3] {
+        self.latent_to_linear_srgb(&self.mix_latent(
+            &self.linear_srgb_to_latent(rgb_a),
+            &self.linear_srgb_to_latent(rgb_b),
+            ratio,
+        ))
+    }
+
+    /// Mixes two `u8` component encoded sRGB (gamma 2.2) colors using
+    /// `ratio`.
+    ///
+    /// See [`mix_srgb_u8()`](crate::mix_srgb_u8).
+    #[inline]
+    fn mix_srgb_u8(
+        &self,
+        srgb_a: &[u8; 3],
+        srgb_b: &[u8; 3],
+        ratio: f32,
+    ) -> [u8; 3] {
+        transfer::encode_srgb(self.mix_linear_srgb(
+            &transfer::decode_srgb_u8(*srgb_a),
+            &transfer::decode_srgb_u8(*srgb_b),
+            ratio,
+        ))
+        .map(|c| (c * u8::MAX as f32 + 0.5) as _)
+    }
+
+    /// Writes evenly spaced mixes from `srgb_a` to `srgb_b`, both ends
+    /// included, to `out`.
+    ///
+    /// See [`gradient_into_slice()`](crate::gradient_into_slice).
+    fn gradient_into_slice(
+        &self,
+        srgb_a: &[u8; 3],
+        srgb_b: &[u8; 3],
+        out: &mut [[u8; 3]],
+    ) {
+        let a = self.linear_srgb_to_latent(&transfer::decode_srgb_u8(*srgb_a));
+        let b = self.linear_srgb_to_latent(&transfer::decode_srgb_u8(*srgb_b));
+
+        let n = out.len();
+        out.iter_mut().enumerate().for_each(|(i, srgb)| {
+            let ratio = sample_position(i, n);
+            *srgb = transfer::encode_srgb(
+                self.latent_to_linear_srgb(&self.mix_latent(&a, &b, ratio)),
+            )
+            .map(|c| (c * u8::MAX as f32 + 0.5) as _);
+        });
+    }
+}
+
+/// The [Mixbox](https://scrtwpns.com/mixbox) model.
+///
+/// This is the model behind all other functions of this crate. Its latent
+/// holds the concentrations of four primary pigments, followed by the RGB
+/// residual between the color and the mix of those pigments.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Mixbox;
+
+impl MixingModel for Mixbox {
+    type Latent = [f32; mixbox::LATENT_LEN];
+
+    #[inline]
+    fn linear_srgb_to_latent(&self, rgb: &[f32; 3]) -> Self::Latent {
+        mixbox::rgb_to_latent(*rgb)
+    }
+
+    #[inline]
+    fn latent_to_linear_srgb(&self, latent: &Self::Latent) -> [f32; 3] {
+        mixbox::latent_to_rgb(latent)
+    }
+
+    #[inline]
+    fn mix_linear_srgb(
+        &self,
+        rgb_a: &[f32; 3],
+        rgb_b: &[f32; 3],
+        ratio: f32,
+    ) -> [f32; 3] {
+        mixbox::lerp_rgb(rgb_a, rgb_b, ratio)
+    }
+}
@@ -0,0 +1,73 @@
+//! Conversions between `Pigment`s and [nalgebra](https://nalgebra.org/)
+//! vectors and matrices of latents.
+use crate::{pigment::PIGMENT_LEN, solve_recipe, Pigment, Recipe};
+use alloc::vec::Vec;
+use nalgebra::{Const, DVector, Dim, Dyn, Matrix, OMatrix, SVector, Storage};
+
+/// The latent representation of a [`Pigment`] as a column vector.
+///
+/// Mixing is linear in this space.
+pub type LatentVector = SVector<f32, PIGMENT_LEN>;
+
+/// The latent representations of several [`Pigment`]s, one per column.
+pub type LatentMatrix = OMatrix<f32, Const<PIGMENT_LEN>, Dyn>;
+
+/// Convert a [`LatentVector`] to a `Pigment`.
+impl From<LatentVector> for Pigment {
+    #[inline]
+    fn from(latent: LatentVector) -> Self {
+        Pigment::from(<[f32; PIGMENT_LEN]>::from(latent))
+    }
+}
+
+/// Convert a `Pigment` to a [`LatentVector`].
+impl From<Pigment> for LatentVector {
+    #[inline]
+    fn from(pigment: Pigment) -> Self {
+        LatentVector::from(<[f32; PIGMENT_LEN]>::from(pigment))
+    }
+}
+
+/// Returns the latents of `pigments` as the columns of a matrix.
+pub fn latent_matrix(pigments: &[Pigment]) -> LatentMatrix {
+    LatentMatrix::from_fn(pigments.len(), |row, column| {
+        <[f32; PIGMENT_LEN]>::from(pigments[column])[row]
+    })
+}
+
+/// Like [`solve_recipe()`] but takes the latents of the paints as the columns
+/// of a matrix, e.g. from [`latent_matrix()`].
+pub fn solve_recipe_matrix<C, S>(
+    target: &Pigment,
+    latents: &Matrix<f32, Const<PIGMENT_LEN>, C, S>,
+) -> Option<Recipe>
+where
+    C: Dim,
+    S: Storage<f32, Const<PIGMENT_LEN>, C>,
+{
+    let paints: Vec<Pigment> = latents
+        .column_iter()
+        .map(|column| {
+            let mut latent = [0.0; PIGMENT_LEN];
+            latent
+                .iter_mut()
+                .zip(column.iter())
+                .for_each(|(latent, value)| *latent = *value);
+
+            Pigment::from(latent)
+        })
+        .collect();
+
+    solve_recipe(target, &paints)
+}
+
+impl Recipe {
+    /// Returns the weights as a vector.
+    ///
+    /// Multiplying a [`LatentMatrix`] of the paints by it gives the latent of
+    /// the mix.
+    #[inline]
+    pub fn weight_vector(&self) -> DVector<f32> {
+        DVector::from_column_slice(&self.weights)
+    }
+}
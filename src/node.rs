@@ -0,0 +1,89 @@
+//! A Node.js API via [napi-rs](https://napi.rs/).
+//!
+//! Build with `napi build --release --features node`. napi-rs emits
+//! TypeScript definitions for everything exported here. Colors are passed as
+//! `Buffer`s of encoded sRGB (gamma 2.2) `[r, g, b]` or as `#rrggbb` hex
+//! strings. Images are `Buffer`s of tightly packed RGB pixels.
+use crate::{gradient_into_slice, mix_srgb_u8, parse_hex, to_hex};
+use alloc::{format, string::String, vec, vec::Vec};
+use napi::{bindgen_prelude::Buffer, Error, Result, Status};
+use napi_derive::napi;
+
+/// Returns the first three components of `srgb`.
+fn to_srgb_u8(srgb: &[u8]) -> Result<[u8; 3]> {
+    srgb.get(..3)
+        .and_then(|srgb| srgb.try_into().ok())
+        .ok_or_else(|| {
+            Error::new(Status::InvalidArg, "a color needs three components")
+        })
+}
+
+/// Returns the color parsed from `hex`.
+fn to_srgb_u8_from_hex(hex: &str) -> Result<[u8; 3]> {
+    parse_hex(hex).ok_or_else(|| {
+        Error::new(Status::InvalidArg, "a color must be #rrggbb or rrggbb")
+    })
+}
+
+/// Mixes two colors given as `[r, g, b]`.
+#[napi(js_name = "mixSrgbU8")]
+pub fn node_mix_srgb_u8(a: Buffer, b: Buffer, ratio: f64) -> Result<Buffer> {
+    Ok(
+        mix_srgb_u8(&to_srgb_u8(&a)?, &to_srgb_u8(&b)?, ratio as f32)
+            .to_vec()
+            .into(),
+    )
+}
+
+/// Mixes two colors given as `#rrggbb` or `rrggbb`.
+#[napi(js_name = "mixHex")]
+pub fn node_mix_hex(a: String, b: String, ratio: f64) -> Result<String> {
+    Ok(to_hex(mix_srgb_u8(
+        &to_srgb_u8_from_hex(&a)?,
+        &to_srgb_u8_from_hex(&b)?,
+        ratio as f32,
+    )))
+}
+
+/// Mixes two RGB images of the same size pixel by pixel.
+#[napi(js_name = "mixImages")]
+pub fn node_mix_images(a: Buffer, b: Buffer, ratio: f64) -> Result<Buffer> {
+    if a.len() != b.len() || !a.len().is_multiple_of(3) {
+        return Err(Error::new(
+            Status::InvalidArg,
+            "images must be RGB and have the same size",
+        ));
+    }
+
+    Ok(a.chunks_exact(3)
+        .zip(b.chunks_exact(3))
+        .flat_map(|(a, b)| {
+            mix_srgb_u8(&[a[0], a[1], a[2]], &[b[0], b[1], b[2]], ratio as f32)
+        })
+        .collect::<Vec<_>>()
+        .into())
+}
+
+/// Returns `n` evenly spaced mixes from `a` to `b`, both ends included, as
+/// `[r, g, b, r, g, b, …]`.
+#[napi(js_name = "gradientSrgbU8")]
+pub fn node_gradient_srgb_u8(a: Buffer, b: Buffer, n: u32) -> Result<Buffer> {
+    let mut gradient = vec![[0; 3]; n as _];
+    gradient_into_slice(&to_srgb_u8(&a)?, &to_srgb_u8(&b)?, &mut gradient);
+
+    Ok(gradient.into_iter().flatten().collect::<Vec<_>>().into())
+}
+
+/// Returns `n` evenly spaced mixes from `a` to `b`, both ends included, as
+/// `#rrggbb` strings.
+#[napi(js_name = "gradientHex")]
+pub fn node_gradient_hex(a: String, b: String, n: u32) -> Result<Vec<String>> {
+    let mut gradient = vec![[0; 3]; n as _];
+    gradient_into_slice(
+        &to_srgb_u8_from_hex(&a)?,
+        &to_srgb_u8_from_hex(&b)?,
+        &mut gradient,
+    );
+
+    Ok(gradient.into_iter().map(to_hex).collect())
+}
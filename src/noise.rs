@@ -0,0 +1,79 @@
+use num_traits::Float;
+
+/// Deterministic, seedable 2D value noise.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ValueNoise {
+    seed: u32,
+}
+
+impl ValueNoise {
+    #[inline]
+    pub(crate) fn new(seed: u64) -> Self {
+        Self {
+            seed: (seed ^ (seed >> 32)) as u32,
+        }
+    }
+
+    /// Returns a pseudo random value in `0.0..1.0` for a lattice point.
+    #[inline]
+    fn lattice(&self, x: i32, y: i32) -> f32 {
+        let mut hash = self.seed
+            ^ (x as u32).wrapping_mul(0x27d4_eb2d)
+            ^ (y as u32).wrapping_mul(0x1656_67b1);
+        hash ^= hash >> 15;
+        hash = hash.wrapping_mul(0x2c1b_3c6d);
+        hash ^= hash >> 12;
+        hash = hash.wrapping_mul(0x297a_2d39);
+        hash ^= hash >> 15;
+
+        (hash >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Returns smoothly interpolated noise in `0.0..1.0` with features about
+    /// one unit in size.
+    pub(crate) fn sample(&self, x: f32, y: f32) -> f32 {
+        let (x0, y0) = (Float::floor(x), Float::floor(y));
+        let (ix, iy) = (x0 as i32, y0 as i32);
+
+        let smooth = |t: f32| t * t * (3.0 - 2.0 * t);
+        let (tx, ty) = (smooth(x - x0), smooth(y - y0));
+
+        let top = self.lattice(ix, iy)
+            + (self.lattice(ix + 1, iy) - self.lattice(ix, iy)) * tx;
+        let bottom = self.lattice(ix, iy + 1)
+            + (self.lattice(ix + 1, iy + 1) - self.lattice(ix, iy + 1)) * tx;
+
+        top + (bottom - top) * ty
+    }
+
+    /// Returns fractal noise in `0.0..1.0` summed over `octaves` octaves.
+    pub(crate) fn fractal(&self, x: f32, y: f32, octaves: u32) -> f32 {
+        let (mut sum, mut amplitude, mut frequency, mut total) =
+            (0.0, 1.0, 1.0, 0.0);
+
+        for octave in 0..octaves.max(1) {
+            // Offset octaves so their lattices do not line up.
+            let offset = octave as f32 * 17.31;
+            sum += amplitude
+                * self.sample(x * frequency + offset, y * frequency - offset);
+            total += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+
+        sum / total
+    }
+
+    /// Returns three octave fractal noise remapped to be roughly uniformly
+    /// distributed in `0.0..1.0`.
+    ///
+    /// Fractal noise clusters around `0.5`. This applies a logistic
+    /// approximation of its cumulative distribution.
+    pub(crate) fn uniform(&self, x: f32, y: f32) -> f32 {
+        // Measured standard deviation of three octaves, scaled to the
+        // logistic distribution: 0.14 · √3 / π.
+        const SCALE: f32 = 0.077;
+
+        1.0 / (1.0 + Float::exp((0.5 - self.fractal(x, y, 3)) / SCALE))
+    }
+}
@@ -0,0 +1,99 @@
+use crate::Pigment;
+
+/// A [`Pigment`] together with the opacity, or covering power, of the paint.
+///
+/// An opacity of `1.0` is a fully opaque paint like a cadmium, small values
+/// are transparent paints like quinacridones or phthalos.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PigmentAlpha {
+    /// The color.
+    pub pigment: Pigment,
+    /// The opacity (covering power), from `0.0` to `1.0`.
+    pub opacity: f32,
+}
+
+impl PigmentAlpha {
+    /// Constructs a `PigmentAlpha`. The `opacity` is clamped to `0.0..=1.0`.
+    #[inline]
+    pub fn new(pigment: Pigment, opacity: f32) -> Self {
+        Self {
+            pigment,
+            opacity: opacity.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Mixes with another `PigmentAlpha` at the nominal `ratio`.
+    ///
+    /// The opacity of the result is the nominal mix of both opacities. See
+    /// [`mix_with_opacity()`] for the model used for the color.
+    pub fn mix(&self, other: &PigmentAlpha, ratio: f32) -> PigmentAlpha {
+        let ratio = ratio.clamp(0.0, 1.0);
+
+        PigmentAlpha {
+            pigment: mix_with_opacity(
+                &self.pigment,
+                self.opacity,
+                &other.pigment,
+                other.opacity,
+                ratio,
+            ),
+            opacity: self.opacity + (other.opacity - self.opacity) * ratio,
+        }
+    }
+}
+
+/// Converts to a fully opaque `PigmentAlpha`.
+impl From<Pigment> for PigmentAlpha {
+    #[inline]
+    fn from(pigment: Pigment) -> Self {
+        Self::new(pigment, 1.0)
+    }
+}
+
+/// Mixes `a` with `b` at the nominal `ratio`, taking the opacity (covering
+/// power) of both paints into account.
+///
+/// # Model
+///
+/// In the Kubelka–Munk model, the share a paint has in the color of a mixture
+/// grows with how much light it scatters. Opacity is used as a proxy for
+/// scattering: the nominal amounts of both paints are weighted by their
+/// opacities, giving the effective ratio
+///
+/// *r*′ = *r* · *o*<sub>b</sub> / ((1 − *r*) · *o*<sub>a</sub> + *r* ·
+/// *o*<sub>b</sub>)
+///
+/// which is then used for [`Pigment::from_mix()`]. So a teaspoon of an
+/// opaque white changes a transparent paint a lot while a teaspoon of a
+/// transparent paint barely tints an opaque one.
+///
+/// If both opacities are equal this is the same as
+/// [`Pigment::from_mix()`]. If both are zero, the nominal ratio is used.
+///
+/// ```
+/// # use pigment_mixing::{mix_with_opacity, Pigment};
+/// let cadmium_red = Pigment::from_srgb_u8(255, 39, 2);
+/// let phthalo_blue = Pigment::from_srgb_u8(13, 27, 68);
+///
+/// // The opaque red dominates the transparent blue.
+/// let mix = mix_with_opacity(&cadmium_red, 1.0, &phthalo_blue, 0.2, 0.5);
+/// ```
+pub fn mix_with_opacity(
+    a: &Pigment,
+    opacity_a: f32,
+    b: &Pigment,
+    opacity_b: f32,
+    ratio: f32,
+) -> Pigment {
+    let ratio = ratio.clamp(0.0, 1.0);
+    let weight_a = (1.0 - ratio) * opacity_a.clamp(0.0, 1.0);
+    let weight_b = ratio * opacity_b.clamp(0.0, 1.0);
+
+    let effective_ratio = if 0.0 < weight_a + weight_b {
+        weight_b / (weight_a + weight_b)
+    } else {
+        ratio
+    };
+
+    Pigment::from_mix(*a, *b, effective_ratio)
+}
@@ -0,0 +1,76 @@
+use crate::{Palette, PaletteEntry};
+
+impl Palette {
+    /// Returns a palette of common artist pigments.
+    ///
+    /// Each entry is named after the Colour Index name of its pigment
+    /// followed by its common name, e.g. `PB29 Ultramarine Blue`. Use
+    /// [`colour_index()`](Palette::colour_index) to look up entries by the
+    /// former.
+    ///
+    /// The values are typical masstones. Real paints vary by brand, binder and
+    /// batch. To add your own measured paints, load them and extend the
+    /// palette:
+    ///
+    /// ```
+    /// # use pigment_mixing::Palette;
+    /// let mut palette = Palette::artist_pigments();
+    ///
+    /// palette.extend(Palette::from_gpl(
+    ///     "GIMP Palette\n\
+    ///      226 190 110 PY42 My Gold Ochre\n",
+    /// )?);
+    ///
+    /// assert!(palette.colour_index("PY42").is_some());
+    /// # Ok::<(), pigment_mixing::Error>(())
+    /// ```
+    ///
+    /// With the `json` or `toml` features, [`Palette::from_json()`] and
+    /// [`Palette::from_toml()`] read palettes in the
+    /// [interchange format](crate#interchange-format) the same way.
+    pub fn artist_pigments() -> Self {
+        Self::from_gpl(include_str!("../data/artist_pigments.gpl"))
+            .expect("bundled palette is valid")
+    }
+
+    /// Returns a generic student grade acrylic paint range.
+    ///
+    /// Entries are named like those of
+    /// [`artist_pigments()`](Palette::artist_pigments). The data is a GIMP
+    /// palette and also available as `data/student_acrylics.gpl` in the
+    /// crate's source, e.g. for side-loading into other applications.
+    #[cfg(feature = "student-acrylics")]
+    pub fn student_acrylics() -> Self {
+        Self::from_gpl(include_str!("../data/student_acrylics.gpl"))
+            .expect("bundled palette is valid")
+    }
+
+    /// Returns a generic artist grade oil paint range.
+    ///
+    /// Entries are named like those of
+    /// [`artist_pigments()`](Palette::artist_pigments). The data is a GIMP
+    /// palette and also available as `data/artist_oils.gpl` in the crate's
+    /// source, e.g. for side-loading into other applications.
+    #[cfg(feature = "artist-oils")]
+    pub fn artist_oils() -> Self {
+        Self::from_gpl(include_str!("../data/artist_oils.gpl"))
+            .expect("bundled palette is valid")
+    }
+
+    /// Returns the first entry whose name starts with the Colour Index name
+    /// `colour_index`, e.g. `PY35`.
+    ///
+    /// The comparison ignores ASCII case and spaces, so `PY 35` and `py35`
+    /// find the same entry.
+    pub fn colour_index(&self, colour_index: &str) -> Option<&PaletteEntry> {
+        let wanted = colour_index.chars().filter(|c| !c.is_whitespace());
+
+        self.iter().find(|entry| {
+            entry.name.split_whitespace().next().is_some_and(|code| {
+                code.chars()
+                    .map(|c| c.to_ascii_uppercase())
+                    .eq(wanted.clone().map(|c| c.to_ascii_uppercase()))
+            })
+        })
+    }
+}
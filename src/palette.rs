@@ -0,0 +1,230 @@
+use crate::{metrics::delta_e_ok, Pigment};
+use alloc::{string::String, vec::Vec};
+
+/// A named color of a [`Palette`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaletteEntry {
+    /// The name of the color, e.g. `Ultramarine`. May be empty.
+    pub name: String,
+    /// The color.
+    pub pigment: Pigment,
+}
+
+impl PaletteEntry {
+    /// Constructs a `PaletteEntry`.
+    #[inline]
+    pub fn new(name: impl Into<String>, pigment: Pigment) -> Self {
+        Self {
+            name: name.into(),
+            pigment,
+        }
+    }
+}
+
+/// An ordered collection of named pigments.
+///
+/// ```
+/// # use pigment_mixing::{Palette, Pigment};
+/// let palette = Palette::new()
+///     .with_entry("Cadmium Yellow", Pigment::from_srgb_u8(254, 236, 0))
+///     .with_entry("Ultramarine", Pigment::from_srgb_u8(25, 0, 89));
+///
+/// for entry in &palette {
+///     let srgb: [u8; 3] = entry.pigment.into();
+///     println!("{}: {:?}", entry.name, srgb);
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Palette {
+    entries: Vec<PaletteEntry>,
+}
+
+impl Palette {
+    /// Constructs an empty `Palette`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a named pigment and returns the palette.
+    #[inline]
+    pub fn with_entry(
+        mut self,
+        name: impl Into<String>,
+        pigment: Pigment,
+    ) -> Self {
+        self.push(name, pigment);
+        self
+    }
+
+    /// Appends a named pigment.
+    #[inline]
+    pub fn push(&mut self, name: impl Into<String>, pigment: Pigment) {
+        self.entries.push(PaletteEntry::new(name, pigment));
+    }
+
+    /// Returns the entries of the palette.
+    #[inline]
+    pub fn entries(&self) -> &[PaletteEntry] {
+        &self.entries
+    }
+
+    /// Returns an iterator over the entries of the palette.
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, PaletteEntry> {
+        self.entries.iter()
+    }
+
+    /// Returns the first entry called `name`.
+    pub fn get(&self, name: &str) -> Option<&PaletteEntry> {
+        self.entries.iter().find(|entry| entry.name == name)
+    }
+
+    /// Returns the entry perceptually closest to `pigment`.
+    ///
+    /// Distance is measured as Euclidean distance in
+    /// [Oklab](https://bottosson.github.io/posts/oklab/).
+    ///
+    /// Returns `None` if the palette is empty.
+    pub fn nearest(&self, pigment: &Pigment) -> Option<&PaletteEntry> {
+        nearest_paint(pigment, self).map(|(entry, _)| entry)
+    }
+
+    /// Returns `true` if the palette has no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<'a> IntoIterator for &'a Palette {
+    type IntoIter = core::slice::Iter<'a, PaletteEntry>;
+    type Item = &'a PaletteEntry;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for Palette {
+    type IntoIter = alloc::vec::IntoIter<PaletteEntry>;
+    type Item = PaletteEntry;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl FromIterator<PaletteEntry> for Palette {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = PaletteEntry>,
+    {
+        Self {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<PaletteEntry> for Palette {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = PaletteEntry>,
+    {
+        self.entries.extend(iter);
+    }
+}
+
+/// Returns the entry of `palette` perceptually closest to `pigment` and its
+/// distance.
+///
+/// The distance is the Euclidean distance in
+/// [Oklab](https://bottosson.github.io/posts/oklab/), scaled by `100`.
+///
+/// Returns `None` if the palette is empty.
+///
+/// ```
+/// # use pigment_mixing::{nearest_paint, Palette, Pigment};
+/// let palette = Palette::new()
+///     .with_entry("Cadmium Yellow", Pigment::from_srgb_u8(254, 236, 0))
+///     .with_entry("Ultramarine", Pigment::from_srgb_u8(25, 0, 89));
+///
+/// let (paint, _) =
+///     nearest_paint(&Pigment::from_srgb_u8(240, 220, 20), &palette).unwrap();
+///
+/// assert_eq!("Cadmium Yellow", paint.name);
+/// ```
+pub fn nearest_paint<'a>(
+    pigment: &Pigment,
+    palette: &'a Palette,
+) -> Option<(&'a PaletteEntry, f32)> {
+    palette
+        .iter()
+        .map(|entry| (entry, delta_e_ok(&entry.pigment, pigment)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Returns up to `k` entries of `palette` perceptually closest to `pigment`
+/// with their distances, closest first.
+///
+/// See [`nearest_paint()`].
+pub fn nearest_paints<'a>(
+    pigment: &Pigment,
+    palette: &'a Palette,
+    k: usize,
+) -> Vec<(&'a PaletteEntry, f32)> {
+    let mut nearest: Vec<_> = palette
+        .iter()
+        .map(|entry| (entry, delta_e_ok(&entry.pigment, pigment)))
+        .collect();
+
+    nearest.sort_by(|a, b| a.1.total_cmp(&b.1));
+    nearest.truncate(k);
+
+    nearest
+}
+
+/// Converts a CIELAB color with a D50 white point, as used by Adobe's
+/// formats, to a `Pigment`.
+pub(crate) fn pigment_from_lab_d50(l: f32, a: f32, b: f32) -> Pigment {
+    // CIELAB -> XYZ (D50).
+    let fy = (l + 16.0) / 116.0;
+    let fx = fy + a / 500.0;
+    let fz = fy - b / 200.0;
+
+    let finv = |t: f32| {
+        if t > 6.0 / 29.0 {
+            t * t * t
+        } else {
+            0.128_418_55 * (t - 4.0 / 29.0)
+        }
+    };
+
+    let x = 0.964_22 * finv(fx);
+    let y = finv(fy);
+    let z = 0.825_21 * finv(fz);
+
+    // XYZ (D50) -> linear sRGB, Bradford adapted.
+    Pigment::from_linear_srgb(
+        3.133_856 * x - 1.616_867 * y - 0.490_615 * z,
+        -0.978_768 * x + 1.916_142 * y + 0.033_454 * z,
+        0.071_945 * x - 0.228_991 * y + 1.405_243 * z,
+    )
+}
+
+/// Converts a naive (uncalibrated) CMYK color to a `Pigment`.
+pub(crate) fn pigment_from_cmyk(c: f32, m: f32, y: f32, k: f32) -> Pigment {
+    Pigment::from_srgb(
+        (1.0 - c) * (1.0 - k),
+        (1.0 - m) * (1.0 - k),
+        (1.0 - y) * (1.0 - k),
+    )
+}
@@ -1,25 +1,48 @@
-use crate::clamp;
+use crate::{clamp, mixbox, transfer, Error};
 use arrayvec::ArrayVec;
-use colstodian::{kolor::Vec3, Color, EncodedSrgb, LinearSrgb, Scene};
+#[cfg(feature = "colstodian")]
+use colstodian::{kolor::Vec3, Color, LinearSrgb, Scene};
 use core::{
-    mem::MaybeUninit,
+    array,
     ops::{Add, Mul},
 };
-use mixbox_sys::{
-    mixbox_latent_to_srgb32f, mixbox_srgb32f_to_latent, MIXBOX_NUMLATENTS,
-};
 use num_traits::{
     cast::AsPrimitive,
     float::Float,
     identities::{one, zero},
 };
 
-const PIGMENT_LEN: usize = MIXBOX_NUMLATENTS as _;
+pub(crate) const PIGMENT_LEN: usize = mixbox::LATENT_LEN;
+
+/// The number of pigment concentrations at the start of a latent. The rest
+/// is the RGB residual.
+const CONCENTRATIONS_LEN: usize = 4;
+
+/// How far latent components may stray outside the range of the model
+/// before [`Pigment::validate()`] rejects them.
+const LATENT_TOLERANCE: f32 = 0.1;
 
 /// A color represented as pigment mixture.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Pigment([f32; PIGMENT_LEN]);
 
 impl Pigment {
+    /// Constructs a `Pigment` from its latent representation.
+    ///
+    /// This is a `const fn`, so `Pigment` constants can be defined from
+    /// latents baked by a build script, e.g. with `write_pigment_consts()`
+    /// from the `codegen` feature.
+    ///
+    /// ```
+    /// # use pigment_mixing::Pigment;
+    /// const WHITE: Pigment =
+    ///     Pigment::from_latent([0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0]);
+    /// ```
+    #[inline]
+    pub const fn from_latent(latent: [f32; PIGMENT_LEN]) -> Self {
+        Self(latent)
+    }
+
     /// Constructs a `Pigment` from a [`u8`] component encoded sRGB (gamma 2.2)
     /// color.
     #[inline]
@@ -27,71 +50,46 @@ impl Pigment {
         Self::from((r, g, b))
     }
 
+    /// Constructs a `Pigment` from an [`f32`] component encoded sRGB (gamma
+    /// 2.2) color.
+    #[inline]
+    pub fn from_srgb(r: f32, g: f32, b: f32) -> Self {
+        Self::from(transfer::decode_srgb([r, g, b]))
+    }
+
     /// Constructs a `Pigment` from a [`u8`] component encoded sRGB (gamma 2.2)
     /// color.
     #[inline]
     pub fn from_srgb_u8(r: u8, g: u8, b: u8) -> Self {
-        let srgb_linear = Color::<EncodedSrgb, Scene>::new(
-            r as f32 / u8::MAX as f32,
-            g as f32 / u8::MAX as f32,
-            b as f32 / u8::MAX as f32,
-        )
-        .linearize();
-
-        let mut pigment = MaybeUninit::<[f32; PIGMENT_LEN]>::uninit();
-
-        unsafe {
-            mixbox_srgb32f_to_latent(
-                srgb_linear.raw[0],
-                srgb_linear.raw[1],
-                srgb_linear.raw[2],
-                pigment.as_mut_ptr() as _,
-            );
-
-            Self(pigment.assume_init())
-        }
+        Self(mixbox::rgb_to_latent(transfer::decode_srgb_u8([r, g, b])))
     }
 
     /// Constructs a `Pigment` from a [`u16`] component linear sRGB color.
     #[inline]
     pub fn from_linear_srgb_u16(r: u16, g: u16, b: u16) -> Self {
-        let mut pigment = MaybeUninit::<[f32; PIGMENT_LEN]>::uninit();
-
-        unsafe {
-            mixbox_srgb32f_to_latent(
-                r as f32 / u8::MAX as f32,
-                g as f32 / u8::MAX as f32,
-                b as f32 / u8::MAX as f32,
-                pigment.as_mut_ptr() as _,
-            );
-
-            Self(pigment.assume_init())
-        }
+        Self(mixbox::rgb_to_latent([
+            r as f32 / u8::MAX as f32,
+            g as f32 / u8::MAX as f32,
+            b as f32 / u8::MAX as f32,
+        ]))
     }
 
     /// Constructs a `Pigment` from a [`u16`] component encoded sRGB (gamma
     /// 2.2) color.
     #[inline]
     pub fn from_srgb_u16(r: u16, g: u16, b: u16) -> Self {
-        let srgb_linear = Color::<EncodedSrgb, Scene>::new(
-            r as f32 / u16::MAX as f32,
-            g as f32 / u16::MAX as f32,
-            b as f32 / u16::MAX as f32,
-        )
-        .linearize();
-
-        let mut pigment = MaybeUninit::<[f32; PIGMENT_LEN]>::uninit();
-
-        unsafe {
-            mixbox_srgb32f_to_latent(
-                srgb_linear.raw[0],
-                srgb_linear.raw[1],
-                srgb_linear.raw[2],
-                pigment.as_mut_ptr() as _,
-            );
-
-            Self(pigment.assume_init())
-        }
+        Self(mixbox::rgb_to_latent(transfer::decode_srgb_u16([r, g, b])))
+    }
+
+    /// Constructs a `Pigment` from a measured reflectance spectrum.
+    ///
+    /// The spectrum is integrated under D65 with the CIE 1931 2° standard
+    /// observer. Use [`resample_spectrum()`](crate::resample_spectrum) for
+    /// measurements with a different sampling.
+    #[cfg(feature = "spectral")]
+    #[inline]
+    pub fn from_reflectance(reflectance: &crate::Spectrum) -> Self {
+        Self::from(crate::reflectance_to_linear_srgb(reflectance))
     }
 
     /// Constructs a `Pigment` from the mixing of two other `Pigment`s using
@@ -103,16 +101,189 @@ impl Pigment {
         f32: Mul<T, Output = f32>,
     {
         let ratio = clamp(ratio, zero(), one());
-        let result: ArrayVec<_, PIGMENT_LEN> =
-            a.0.iter()
-                .zip(b.0.iter())
-                .map(|(&a, &b)| a * (one::<T>() - ratio) + b * ratio)
-                .collect();
 
-        unsafe { Self(result.into_inner_unchecked()) }
+        Self(array::from_fn(|i| {
+            a.0[i] * (one::<T>() - ratio) + b.0[i] * ratio
+        }))
+    }
+
+    /// Constructs a `Pigment` from mixing absolute amounts of two other
+    /// `Pigment`s.
+    ///
+    /// Unlike [`from_mix()`](Pigment::from_mix), which takes a normalized
+    /// ratio, this mixes by quantity: adding a small dab to a large puddle
+    /// shifts the result only slightly.
+    ///
+    /// Negative amounts are treated as zero. If both amounts are zero, `a` is
+    /// returned.
+    ///
+    /// ```
+    /// # use pigment_mixing::Pigment;
+    /// let yellow = Pigment::from_srgb_u8(254, 236, 0);
+    /// let blue = Pigment::from_srgb_u8(25, 0, 89);
+    ///
+    /// assert_eq!(
+    ///     Pigment::from_mix(yellow, blue, 0.25),
+    ///     Pigment::mix_amounts(yellow, 3.0, blue, 1.0)
+    /// );
+    /// ```
+    #[inline]
+    pub fn mix_amounts(
+        a: Pigment,
+        amount_a: f32,
+        b: Pigment,
+        amount_b: f32,
+    ) -> Self {
+        let (amount_a, amount_b) = (amount_a.max(0.0), amount_b.max(0.0));
+        let total = amount_a + amount_b;
+
+        if 0.0 < total {
+            Self::from_mix(a, b, amount_b / total)
+        } else {
+            a
+        }
+    }
+
+    /// Removes the share `amount` of `removed` from the mixture, the inverse
+    /// of [`from_mix()`](Pigment::from_mix).
+    ///
+    /// `amount` is the ratio `removed` was mixed in with and is clamped to
+    /// `0.0..=1.0`. The remaining pigment is rescaled to make up the whole
+    /// mixture again, i.e. `Pigment::from_mix(a, b, t).remove(&b, t)` gives
+    /// back `a`.
+    ///
+    /// Returns [`Error::InvalidLatent`] if the result fails
+    /// [`validate()`](Pigment::validate), e.g. when more of `removed` is
+    /// taken out than the mixture holds or `amount` is `1`. Otherwise, the
+    /// rounding error left is clamped away.
+    ///
+    /// ```
+    /// # use pigment_mixing::Pigment;
+    /// let yellow = Pigment::from_srgb_u8(254, 236, 0);
+    /// let blue = Pigment::from_srgb_u8(25, 0, 89);
+    ///
+    /// let green = Pigment::from_mix(yellow, blue, 0.3);
+    /// let [r, g, b]: [u8; 3] = green.remove(&blue, 0.3).unwrap().into();
+    /// assert!(r.abs_diff(254) <= 1 && g.abs_diff(236) <= 1 && b <= 1);
+    ///
+    /// // There is not that much blue in the mix.
+    /// assert!(green.remove(&blue, 0.9).is_err());
+    /// ```
+    pub fn remove(
+        &self,
+        removed: &Pigment,
+        amount: f32,
+    ) -> Result<Self, Error> {
+        let amount = clamp(amount, 0.0, 1.0);
+        let remaining = Self(array::from_fn(|i| {
+            (self.0[i] - removed.0[i] * amount) / (1.0 - amount)
+        }));
+
+        remaining.validate()?;
+
+        Ok(remaining.clamped())
+    }
+
+    /// Clamps the latent to the range of the model and renormalizes the
+    /// concentrations.
+    fn clamped(mut self) -> Self {
+        let (concentrations, residual) =
+            self.0.split_at_mut(CONCENTRATIONS_LEN);
+        concentrations
+            .iter_mut()
+            .for_each(|c| *c = clamp(*c, 0.0, 1.0));
+        residual.iter_mut().for_each(|c| *c = clamp(*c, -1.0, 1.0));
+
+        let sum = concentrations.iter().sum::<f32>();
+        if 0.0 < sum {
+            concentrations.iter_mut().for_each(|c| *c /= sum);
+        }
+
+        self
+    }
+
+    /// Returns `true` if [`validate()`](Pigment::validate) finds nothing
+    /// wrong with the latent.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    /// Checks that the latent is one the model can convert to a sensible
+    /// color.
+    ///
+    /// Mixing valid `Pigment`s with [`from_mix()`](Pigment::from_mix) always
+    /// gives a valid one. Accumulating weighted dabs with `+` and `*` whose
+    /// weights do not sum to `1`, or latents from elsewhere, may not. The
+    /// color of such a latent is garbage.
+    ///
+    /// Returns [`Error::InvalidLatent`] naming the first bad component if
+    /// one is not finite, a pigment concentration is outside `0.0..=1.0`, a
+    /// residual is outside `-1.0..=1.0`, or the concentrations do not sum to
+    /// `1`. A small tolerance is allowed for rounding.
+    ///
+    /// ```
+    /// # use pigment_mixing::{Error, Pigment};
+    /// let yellow = Pigment::from_srgb_u8(254, 236, 0);
+    /// let blue = Pigment::from_srgb_u8(25, 0, 89);
+    /// assert!((0.7 * yellow + 0.3 * blue).is_valid());
+    ///
+    /// // Weights summing to 1.2.
+    /// assert!(matches!(
+    ///     (0.6 * yellow + 0.6 * blue).validate(),
+    ///     Err(Error::InvalidLatent { component: None, .. })
+    /// ));
+    ///
+    /// let mut latent: [f32; 7] = yellow.into();
+    /// latent[5] = f32::NAN;
+    /// assert!(matches!(
+    ///     Pigment::from_latent(latent).validate(),
+    ///     Err(Error::InvalidLatent { component: Some(5), .. })
+    /// ));
+    /// ```
+    pub fn validate(&self) -> Result<(), Error> {
+        let invalid =
+            |component, reason| Err(Error::InvalidLatent { component, reason });
+
+        for (i, &c) in self.0.iter().enumerate() {
+            let (min, max) = if i < CONCENTRATIONS_LEN {
+                (0.0, 1.0)
+            } else {
+                (-1.0, 1.0)
+            };
+
+            if !c.is_finite() {
+                return invalid(Some(i), "not finite");
+            }
+            if c < min - LATENT_TOLERANCE || max + LATENT_TOLERANCE < c {
+                return invalid(
+                    Some(i),
+                    if i < CONCENTRATIONS_LEN {
+                        "concentration outside 0.0..=1.0"
+                    } else {
+                        "residual outside -1.0..=1.0"
+                    },
+                );
+            }
+        }
+
+        let sum = self.0[..CONCENTRATIONS_LEN].iter().sum::<f32>();
+        if LATENT_TOLERANCE < (sum - 1.0).abs() {
+            return invalid(None, "concentrations do not sum to 1");
+        }
+
+        Ok(())
+    }
+
+    /// Converts the `Pigment` to an [`f32`] component encoded sRGB (gamma
+    /// 2.2) color.
+    #[inline]
+    pub fn to_srgb(self) -> [f32; 3] {
+        transfer::encode_srgb(self.into())
     }
 
     /// Mixes with another `Pigment` using the given `ratio`.
+    #[inline]
     pub fn mix<T>(&mut self, b: Pigment, ratio: T)
     where
         T: Float,
@@ -121,7 +292,7 @@ impl Pigment {
         let ratio = clamp(ratio, zero(), one());
         self.0
             .iter_mut()
-            .zip(b.0.into_iter())
+            .zip(b.0)
             .for_each(|(a, b)| *a = *a * (one::<T>() - ratio) + b * ratio);
     }
 }
@@ -129,16 +300,16 @@ impl Pigment {
 impl Mul<f32> for Pigment {
     type Output = Pigment;
 
+    #[inline]
     fn mul(self, rhs: f32) -> Self {
-        let result: ArrayVec<_, PIGMENT_LEN> =
-            self.0.iter().map(|a| a * rhs).collect();
-        unsafe { Self(result.into_inner_unchecked()) }
+        Self(self.0.map(|a| a * rhs))
     }
 }
 
 impl Mul<Pigment> for f32 {
     type Output = Pigment;
 
+    #[inline]
     fn mul(self, rhs: Pigment) -> Pigment {
         rhs * self
     }
@@ -147,14 +318,9 @@ impl Mul<Pigment> for f32 {
 impl Add for Pigment {
     type Output = Self;
 
+    #[inline]
     fn add(self, rhs: Self) -> Self {
-        let result: ArrayVec<_, PIGMENT_LEN> = self
-            .0
-            .iter()
-            .zip(rhs.0.iter())
-            .map(|(a, b)| a + b)
-            .collect();
-        unsafe { Self(result.into_inner_unchecked()) }
+        Self(array::from_fn(|i| self.0[i] + rhs.0[i]))
     }
 }
 
@@ -191,6 +357,7 @@ impl From<Pigment> for [f32; PIGMENT_LEN] {
 }
 
 /// Convert a linear sRGB [`Color`] to a `Pigment`.
+#[cfg(feature = "colstodian")]
 impl From<Color<LinearSrgb, Scene>> for Pigment {
     #[inline]
     fn from(srgb: Color<LinearSrgb, Scene>) -> Self {
@@ -210,37 +377,17 @@ impl From<[f32; 3]> for Pigment {
 impl From<(f32, f32, f32)> for Pigment {
     #[inline]
     fn from(srgb: (f32, f32, f32)) -> Self {
-        let mut pigment = MaybeUninit::<[f32; PIGMENT_LEN]>::uninit();
-
-        unsafe {
-            mixbox_srgb32f_to_latent(
-                srgb.0,
-                srgb.1,
-                srgb.2,
-                pigment.as_mut_ptr() as _,
-            );
-
-            Self(pigment.assume_init())
-        }
+        Self(mixbox::rgb_to_latent([srgb.0, srgb.1, srgb.2]))
     }
 }
 
 /// Convert a `Pigment` to a linear sRGB [`Color`].
+#[cfg(feature = "colstodian")]
 impl From<Pigment> for Color<LinearSrgb, Scene> {
     #[inline]
     fn from(pigment: Pigment) -> Self {
-        let mut color = MaybeUninit::<Vec3>::uninit();
-        let color_ptr = color.as_mut_ptr().cast::<f32>();
-
-        Color::from_raw(unsafe {
-            mixbox_latent_to_srgb32f(
-                &pigment.0 as *const _ as _,
-                color_ptr as _,
-                color_ptr.offset(1) as _,
-                color_ptr.offset(2) as _,
-            );
-            color.assume_init()
-        })
+        let [r, g, b] = mixbox::latent_to_rgb(&pigment.0);
+        Color::from_raw(Vec3::new(r, g, b))
     }
 }
 
@@ -248,18 +395,21 @@ impl From<Pigment> for Color<LinearSrgb, Scene> {
 impl From<Pigment> for [f32; 3] {
     #[inline]
     fn from(pigment: Pigment) -> Self {
-        let mut srgb = MaybeUninit::<[f32; 3]>::uninit();
+        mixbox::latent_to_rgb(&pigment.0)
+    }
+}
 
-        unsafe {
-            mixbox_latent_to_srgb32f(
-                &pigment.0 as *const _ as _,
-                srgb.as_mut_ptr() as _,
-                srgb.as_mut_ptr().offset(1) as _,
-                srgb.as_mut_ptr().offset(2) as _,
-            );
+/// Convert a `Pigment` to an [`u8`] component encoded sRGB (gamma 2.2) slice.
+impl From<Pigment> for [u8; 3] {
+    #[inline]
+    fn from(pigment: Pigment) -> Self {
+        let srgb = pigment.to_srgb();
 
-            srgb.assume_init()
-        }
+        [
+            (clamp(srgb[0], 0.0, 1.0) * u8::MAX as f32 + 0.5) as _,
+            (clamp(srgb[1], 0.0, 1.0) * u8::MAX as f32 + 0.5) as _,
+            (clamp(srgb[2], 0.0, 1.0) * u8::MAX as f32 + 0.5) as _,
+        ]
     }
 }
 
@@ -267,19 +417,7 @@ impl From<Pigment> for [f32; 3] {
 impl From<Pigment> for (f32, f32, f32) {
     #[inline]
     fn from(pigment: Pigment) -> Self {
-        let srgb = MaybeUninit::<(f32, f32, f32)>::uninit();
-
-        unsafe {
-            let mut srgb = srgb.assume_init();
-
-            mixbox_latent_to_srgb32f(
-                &pigment.0 as *const _ as _,
-                &mut srgb.0 as _,
-                &mut srgb.1 as _,
-                &mut srgb.2 as _,
-            );
-
-            srgb
-        }
+        let [r, g, b] = mixbox::latent_to_rgb(&pigment.0);
+        (r, g, b)
     }
 }
@@ -0,0 +1,47 @@
+//! The polynomial of the pure Rust Mixbox model.
+//!
+//! This module is shared with the build script, which fits the table mapping
+//! RGB to pigment concentrations against it and, for the `fixed-point`
+//! feature, converts it to Q16.16.
+
+/// The RGB of a mix of the four primaries as a cubic polynomial in their
+/// concentrations. Each term is the product of the concentrations at the
+/// three indices, times the RGB coefficients.
+#[allow(clippy::excessive_precision)]
+pub(crate) const POLYNOMIAL: [([usize; 3], [f32; 3]); 20] = [
+    ([0, 0, 0], [0.077_170_53, 0.028_269_78, 0.248_329_92]),
+    ([1, 1, 1], [0.959_123_02, 0.802_565_28, 0.035_618_39]),
+    ([2, 2, 2], [0.746_837_74, 0.048_685_86, 0.0]),
+    ([3, 3, 3], [0.995_181_38, 0.999_781_49, 0.997_048_02]),
+    ([0, 0, 1], [0.048_191_46, 0.833_637_81, 0.325_153_77]),
+    ([0, 1, 1], [-0.681_469_5, 1.461_078_03, 1.069_809_36]),
+    ([0, 0, 2], [0.270_584_19, -0.153_248_7, 1.987_350_57]),
+    ([0, 2, 2], [0.804_781_89, 0.670_937_1, 0.184_245]),
+    ([0, 0, 3], [-0.350_310_03, 1.378_558_26, 3.688_65]),
+    ([0, 3, 3], [1.051_280_46, 1.978_152_39, 2.829_890_73]),
+    ([1, 1, 2], [3.216_071_25, 0.812_702_28, 1.033_845_39]),
+    ([1, 2, 2], [2.788_933_74, 0.415_655_49, -0.044_872_95]),
+    ([1, 1, 3], [3.021_625_77, 2.553_741_03, 0.327_661_14]),
+    ([1, 3, 3], [2.951_246_91, 2.812_011_12, 1.175_784_42]),
+    ([2, 2, 3], [2.826_770_43, 0.799_330_38, 1.817_152_62]),
+    ([2, 3, 3], [2.996_910_99, 1.225_930_53, 1.806_536_61]),
+    ([0, 1, 2], [1.873_941_06, 2.050_271_82, -0.298_359_96]),
+    ([0, 1, 3], [2.566_095_66, 7.034_281_98, 0.625_753_74]),
+    ([0, 2, 3], [4.083_294_84, -1.404_083_58, 2.149_955_22]),
+    ([1, 2, 3], [6.000_670_6, 2.730_450_48, 1.659_242_27]),
+];
+
+/// Returns the RGB of the mix of the primaries with the concentrations
+/// `c`.
+#[inline]
+pub(crate) fn eval_polynomial(c: &[f32; 4]) -> [f32; 3] {
+    let mut rgb = [0.0; 3];
+    for ([i, j, k], coefficients) in POLYNOMIAL.iter() {
+        let term = c[*i] * c[*j] * c[*k];
+        rgb.iter_mut()
+            .zip(coefficients)
+            .for_each(|(rgb, coefficient)| *rgb += term * coefficient);
+    }
+
+    rgb
+}
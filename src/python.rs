@@ -0,0 +1,152 @@
+//! A Python API via [PyO3](https://pyo3.rs/).
+//!
+//! Build with `maturin develop --features python`. Colors are passed as
+//! encoded sRGB (gamma 2.2) `(r, g, b)` tuples of `int`s. Images are numpy
+//! `uint8` arrays of shape `(height, width, 3)`.
+use crate::{
+    gradient_into_slice, mix_srgb_u8, pigment::PIGMENT_LEN, solve_recipe,
+    Gradient, Pigment,
+};
+use alloc::{format, string::String, vec, vec::Vec};
+use numpy::{
+    ndarray::Array3, IntoPyArray, PyArray3, PyReadonlyArray3,
+    PyUntypedArrayMethods,
+};
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+/// Mixes two colors.
+#[pyfunction(name = "mix_srgb_u8")]
+fn py_mix_srgb_u8(a: [u8; 3], b: [u8; 3], ratio: f32) -> [u8; 3] {
+    mix_srgb_u8(&a, &b, ratio)
+}
+
+/// Mixes two images of the same shape pixel by pixel.
+#[pyfunction(name = "mix_images")]
+fn py_mix_images<'py>(
+    py: Python<'py>,
+    a: PyReadonlyArray3<'py, u8>,
+    b: PyReadonlyArray3<'py, u8>,
+    ratio: f32,
+) -> PyResult<Bound<'py, PyArray3<u8>>> {
+    let shape = a.shape();
+    if shape != b.shape() || 3 != shape[2] {
+        return Err(PyValueError::new_err(
+            "images must have the same (height, width, 3) shape",
+        ));
+    }
+    let (height, width) = (shape[0], shape[1]);
+
+    let (a, b) = (a.as_array(), b.as_array());
+    let mut mixed = Array3::<u8>::zeros((height, width, 3));
+    for y in 0..height {
+        for x in 0..width {
+            let srgb = mix_srgb_u8(
+                &[a[[y, x, 0]], a[[y, x, 1]], a[[y, x, 2]]],
+                &[b[[y, x, 0]], b[[y, x, 1]], b[[y, x, 2]]],
+                ratio,
+            );
+            for (c, &value) in srgb.iter().enumerate() {
+                mixed[[y, x, c]] = value;
+            }
+        }
+    }
+
+    Ok(mixed.into_pyarray_bound(py))
+}
+
+/// Returns `n` evenly spaced mixes from `a` to `b`, both ends included.
+#[pyfunction(name = "gradient_srgb_u8")]
+fn py_gradient_srgb_u8(a: [u8; 3], b: [u8; 3], n: usize) -> Vec<[u8; 3]> {
+    let mut gradient = vec![[0; 3]; n];
+    gradient_into_slice(&a, &b, &mut gradient);
+
+    gradient
+}
+
+/// Returns `n` evenly spaced samples of a gradient through `stops`, given as
+/// `(position, (r, g, b))` pairs.
+#[pyfunction(name = "gradient")]
+fn py_gradient(stops: Vec<(f32, [u8; 3])>, n: usize) -> Vec<[u8; 3]> {
+    stops
+        .into_iter()
+        .fold(Gradient::new(), |gradient, (position, [r, g, b])| {
+            gradient.with_stop(position, Pigment::from_srgb_u8(r, g, b))
+        })
+        .samples(n)
+        .map(Into::into)
+        .collect()
+}
+
+/// Finds weights for mixing `paints` to match `target`.
+///
+/// Returns `(weights, delta_e)` or `None` if `paints` is empty.
+#[pyfunction(name = "solve_recipe")]
+fn py_solve_recipe(
+    target: [u8; 3],
+    paints: Vec<[u8; 3]>,
+) -> Option<(Vec<f32>, f32)> {
+    let target = Pigment::from_srgb_u8(target[0], target[1], target[2]);
+    let paints: Vec<Pigment> = paints
+        .into_iter()
+        .map(|[r, g, b]| Pigment::from_srgb_u8(r, g, b))
+        .collect();
+
+    solve_recipe(&target, &paints)
+        .map(|recipe| (recipe.weights, recipe.delta_e))
+}
+
+/// A color represented as pigment mixture.
+///
+/// Mix several colors in this form and convert back once, instead of
+/// converting on every mix.
+#[pyclass(name = "Pigment", module = "pigment_mixing")]
+#[derive(Clone, Debug)]
+pub struct PyPigment(Pigment);
+
+#[pymethods]
+impl PyPigment {
+    /// Constructs a pigment from encoded sRGB (gamma 2.2) components.
+    #[new]
+    fn new(r: u8, g: u8, b: u8) -> Self {
+        PyPigment(Pigment::from_srgb_u8(r, g, b))
+    }
+
+    /// Constructs a pigment from its latent representation.
+    #[staticmethod]
+    fn from_latent(latent: [f32; PIGMENT_LEN]) -> Self {
+        PyPigment(latent.into())
+    }
+
+    /// Returns this pigment mixed with `other` using `ratio`.
+    fn mix(&self, other: &PyPigment, ratio: f32) -> Self {
+        PyPigment(Pigment::from_mix(self.0, other.0, ratio))
+    }
+
+    /// Returns the color as `(r, g, b)`.
+    fn to_srgb_u8(&self) -> [u8; 3] {
+        self.0.into()
+    }
+
+    /// Returns the latent representation, in which mixing is linear.
+    fn latent(&self) -> [f32; PIGMENT_LEN] {
+        self.0.into()
+    }
+
+    fn __repr__(&self) -> String {
+        let [r, g, b]: [u8; 3] = self.0.into();
+        format!("Pigment({r}, {g}, {b})")
+    }
+}
+
+/// The `pigment_mixing` Python module.
+#[pymodule]
+fn pigment_mixing(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(py_mix_srgb_u8, module)?)?;
+    module.add_function(wrap_pyfunction!(py_mix_images, module)?)?;
+    module.add_function(wrap_pyfunction!(py_gradient_srgb_u8, module)?)?;
+    module.add_function(wrap_pyfunction!(py_gradient, module)?)?;
+    module.add_function(wrap_pyfunction!(py_solve_recipe, module)?)?;
+    module.add_class::<PyPigment>()?;
+
+    Ok(())
+}
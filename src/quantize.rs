@@ -1,33 +1,921 @@
 use crate::clamp;
-use core::ops::Add;
-use nanorand::Rng as WyRandRng;
+use nanorand::Rng as _;
+use num_traits::{Float, NumCast, PrimInt};
+
+/// The default random number generator for the `mix_*_dither` variants.
+///
+/// Construct it with [`Rng::new_seed()`] to get byte-identical output for the
+/// same inputs, e.g. for golden-image tests or reproducible renders.
 pub use nanorand::WyRand as Rng;
-use num_traits::Float;
 
-/// Random number generator needed for the `mix_*_dither` variants.
-//pub type Rng = WyRand;
+/// A source of random numbers for dithering.
+///
+/// Implemented for [`Rng`]. With the `rand_core` feature, any
+/// [`rand_core::RngCore`](https://docs.rs/rand_core/latest/rand_core/trait.RngCore.html)
+/// can be used by wrapping it in `RandCore`.
+pub trait DitherRng {
+    /// Returns the next random `u32`.
+    fn next_u32(&mut self) -> u32;
+}
+
+impl DitherRng for Rng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.generate()
+    }
+}
+
+/// Adapts a [`rand_core::RngCore`] for use as a [`DitherRng`].
+///
+/// ```ignore
+/// let mut rng = rand::thread_rng();
+/// let srgb = mix_srgb_u8_dither(
+///     &a,
+///     &b,
+///     0.5,
+///     DitherNoise::default(),
+///     DitherAmplitude::default(),
+///     &mut RandCore(&mut rng),
+/// );
+/// ```
+#[cfg(feature = "rand_core")]
+#[derive(Clone, Debug)]
+pub struct RandCore<R>(pub R);
+
+#[cfg(feature = "rand_core")]
+impl<R: rand_core::RngCore> DitherRng for RandCore<R> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+}
+
+/// A process-wide RNG for the `mix_*_dither_auto` variants.
+///
+/// Lock-free, so it works without `std` and from several threads. The
+/// sequence depends on the order of calls across threads, so it is not
+/// reproducible.
+#[cfg(target_has_atomic = "64")]
+pub(crate) struct GlobalRng;
+
+#[cfg(target_has_atomic = "64")]
+static GLOBAL_RNG_STATE: core::sync::atomic::AtomicU64 =
+    core::sync::atomic::AtomicU64::new(0x853c_49e6_748f_ea9b);
+
+#[cfg(target_has_atomic = "64")]
+impl DitherRng for GlobalRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        // SplitMix64.
+        let mut hash = GLOBAL_RNG_STATE.fetch_add(
+            0x9e37_79b9_7f4a_7c15,
+            core::sync::atomic::Ordering::Relaxed,
+        );
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+
+        ((hash ^ (hash >> 31)) >> 32) as u32
+    }
+}
 
 /// Generates a random number in the range -0.5 .. 0.5.
 #[inline]
-fn generate_random_number(rng: &mut Rng) -> f32 {
-    rng.generate_range(u32::MIN / 2..=u32::MAX / 2) as f32 / u32::MAX as f32
+fn generate_random_number<R: DitherRng + ?Sized>(rng: &mut R) -> f32 {
+    (rng.next_u32() >> 8) as f32 / (1u32 << 24) as f32 - 0.5
+}
+
+/// How dither noise is applied to the three channels of a color, by the
+/// `mix_*_dither` variants, the `dither_srgb_*_into_with_amplitude` functions
+/// and `Quantizer`.
+///
+/// The dithers keyed by pixel position look up the noise at a different
+/// position for each channel to get independent noise.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DitherNoise {
+    /// Draws independent noise for each channel.
+    ///
+    /// The noise mostly shifts hue and saturation, which the eye is less
+    /// sensitive to than changes in brightness.
+    #[default]
+    PerChannel,
+    /// Applies the same noise to all channels.
+    ///
+    /// Cheaper, but the correlated noise shows up as luminance speckle.
+    Shared,
+}
+
+/// The amplitude and distribution of random dither noise, in units of the
+/// least significant bit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DitherAmplitude {
+    /// Uniformly distributed noise in `-amplitude..amplitude`.
+    ///
+    /// The default is `Rectangular(0.5)`, which hides banding without adding
+    /// more noise than necessary.
+    Rectangular(f32),
+    /// Noise in `-amplitude..amplitude` with a triangular distribution, the
+    /// sum of two uniform random numbers.
+    ///
+    /// `Triangular(1.0)` makes the quantization error independent of the
+    /// signal, which is the theoretically correct choice, e.g. for media that
+    /// are processed further.
+    Triangular(f32),
+}
+
+impl Default for DitherAmplitude {
+    fn default() -> Self {
+        DitherAmplitude::Rectangular(0.5)
+    }
+}
+
+impl DitherAmplitude {
+    /// Returns a random offset with this amplitude and distribution.
+    #[inline]
+    fn sample<R: DitherRng + ?Sized>(self, rng: &mut R) -> f32 {
+        match self {
+            DitherAmplitude::Rectangular(amplitude) => {
+                2.0 * amplitude * generate_random_number(rng)
+            }
+            DitherAmplitude::Triangular(amplitude) => {
+                amplitude
+                    * (generate_random_number(rng)
+                        + generate_random_number(rng))
+            }
+        }
+    }
+
+    /// Scales two offsets from `-0.5..0.5` to this amplitude and
+    /// distribution.
+    #[inline]
+    fn combine(self, a: f32, b: f32) -> f32 {
+        match self {
+            DitherAmplitude::Rectangular(amplitude) => 2.0 * amplitude * a,
+            DitherAmplitude::Triangular(amplitude) => amplitude * (a + b),
+        }
+    }
+}
+
+/// A source of dither offsets for [`quantize()`].
+///
+/// Implemented for every [`DitherRng`], which adds random noise with an
+/// amplitude of 0.5, and for [`NoDither`].
+pub trait DitherSource {
+    /// Returns the offset, in units of the least significant bit, to add to
+    /// the next value before rounding.
+    fn offset(&mut self) -> f32;
+}
+
+impl<R: DitherRng + ?Sized> DitherSource for R {
+    #[inline]
+    fn offset(&mut self) -> f32 {
+        generate_random_number(self)
+    }
+}
+
+/// A [`DitherSource`] that does not dither.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NoDither;
+
+impl DitherSource for NoDither {
+    #[inline]
+    fn offset(&mut self) -> f32 {
+        0.0
+    }
 }
 
+/// Replays a single offset, to share noise between channels.
+struct FixedOffset(f32);
+
+impl DitherSource for FixedOffset {
+    #[inline]
+    fn offset(&mut self) -> f32 {
+        self.0
+    }
+}
+
+/// How [`quantize()`] rounds values that fall between two integers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Rounds to the nearest integer, halfway cases away from zero.
+    #[default]
+    Nearest,
+    /// Rounds to the nearest integer, halfway cases to the even one. Avoids
+    /// the slight upward bias of [`Nearest`](RoundingMode::Nearest).
+    NearestEven,
+    /// Rounds down.
+    Floor,
+}
+
+impl RoundingMode {
+    #[inline]
+    fn round<T: Float>(self, value: T) -> T {
+        match self {
+            RoundingMode::Nearest => value.round(),
+            RoundingMode::NearestEven => {
+                let floor = value.floor();
+                let half = T::one() / (T::one() + T::one());
+                match (value - floor).partial_cmp(&half) {
+                    Some(core::cmp::Ordering::Less) => floor,
+                    Some(core::cmp::Ordering::Greater) => floor + T::one(),
+                    _ if (floor * half).floor() == floor * half => floor,
+                    _ => floor + T::one(),
+                }
+            }
+            RoundingMode::Floor => value.floor(),
+        }
+    }
+}
+
+/// Quantizes a normalized `value` to the integer type `U`.
+///
+/// For unsigned `U`, `0.0..=1.0` maps to `0..=U::MAX`. For signed `U`,
+/// `-1.0..=1.0` maps to `-U::MAX..=U::MAX`. The result is clamped to that
+/// range.
+///
+/// The offset from `dither` is added before rounding.
+///
+/// ```
+/// # use pigment_mixing::{quantize, NoDither, RoundingMode};
+/// let byte: u8 = quantize(0.5f32, RoundingMode::Nearest, &mut NoDither);
+/// assert_eq!(128, byte);
+///
+/// let sample: i16 = quantize(-1.0f32, RoundingMode::Nearest, &mut NoDither);
+/// assert_eq!(-i16::MAX, sample);
+/// ```
 #[inline]
-pub(crate) fn quantize_triplet<T>(
-    value: (T, T, T),
-    one: T,
+pub fn quantize<T, U>(
+    value: T,
+    rounding: RoundingMode,
+    dither: &mut (impl DitherSource + ?Sized),
+) -> U
+where
+    T: Float,
+    U: PrimInt,
+{
+    let max = <T as NumCast>::from(U::max_value()).unwrap_or_else(T::max_value);
+    let min = if U::min_value() < U::zero() {
+        -max
+    } else {
+        T::zero()
+    };
+
+    quantize_scaled(value * max, min, max, rounding, dither)
+}
+
+/// Quantizes a normalized `value` from `0.0` to `1.0` to `bits` bits.
+///
+/// The result is in `0..=2^bits - 1`, e.g. `0..=1023` for 10 bits. Use this
+/// for targets that are not a whole integer type, like 10 or 12 bit video,
+/// 4 bit e-ink displays or LED drivers, or posterization effects.
+///
+/// The offset from `dither` is added before rounding.
+///
+/// # Panics
+///
+/// If `bits` is not in `1..=16`.
+///
+/// ```
+/// # use pigment_mixing::{quantize_bits, NoDither, RoundingMode};
+/// let rounding = RoundingMode::Nearest;
+///
+/// assert_eq!(1023, quantize_bits(1.0f32, 10, rounding, &mut NoDither));
+/// assert_eq!(1, quantize_bits(0.6f32, 1, rounding, &mut NoDither));
+/// ```
+#[inline]
+pub fn quantize_bits<T: Float>(
+    value: T,
+    bits: u32,
+    rounding: RoundingMode,
+    dither: &mut (impl DitherSource + ?Sized),
+) -> u16 {
+    assert!((1..=16).contains(&bits), "bit depth must be in 1..=16");
+
+    let max = <T as NumCast>::from((1u32 << bits) - 1).unwrap_or_else(T::one);
+    quantize_scaled(value * max, T::zero(), max, rounding, dither)
+}
+
+/// Quantizes normalized `values` from `0.0` to `1.0` to `bits` bits, writing
+/// them into `out`.
+///
+/// See [`quantize_bits()`].
+///
+/// # Panics
+///
+/// If `bits` is not in `1..=16` or `out` has a different length than
+/// `values`.
+pub fn quantize_bits_into<T: Float>(
+    values: &[T],
+    bits: u32,
+    rounding: RoundingMode,
+    dither: &mut (impl DitherSource + ?Sized),
+    out: &mut [u16],
+) {
+    assert_eq!(values.len(), out.len(), "output has the wrong size");
+    values.iter().zip(out.iter_mut()).for_each(|(value, out)| {
+        *out = quantize_bits(*value, bits, rounding, dither)
+    });
+}
+
+/// Quantizes a `value` already scaled to the integer range and clamps it to
+/// `min..=max`.
+#[inline]
+pub(crate) fn quantize_scaled<T, U>(
+    value: T,
     min: T,
     max: T,
-    rng: &mut Rng,
-) -> (T, T, T)
+    rounding: RoundingMode,
+    dither: &mut (impl DitherSource + ?Sized),
+) -> U
 where
-    T: Float + Add<f32, Output = T>,
+    T: Float,
+    U: PrimInt,
 {
-    let random = generate_random_number(rng);
-    (
-        clamp((one * value.0 + random).round(), min, max),
-        clamp((one * value.1 + random).round(), min, max),
-        clamp((one * value.2 + random).round(), min, max),
-    )
+    let offset = <T as NumCast>::from(dither.offset()).unwrap_or_else(T::zero);
+    let value = clamp(rounding.round(value + offset), min, max);
+
+    // `max` may not be exactly representable in `T`, e.g. `u32::MAX` as
+    // `f32`, so saturate if the cast fails.
+    U::from(value).unwrap_or_else(|| {
+        if T::zero() < value {
+            U::max_value()
+        } else {
+            U::min_value()
+        }
+    })
+}
+
+/// Quantizes three normalized channels, see [`quantize()`].
+#[inline]
+pub(crate) fn quantize_triplet<U, R>(
+    value: [f32; 3],
+    noise: DitherNoise,
+    amplitude: DitherAmplitude,
+    rng: &mut R,
+) -> [U; 3]
+where
+    U: PrimInt,
+    R: DitherRng + ?Sized,
+{
+    match noise {
+        DitherNoise::PerChannel => value.map(|value| {
+            quantize(
+                value,
+                RoundingMode::Nearest,
+                &mut FixedOffset(amplitude.sample(rng)),
+            )
+        }),
+        DitherNoise::Shared => {
+            let mut offset = FixedOffset(amplitude.sample(rng));
+            value.map(|value| {
+                quantize(value, RoundingMode::Nearest, &mut offset)
+            })
+        }
+    }
+}
+
+/// The dither used to quantize a buffer with [`dither_srgb_u8_into()`] and
+/// [`dither_srgb_u16_into()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Dither<'a> {
+    /// Adds random noise with an amplitude of 0.5 to every pixel before
+    /// rounding. Cheap and stateless but noisy.
+    #[default]
+    Random,
+    /// Like [`Random`](Dither::Random) but the noise is derived from the
+    /// pixel position and the given seed instead of the RNG.
+    ///
+    /// The same inputs and seed always produce byte-identical output,
+    /// regardless of the state of the RNG or which part of a larger image is
+    /// quantized.
+    Seeded(u64),
+    /// Ordered dithering with an 8 × 8
+    /// [Bayer matrix](https://en.wikipedia.org/wiki/Ordered_dithering) keyed by
+    /// pixel position.
+    ///
+    /// Deterministic and tileable. Unlike random noise it does not shimmer
+    /// between the frames of an animation.
+    Ordered,
+    /// Ordered dithering with an embedded 64 × 64 blue noise texture keyed by
+    /// pixel position.
+    ///
+    /// Deterministic and tileable like [`Ordered`](Dither::Ordered) but
+    /// without its visible cross-hatch pattern.
+    BlueNoise,
+    /// Ordered dithering with a user-supplied threshold texture, e.g. a larger
+    /// blue noise texture, keyed by pixel position and tiled.
+    ///
+    /// The texture holds one threshold per pixel, from `0` to `255`, in
+    /// row-major order and should use every value about equally often.
+    Texture {
+        /// The thresholds.
+        texture: &'a [u8],
+        /// The width of the texture in pixels.
+        width: usize,
+    },
+    /// [Floyd–Steinberg](https://en.wikipedia.org/wiki/Floyd%E2%80%93Steinberg_dithering)
+    /// error diffusion. Distributes all of the quantization error of a pixel
+    /// to its four unprocessed neighbors.
+    FloydSteinberg,
+    /// [Atkinson](https://en.wikipedia.org/wiki/Atkinson_dithering) error
+    /// diffusion. Distributes three quarters of the quantization error of a
+    /// pixel to six neighbors. Gives more contrast but loses detail in
+    /// highlights and shadows.
+    Atkinson,
+}
+
+/// The 8 × 8 Bayer index matrix.
+const BAYER_8: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// The width and height of the embedded blue noise texture.
+const BLUE_NOISE_SIZE: usize = 64;
+
+/// A blue noise threshold texture generated with the void-and-cluster method.
+static BLUE_NOISE: &[u8; BLUE_NOISE_SIZE * BLUE_NOISE_SIZE] =
+    include_bytes!("../data/blue_noise_64.bin");
+
+/// Returns the dither offset, in the range -0.5 .. 0.5, of a pixel for a tiled
+/// threshold `texture` with values from `0` to `255`.
+#[inline]
+fn texture_offset(texture: &[u8], width: usize, x: usize, y: usize) -> f32 {
+    let height = texture.len() / width;
+    (texture[(y % height) * width + x % width] as f32 + 0.5) / 256.0 - 0.5
+}
+
+/// Returns a pseudo random offset, in the range -0.5 .. 0.5, for a pixel.
+#[inline]
+fn hash_offset(seed: u64, x: usize, y: usize) -> f32 {
+    // SplitMix64 finalizer over the seed and position.
+    let mut hash = seed
+        ^ (x as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+        ^ (y as u64).wrapping_mul(0xc2b2_ae3d_27d4_eb4f);
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^= hash >> 31;
+
+    (hash >> 40) as f32 / (1u64 << 24) as f32 - 0.5
+}
+
+/// Returns the ordered dither offset, in the range -0.5 .. 0.5, for a pixel.
+#[inline]
+fn bayer_offset(x: usize, y: usize) -> f32 {
+    (BAYER_8[y % 8][x % 8] as f32 + 0.5) / 64.0 - 0.5
+}
+
+/// How far from the pixel each channel reads the dithers keyed by pixel
+/// position, so the channels get different offsets.
+///
+/// The shifts are odd in `x + y`, which flips the coarsest level of the Bayer
+/// matrix against the first channel.
+const CHANNEL_SHIFT: [(usize, usize); 3] = [(0, 0), (37, 18), (18, 45)];
+
+impl Dither<'_> {
+    /// Panics if the texture of a [`Dither::Texture`] is malformed.
+    fn validate(&self) {
+        if let Dither::Texture { texture, width } = *self {
+            assert!(
+                0 < width && !texture.is_empty() && 0 == texture.len() % width,
+                "texture has the wrong size"
+            );
+        }
+    }
+
+    /// Returns the offset with the given `amplitude` to add to `channel` of
+    /// the pixel at `x`, `y` before rounding.
+    ///
+    /// Ordered dithers have a single threshold per pixel and channel and are
+    /// always rectangular.
+    #[inline]
+    fn offset(
+        &self,
+        rng: &mut (impl DitherRng + ?Sized),
+        amplitude: DitherAmplitude,
+        x: usize,
+        y: usize,
+        channel: usize,
+    ) -> f32 {
+        let ordered = |offset| 2.0 * offset * amplitude.combine(0.5, 0.5);
+        let (x, y) =
+            (x + CHANNEL_SHIFT[channel].0, y + CHANNEL_SHIFT[channel].1);
+
+        match *self {
+            Dither::Random => amplitude.sample(rng),
+            Dither::Seeded(seed) => amplitude
+                .combine(hash_offset(seed, x, y), hash_offset(!seed, x, y)),
+            Dither::Ordered => ordered(bayer_offset(x, y)),
+            Dither::BlueNoise => {
+                ordered(texture_offset(BLUE_NOISE, BLUE_NOISE_SIZE, x, y))
+            }
+            Dither::Texture { texture, width } => {
+                ordered(texture_offset(texture, width, x, y))
+            }
+            Dither::FloydSteinberg | Dither::Atkinson => 0.0,
+        }
+    }
+
+    /// Returns the neighbors, as `(dx, dy)` offsets, that receive the error of
+    /// a pixel and their weights.
+    fn kernel(self) -> &'static [(isize, usize, f32)] {
+        match self {
+            Dither::Random
+            | Dither::Seeded(_)
+            | Dither::Ordered
+            | Dither::BlueNoise
+            | Dither::Texture { .. } => &[],
+            Dither::FloydSteinberg => &[
+                (1, 0, 7.0 / 16.0),
+                (-1, 1, 3.0 / 16.0),
+                (0, 1, 5.0 / 16.0),
+                (1, 1, 1.0 / 16.0),
+            ],
+            Dither::Atkinson => &[
+                (1, 0, 1.0 / 8.0),
+                (2, 0, 1.0 / 8.0),
+                (-1, 1, 1.0 / 8.0),
+                (0, 1, 1.0 / 8.0),
+                (1, 1, 1.0 / 8.0),
+                (0, 2, 1.0 / 8.0),
+            ],
+        }
+    }
+}
+
+/// The configuration and position of a dither that quantizes an image row by
+/// row, shared by [`dither_into()`] and `Quantizer`.
+#[derive(Clone, Copy, Debug)]
+struct RowDither<'a> {
+    width: usize,
+    dither: Dither<'a>,
+    noise: DitherNoise,
+    amplitude: DitherAmplitude,
+    /// The index of the next row.
+    y: usize,
+}
+
+impl<'a> RowDither<'a> {
+    fn new(
+        width: usize,
+        dither: Dither<'a>,
+        noise: DitherNoise,
+        amplitude: DitherAmplitude,
+    ) -> Self {
+        dither.validate();
+
+        Self {
+            width,
+            dither,
+            noise,
+            amplitude,
+            y: 0,
+        }
+    }
+
+    /// Quantizes the next row to the full range of `U`, writing it into `dst`.
+    ///
+    /// `rows[0]` holds the components of the row with the error diffused into
+    /// it added. The quantization error is diffused into it and the next two
+    /// rows, `rows[1]` and `rows[2]`, which are empty past the end of the
+    /// image.
+    fn quantize_row<U: PrimInt>(
+        &mut self,
+        rng: &mut (impl DitherRng + ?Sized),
+        rows: [&mut [f32]; 3],
+        dst: &mut [U],
+    ) {
+        let max = <f32 as NumCast>::from(U::max_value()).unwrap_or(f32::MAX);
+        let min = if U::min_value() < U::zero() {
+            -max
+        } else {
+            0.0
+        };
+        let kernel = self.dither.kernel();
+
+        for x in 0..self.width {
+            let mut offset = FixedOffset(0.0);
+
+            for channel in 0..3 {
+                if 0 == channel || DitherNoise::PerChannel == self.noise {
+                    offset.0 = self.dither.offset(
+                        rng,
+                        self.amplitude,
+                        x,
+                        self.y,
+                        channel,
+                    );
+                }
+                let index = 3 * x + channel;
+                let value = rows[0][index] * max;
+                dst[index] = quantize_scaled(
+                    value,
+                    min,
+                    max,
+                    RoundingMode::Nearest,
+                    &mut offset,
+                );
+
+                let quantized =
+                    <f32 as NumCast>::from(dst[index]).unwrap_or_default();
+                let error = (value - quantized) / max;
+                for &(dx, dy, weight) in kernel {
+                    let nx = x as isize + dx;
+                    if 0 <= nx && (nx as usize) < self.width {
+                        if let Some(component) =
+                            rows[dy].get_mut(3 * nx as usize + channel)
+                        {
+                            *component += error * weight;
+                        }
+                    }
+                }
+            }
+        }
+
+        self.y += 1;
+    }
+}
+
+/// Quantizes the components of `pixels` to the full range of `U`, scanline by
+/// scanline, and writes them into `out`.
+fn dither_into<U: PrimInt>(
+    pixels: &mut [f32],
+    width: usize,
+    dither: Dither<'_>,
+    noise: DitherNoise,
+    amplitude: DitherAmplitude,
+    rng: &mut (impl DitherRng + ?Sized),
+    out: &mut [U],
+) {
+    assert_eq!(pixels.len(), out.len(), "output has the wrong size");
+    assert_eq!(0, pixels.len() % 3, "pixels have three components");
+    if 0 == width {
+        return;
+    }
+    assert_eq!(0, pixels.len() % (width * 3), "pixels have the wrong size");
+
+    let mut row_dither = RowDither::new(width, dither, noise, amplitude);
+    for (y, dst) in out.chunks_exact_mut(width * 3).enumerate() {
+        let (row, rest) = pixels[y * width * 3..].split_at_mut(width * 3);
+        let (next, rest) = rest.split_at_mut(rest.len().min(width * 3));
+        let (after, _) = rest.split_at_mut(rest.len().min(width * 3));
+
+        row_dither.quantize_row(rng, [row, next, after], dst);
+    }
+}
+
+/// Quantizes encoded sRGB (gamma 2.2) pixels with components from `0.0` to
+/// `1.0` to `u8` components, writing them into `out`.
+///
+/// `pixels` holds three components per pixel in row-major order with `width`
+/// pixels per row. It is used as scratch space for the error diffusion and
+/// holds unspecified values afterwards. Nothing is allocated.
+///
+/// # Panics
+///
+/// If `pixels` does not hold a whole number of rows, `out` has a different
+/// length or the texture of a [`Dither::Texture`] does not hold a whole number
+/// of rows.
+///
+/// ```
+/// # use pigment_mixing::{dither_srgb_u8_into, Dither, Rng};
+/// // A smooth dark ramp that would band if simply rounded.
+/// let width = 256;
+/// let mut pixels: Vec<f32> = (0..width * 3)
+///     .map(|i| (i / 3) as f32 / width as f32 * 0.05)
+///     .collect();
+///
+/// let mut out = vec![0; width * 3];
+/// dither_srgb_u8_into(
+///     &mut pixels,
+///     width,
+///     Dither::FloydSteinberg,
+///     &mut Rng::new_seed(0),
+///     &mut out,
+/// );
+/// ```
+pub fn dither_srgb_u8_into(
+    pixels: &mut [f32],
+    width: usize,
+    dither: Dither<'_>,
+    rng: &mut (impl DitherRng + ?Sized),
+    out: &mut [u8],
+) {
+    dither_srgb_u8_into_with_amplitude(
+        pixels,
+        width,
+        dither,
+        DitherNoise::default(),
+        DitherAmplitude::default(),
+        rng,
+        out,
+    );
+}
+
+/// Quantizes like [`dither_srgb_u8_into()`] with the noise applied to the
+/// channels as given by `noise` and shaped by `amplitude`.
+///
+/// Error diffusion dithers add no noise and ignore both.
+///
+/// ```
+/// # use pigment_mixing::{
+/// #     dither_srgb_u8_into_with_amplitude, Dither, DitherAmplitude,
+/// #     DitherNoise, Rng,
+/// # };
+/// let width = 256;
+/// let mut pixels: Vec<f32> = (0..width * 3)
+///     .map(|i| (i / 3) as f32 / width as f32 * 0.05)
+///     .collect();
+///
+/// let mut out = vec![0; width * 3];
+/// dither_srgb_u8_into_with_amplitude(
+///     &mut pixels,
+///     width,
+///     Dither::BlueNoise,
+///     DitherNoise::Shared,
+///     DitherAmplitude::Triangular(1.0),
+///     &mut Rng::new_seed(0),
+///     &mut out,
+/// );
+/// ```
+pub fn dither_srgb_u8_into_with_amplitude(
+    pixels: &mut [f32],
+    width: usize,
+    dither: Dither<'_>,
+    noise: DitherNoise,
+    amplitude: DitherAmplitude,
+    rng: &mut (impl DitherRng + ?Sized),
+    out: &mut [u8],
+) {
+    dither_into(pixels, width, dither, noise, amplitude, rng, out);
+}
+
+/// Quantizes encoded sRGB (gamma 2.2) pixels with components from `0.0` to
+/// `1.0` to `u16` components, writing them into `out`.
+///
+/// See [`dither_srgb_u8_into()`].
+pub fn dither_srgb_u16_into(
+    pixels: &mut [f32],
+    width: usize,
+    dither: Dither<'_>,
+    rng: &mut (impl DitherRng + ?Sized),
+    out: &mut [u16],
+) {
+    dither_srgb_u16_into_with_amplitude(
+        pixels,
+        width,
+        dither,
+        DitherNoise::default(),
+        DitherAmplitude::default(),
+        rng,
+        out,
+    );
+}
+
+/// Quantizes like [`dither_srgb_u16_into()`] with the noise applied to the
+/// channels as given by `noise` and shaped by `amplitude`.
+///
+/// See [`dither_srgb_u8_into_with_amplitude()`].
+pub fn dither_srgb_u16_into_with_amplitude(
+    pixels: &mut [f32],
+    width: usize,
+    dither: Dither<'_>,
+    noise: DitherNoise,
+    amplitude: DitherAmplitude,
+    rng: &mut (impl DitherRng + ?Sized),
+    out: &mut [u16],
+) {
+    dither_into(pixels, width, dither, noise, amplitude, rng, out);
+}
+
+/// Quantizes an image scanline by scanline.
+///
+/// Holds the [`Dither`] configuration and, for error diffusion, the error
+/// carried over to the following rows, so images can be quantized as they
+/// are produced, without keeping them in memory.
+///
+/// ```
+/// # use pigment_mixing::{Dither, Quantizer};
+/// let width = 256;
+/// let mut quantizer = Quantizer::new(width, Dither::FloydSteinberg);
+///
+/// let mut row = vec![0u8; width * 3];
+/// for y in 0..64 {
+///     let src: Vec<f32> = (0..width * 3)
+///         .map(|i| (i / 3 + y) as f32 / 1024.0)
+///         .collect();
+///     quantizer.quantize_row(&src, &mut row);
+/// }
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct Quantizer<'a, R = Rng> {
+    row_dither: RowDither<'a>,
+    rng: R,
+    /// The error diffused into the current and the next two rows.
+    error: [alloc::vec::Vec<f32>; 3],
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Quantizer<'a> {
+    /// Constructs a `Quantizer` for rows of `width` pixels with three
+    /// components each.
+    ///
+    /// Random dithering uses an [`Rng`] with a fixed seed, so the output is
+    /// reproducible. Use [`with_rng()`](Quantizer::with_rng) to change that.
+    ///
+    /// # Panics
+    ///
+    /// If the texture of a [`Dither::Texture`] does not hold a whole number of
+    /// rows.
+    pub fn new(width: usize, dither: Dither<'a>) -> Self {
+        Self {
+            row_dither: RowDither::new(
+                width,
+                dither,
+                DitherNoise::default(),
+                DitherAmplitude::default(),
+            ),
+            rng: Rng::new_seed(0),
+            error: core::array::from_fn(|_| alloc::vec![0.0; width * 3]),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, R: DitherRng> Quantizer<'a, R> {
+    /// Replaces the RNG used for [`Dither::Random`] and returns the quantizer.
+    pub fn with_rng<S: DitherRng>(self, rng: S) -> Quantizer<'a, S> {
+        Quantizer {
+            row_dither: self.row_dither,
+            rng,
+            error: self.error,
+        }
+    }
+
+    /// Sets how the dither noise is applied to the channels and returns the
+    /// quantizer.
+    ///
+    /// Applies to all but the error diffusion dithers.
+    pub fn with_noise(mut self, noise: DitherNoise) -> Self {
+        self.row_dither.noise = noise;
+        self
+    }
+
+    /// Sets the amplitude of the dither and returns the quantizer.
+    ///
+    /// Applies to all but the error diffusion dithers. The ordered dithers
+    /// have a single threshold per pixel and stay rectangular, with the
+    /// same total amplitude.
+    pub fn with_amplitude(mut self, amplitude: DitherAmplitude) -> Self {
+        self.row_dither.amplitude = amplitude;
+        self
+    }
+
+    /// Returns the width of a row in pixels.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.row_dither.width
+    }
+
+    /// Returns the index of the next row.
+    #[inline]
+    pub fn row(&self) -> usize {
+        self.row_dither.y
+    }
+
+    /// Forgets all diffused error and starts over at the first row, e.g. for
+    /// the next image.
+    pub fn reset(&mut self) {
+        self.row_dither.y = 0;
+        self.error.iter_mut().for_each(|row| row.fill(0.0));
+    }
+
+    /// Quantizes the next row.
+    ///
+    /// `src` holds three components per pixel, normalized as for
+    /// [`quantize()`], e.g. encoded sRGB (gamma 2.2) from `0.0` to `1.0`.
+    /// `dst` receives the quantized components.
+    ///
+    /// # Panics
+    ///
+    /// If `src` or `dst` do not hold exactly [`width()`](Self::width) pixels.
+    pub fn quantize_row<U: PrimInt>(&mut self, src: &[f32], dst: &mut [U]) {
+        let width = self.row_dither.width;
+        assert_eq!(width * 3, src.len(), "source row has the wrong size");
+        assert_eq!(width * 3, dst.len(), "destination row has the wrong size");
+
+        let [row, next, after] = &mut self.error;
+        row.iter_mut().zip(src).for_each(|(row, src)| *row += src);
+        self.row_dither
+            .quantize_row(&mut self.rng, [row, next, after], dst);
+
+        self.error.rotate_left(1);
+        self.error[2].fill(0.0);
+    }
 }
@@ -0,0 +1,148 @@
+//! Handling of linear colors outside `0.0..=1.0`.
+#[cfg(feature = "pigment")]
+use crate::Pigment;
+use crate::{mixbox, Error};
+
+/// What happens to linear sRGB components outside `0.0..=1.0`, e.g. from
+/// compositing or HDR pipelines.
+///
+/// Functions without a policy parameter use
+/// [`ClampInput`](RangePolicy::ClampInput).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RangePolicy {
+    /// Clamps every input component to `0.0..=1.0` before it is converted.
+    /// NaN becomes `0.0`.
+    ///
+    /// ```
+    /// # use pigment_mixing::{mix_linear_srgb_f32_with, RangePolicy};
+    /// let mix = mix_linear_srgb_f32_with(
+    ///     &[2.0, -1.0, 0.5],
+    ///     &[1.0, 0.0, 0.5],
+    ///     0.5,
+    ///     RangePolicy::ClampInput,
+    /// )
+    /// .unwrap();
+    ///
+    /// // The inputs were the same after clamping.
+    /// assert!((mix[0] - 1.0).abs() < 0.01 && mix[1].abs() < 0.01);
+    /// ```
+    #[default]
+    ClampInput,
+    /// Mixes the clamped inputs as pigments and the parts outside
+    /// `0.0..=1.0` linearly, like light, then clamps the sum to `0.0..=1.0`.
+    ///
+    /// Out of range input thus still affects the result, e.g. an over-white
+    /// highlight brightens a mix with a dark color more than white would.
+    ///
+    /// ```
+    /// # use pigment_mixing::{mix_linear_srgb_f32_with, RangePolicy};
+    /// let mix = |a: f32, policy| {
+    ///     mix_linear_srgb_f32_with(&[a; 3], &[0.0; 3], 0.5, policy).unwrap()[0]
+    /// };
+    ///
+    /// assert!(mix(1.0, RangePolicy::ClampInput) < mix(1.5, RangePolicy::ClampOutput));
+    /// assert_eq!(1.0, mix(100.0, RangePolicy::ClampOutput));
+    /// ```
+    ClampOutput,
+    /// Rejects input with components outside `0.0..=1.0` or not finite with
+    /// [`Error::OutOfRange`].
+    ///
+    /// ```
+    /// # use pigment_mixing::{mix_linear_srgb_f32_with, Error, RangePolicy};
+    /// assert_eq!(
+    ///     Err(Error::OutOfRange),
+    ///     mix_linear_srgb_f32_with(&[1.5, 0.0, 0.0], &[0.0; 3], 0.5, RangePolicy::Error)
+    /// );
+    /// assert_eq!(
+    ///     Err(Error::OutOfRange),
+    ///     mix_linear_srgb_f32_with(&[f32::NAN, 0.0, 0.0], &[0.0; 3], 0.5, RangePolicy::Error)
+    /// );
+    /// ```
+    Error,
+}
+
+impl RangePolicy {
+    /// Checks `rgb` against the policy.
+    #[inline]
+    fn check(self, rgb: &[f32; 3]) -> Result<(), Error> {
+        if RangePolicy::Error == self
+            && !rgb.iter().all(|c| (0.0..=1.0).contains(c))
+        {
+            Err(Error::OutOfRange)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Returns the part of `rgb` outside `0.0..=1.0`. Non-finite components
+/// are clamped first.
+#[inline]
+fn excess(rgb: &[f32; 3]) -> [f32; 3] {
+    let clamped = mixbox::clamp_rgb(*rgb);
+    [0, 1, 2].map(|i| {
+        let c = rgb[i].clamp(f32::MIN, f32::MAX);
+        if c.is_nan() {
+            0.0
+        } else {
+            c - clamped[i]
+        }
+    })
+}
+
+/// Mixes two linear sRGB colors like
+/// [`mix_linear_srgb_f32()`](crate::mix_linear_srgb_f32), handling components
+/// outside `0.0..=1.0` according to `policy`.
+///
+/// Returns [`Error::OutOfRange`] only with [`RangePolicy::Error`].
+#[inline]
+pub fn mix_linear_srgb_f32_with(
+    srgb_a: &[f32; 3],
+    srgb_b: &[f32; 3],
+    ratio: f32,
+    policy: RangePolicy,
+) -> Result<[f32; 3], Error> {
+    policy.check(srgb_a)?;
+    policy.check(srgb_b)?;
+
+    let mix = mixbox::lerp_rgb(srgb_a, srgb_b, ratio);
+
+    Ok(match policy {
+        RangePolicy::ClampOutput => {
+            let (a, b) = (excess(srgb_a), excess(srgb_b));
+            mixbox::clamp_rgb(
+                [0, 1, 2].map(|i| mix[i] + a[i] + (b[i] - a[i]) * ratio),
+            )
+        }
+        _ => mix,
+    })
+}
+
+#[cfg(feature = "pigment")]
+impl Pigment {
+    /// Constructs a `Pigment` from a linear sRGB color, handling components
+    /// outside `0.0..=1.0` according to `policy`.
+    ///
+    /// A `Pigment` can only hold colors inside `0.0..=1.0`, so
+    /// [`RangePolicy::ClampOutput`] behaves like
+    /// [`RangePolicy::ClampInput`] here. Use
+    /// [`from_linear_hdr()`](Pigment::from_linear_hdr) to keep brighter
+    /// colors.
+    ///
+    /// ```
+    /// # use pigment_mixing::{Pigment, RangePolicy};
+    /// assert!(Pigment::from_linear_srgb_with([0.5, 1.2, 0.0], RangePolicy::Error).is_err());
+    /// assert_eq!(
+    ///     Pigment::from_linear_srgb(0.5, 1.0, 0.0),
+    ///     Pigment::from_linear_srgb_with([0.5, 1.2, 0.0], RangePolicy::ClampInput).unwrap()
+    /// );
+    /// ```
+    #[inline]
+    pub fn from_linear_srgb_with(
+        rgb: [f32; 3],
+        policy: RangePolicy,
+    ) -> Result<Self, Error> {
+        policy.check(&rgb)?;
+        Ok(Self::from(rgb))
+    }
+}
@@ -0,0 +1,566 @@
+use crate::{
+    metrics::linear_srgb_to_oklab, pigment::PIGMENT_LEN, solve::distance,
+    Palette, Pigment,
+};
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt;
+
+/// The number of projected gradient iterations in latent space.
+const LATENT_STEPS: usize = 200;
+/// The number of projected gradient iterations on the perceptual error.
+const PERCEPTUAL_STEPS: usize = 32;
+/// The step used for the finite difference gradient of the perceptual error.
+const GRADIENT_EPSILON: f32 = 1.0e-3;
+
+/// Weights for mixing a set of paints to match a target color.
+///
+/// See [`solve_recipe()`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Recipe {
+    /// One non-negative weight per paint. The weights sum to `1`.
+    pub weights: Vec<f32>,
+    /// The perceptual distance of the mix to the target. This is the
+    /// Euclidean distance in [Oklab](https://bottosson.github.io/posts/oklab/),
+    /// scaled by `100`.
+    pub delta_e: f32,
+}
+
+impl Recipe {
+    /// Mixes `paints` using the weights of the recipe.
+    ///
+    /// # Panics
+    ///
+    /// If `paints` has a different length than [`weights`](Recipe::weights).
+    pub fn mix(&self, paints: &[Pigment]) -> Pigment {
+        assert_eq!(self.weights.len(), paints.len(), "one weight per paint");
+
+        weighted_mix(paints, &self.weights)
+    }
+}
+
+/// Finds non-negative weights, summing to `1`, for mixing `paints` so the
+/// result gets perceptually as close as possible to `target`.
+///
+/// The weights are first solved for with a least squares fit in the latent
+/// space, where mixing is linear. This is then refined by minimizing the
+/// perceptual error directly.
+///
+/// Returns `None` if `paints` is empty or holds non-finite values.
+///
+/// ```
+/// # use pigment_mixing::{solve_recipe, Pigment};
+/// let paints = [
+///     Pigment::from_srgb_u8(254, 236, 0),
+///     Pigment::from_srgb_u8(25, 0, 89),
+///     Pigment::from_srgb_u8(255, 255, 255),
+/// ];
+/// let target = Pigment::from_mix(paints[0], paints[2], 0.25);
+///
+/// let recipe = solve_recipe(&target, &paints).unwrap();
+///
+/// assert!((recipe.weights.iter().sum::<f32>() - 1.0).abs() < 1.0e-4);
+/// assert!(recipe.delta_e < 1.0);
+/// ```
+pub fn solve_recipe(target: &Pigment, paints: &[Pigment]) -> Option<Recipe> {
+    let mut weights = vec![0.0; paints.len()];
+    let mut scratch = vec![0.0; 3 * paints.len()];
+
+    solve_recipe_into(target, paints, &mut weights, &mut scratch)
+        .map(|delta_e| Recipe { weights, delta_e })
+}
+
+/// Finds weights for mixing `paints` to match `target`, like
+/// [`solve_recipe()`], without allocating.
+///
+/// The weights are written to `weights`. `scratch` is working memory of at
+/// least three times the number of paints.
+///
+/// Returns the perceptual distance of the mix to the target, or `None` if
+/// `paints` is empty or holds non-finite values.
+///
+/// # Panics
+///
+/// If `weights` has a different length than `paints` or if `scratch` is too
+/// short.
+///
+/// ```
+/// # use pigment_mixing::{solve_recipe_into, Pigment};
+/// let paints = [
+///     Pigment::from_srgb_u8(254, 236, 0),
+///     Pigment::from_srgb_u8(25, 0, 89),
+///     Pigment::from_srgb_u8(255, 255, 255),
+/// ];
+/// let target = Pigment::from_mix(paints[0], paints[2], 0.25);
+///
+/// let mut weights = [0.0; 3];
+/// let mut scratch = [0.0; 9];
+/// let delta_e =
+///     solve_recipe_into(&target, &paints, &mut weights, &mut scratch).unwrap();
+///
+/// assert!(delta_e < 1.0);
+/// ```
+pub fn solve_recipe_into(
+    target: &Pigment,
+    paints: &[Pigment],
+    weights: &mut [f32],
+    scratch: &mut [f32],
+) -> Option<f32> {
+    let n = paints.len();
+    assert_eq!(weights.len(), n, "one weight per paint");
+    assert!(
+        3 * n <= scratch.len(),
+        "scratch must hold three values per paint"
+    );
+
+    if paints.is_empty() {
+        return None;
+    }
+
+    let (gradient, scratch) = scratch.split_at_mut(n);
+    let (candidate, scratch) = scratch.split_at_mut(n);
+    let sorted = &mut scratch[..n];
+
+    let target_latent: [f32; PIGMENT_LEN] = (*target).into();
+    let latent = |i: usize| -> [f32; PIGMENT_LEN] { paints[i].into() };
+
+    // Least squares on the simplex in latent space. The step is the inverse
+    // of an upper bound of the Lipschitz constant of the gradient.
+    let trace: f32 = (0..n).flat_map(latent).map(|x| x * x).sum();
+    let step = 0.5 / trace.max(f32::EPSILON);
+
+    weights.fill(1.0 / n as f32);
+    for _ in 0..LATENT_STEPS {
+        let mut residual = [0.0f32; PIGMENT_LEN];
+        for (i, weight) in weights.iter().enumerate() {
+            residual
+                .iter_mut()
+                .zip(latent(i))
+                .for_each(|(r, x)| *r += weight * x);
+        }
+        residual
+            .iter_mut()
+            .zip(&target_latent)
+            .for_each(|(r, t)| *r -= t);
+
+        for (i, weight) in weights.iter_mut().enumerate() {
+            let gradient: f32 = 2.0
+                * latent(i)
+                    .iter()
+                    .zip(&residual)
+                    .map(|(x, r)| x * r)
+                    .sum::<f32>();
+            *weight -= step * gradient;
+        }
+        project_to_simplex(weights, sorted);
+    }
+
+    // Refine the perceptual error.
+    let target_lab = linear_srgb_to_oklab((*target).into());
+    let error =
+        |weights: &[f32]| distance(weighted_mix(paints, weights), target_lab);
+
+    let mut delta_e = error(weights);
+    let mut rate = 0.01;
+
+    for _ in 0..PERCEPTUAL_STEPS {
+        for i in 0..n {
+            candidate.copy_from_slice(weights);
+            candidate[i] += GRADIENT_EPSILON;
+            gradient[i] = (error(candidate) - delta_e) / GRADIENT_EPSILON;
+        }
+
+        // Backtracking line search.
+        loop {
+            candidate
+                .iter_mut()
+                .zip(weights.iter().zip(gradient.iter()))
+                .for_each(|(c, (w, g))| *c = w - rate * g);
+            project_to_simplex(candidate, sorted);
+
+            let candidate_delta_e = error(candidate);
+            if candidate_delta_e < delta_e {
+                weights.copy_from_slice(candidate);
+                delta_e = candidate_delta_e;
+                rate *= 1.5;
+                break;
+            }
+
+            rate *= 0.5;
+            if rate < 1.0e-6 {
+                break;
+            }
+        }
+
+        if rate < 1.0e-6 {
+            break;
+        }
+    }
+
+    (delta_e.is_finite() && weights.iter().all(|w| w.is_finite()))
+        .then_some(delta_e)
+}
+
+/// Options for [`solve_recipe_sparse()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RecipeOptions {
+    /// The largest number of distinct paints a recipe may use.
+    pub max_paints: usize,
+    /// The cost of every paint used, in units of perceptual distance.
+    ///
+    /// A recipe with one more paint is only preferred if it gets closer to
+    /// the target by more than this. `0.0` picks the closest recipe with up to
+    /// [`max_paints`](RecipeOptions::max_paints) paints.
+    pub paint_penalty: f32,
+}
+
+impl Default for RecipeOptions {
+    fn default() -> Self {
+        Self {
+            max_paints: 3,
+            paint_penalty: 0.5,
+        }
+    }
+}
+
+/// Finds a recipe like [`solve_recipe()`] that prefers using few distinct
+/// paints.
+///
+/// Every subset of `paints` with up to
+/// [`max_paints`](RecipeOptions::max_paints) entries is solved for. The
+/// recipe with the lowest [`delta_e`](Recipe::delta_e) plus
+/// [`paint_penalty`](RecipeOptions::paint_penalty) times the number of paints
+/// used wins.
+///
+/// The returned weights have one entry per paint with unused paints set to
+/// `0`.
+///
+/// The number of subsets grows quickly with `max_paints`. With 24 paints and
+/// `max_paints` of `3`, 2324 recipes are solved.
+///
+/// ```
+/// # use pigment_mixing::{solve_recipe_sparse, Pigment, RecipeOptions};
+/// let paints = [
+///     Pigment::from_srgb_u8(254, 236, 0),
+///     Pigment::from_srgb_u8(25, 0, 89),
+///     Pigment::from_srgb_u8(255, 255, 255),
+///     Pigment::from_srgb_u8(0, 0, 0),
+/// ];
+/// let target = Pigment::from_mix(paints[0], paints[1], 0.5);
+///
+/// let recipe =
+///     solve_recipe_sparse(&target, &paints, &RecipeOptions::default()).unwrap();
+///
+/// assert_eq!(0.0, recipe.weights[2]);
+/// assert_eq!(0.0, recipe.weights[3]);
+/// ```
+pub fn solve_recipe_sparse(
+    target: &Pigment,
+    paints: &[Pigment],
+    options: &RecipeOptions,
+) -> Option<Recipe> {
+    let n = paints.len();
+    let mut best: Option<(f32, Recipe)> = None;
+    let mut subset_paints = Vec::with_capacity(options.max_paints);
+
+    for k in 1..=options.max_paints.min(n) {
+        // Visit all `k` element subsets in lexicographic order.
+        let mut subset: Vec<usize> = (0..k).collect();
+        loop {
+            subset_paints.clear();
+            subset_paints.extend(subset.iter().map(|&i| paints[i]));
+
+            if let Some(recipe) = solve_recipe(target, &subset_paints) {
+                let used = recipe
+                    .weights
+                    .iter()
+                    .filter(|&&w| NEGLIGIBLE_WEIGHT <= w)
+                    .count();
+                let score =
+                    recipe.delta_e + options.paint_penalty * used as f32;
+
+                if best.as_ref().is_none_or(|(best, _)| score < *best) {
+                    let mut weights = vec![0.0; n];
+                    subset
+                        .iter()
+                        .zip(&recipe.weights)
+                        .for_each(|(&i, &w)| weights[i] = w);
+
+                    best = Some((
+                        score,
+                        Recipe {
+                            weights,
+                            delta_e: recipe.delta_e,
+                        },
+                    ));
+                }
+            }
+
+            // Advance to the next subset.
+            match (0..k).rev().find(|&i| subset[i] < n - k + i) {
+                Some(i) => {
+                    subset[i] += 1;
+                    for j in i + 1..k {
+                        subset[j] = subset[j - 1] + 1;
+                    }
+                }
+                None => break,
+            }
+        }
+    }
+
+    best.map(|(_, recipe)| recipe)
+}
+
+impl Palette {
+    /// Finds weights for mixing the entries of the palette to match `target`.
+    ///
+    /// See [`solve_recipe()`] for details.
+    pub fn recipe(&self, target: &Pigment) -> Option<Recipe> {
+        solve_recipe(target, &self.pigments())
+    }
+
+    /// Finds weights for mixing few entries of the palette to match `target`.
+    ///
+    /// See [`solve_recipe_sparse()`] for details.
+    pub fn recipe_sparse(
+        &self,
+        target: &Pigment,
+        options: &RecipeOptions,
+    ) -> Option<Recipe> {
+        solve_recipe_sparse(target, &self.pigments(), options)
+    }
+
+    /// Returns the pigments of all entries.
+    pub(crate) fn pigments(&self) -> Vec<Pigment> {
+        self.iter().map(|entry| entry.pigment).collect()
+    }
+}
+
+/// Returns the color closest to `target` that can be mixed from the entries of
+/// `palette`, together with its perceptual distance to `target`.
+///
+/// The colors that can be mixed form the convex region spanned by the latents
+/// of the palette. See [`solve_recipe()`] for how the closest point is found.
+///
+/// Returns `None` if the palette is empty.
+pub fn closest_achievable(
+    target: &Pigment,
+    palette: &Palette,
+) -> Option<(Pigment, f32)> {
+    let paints = palette.pigments();
+
+    solve_recipe(target, &paints)
+        .map(|recipe| (recipe.mix(&paints), recipe.delta_e))
+}
+
+/// Returns `true` if `target` can be mixed from the entries of `palette`
+/// within `tolerance`.
+///
+/// The distance is the Euclidean distance in Oklab, scaled by `100`. Use this
+/// to e.g. gray out colors that can not be made with a set of physical
+/// paints.
+///
+/// ```
+/// # use pigment_mixing::{is_achievable, Palette, Pigment};
+/// let yellow = Pigment::from_srgb_u8(254, 236, 0);
+/// let blue = Pigment::from_srgb_u8(25, 0, 89);
+/// let palette = Palette::new()
+///     .with_entry("Yellow", yellow)
+///     .with_entry("Blue", blue);
+///
+/// assert!(is_achievable(&Pigment::from_mix(yellow, blue, 0.5), &palette, 1.0));
+/// assert!(!is_achievable(&Pigment::from_srgb_u8(255, 0, 0), &palette, 1.0));
+/// ```
+pub fn is_achievable(
+    target: &Pigment,
+    palette: &Palette,
+    tolerance: f32,
+) -> bool {
+    closest_achievable(target, palette)
+        .is_some_and(|(_, delta_e)| delta_e <= tolerance)
+}
+
+/// Mixes `paints` using `weights` in latent space.
+pub(crate) fn weighted_mix(paints: &[Pigment], weights: &[f32]) -> Pigment {
+    let mut latent = [0.0f32; PIGMENT_LEN];
+    for (&paint, weight) in paints.iter().zip(weights) {
+        let paint: [f32; PIGMENT_LEN] = paint.into();
+        latent
+            .iter_mut()
+            .zip(paint)
+            .for_each(|(l, x)| *l += weight * x);
+    }
+
+    Pigment::from(latent)
+}
+
+/// Projects `weights` onto the probability simplex, i.e. makes them
+/// non-negative and summing to `1` while moving them as little as possible.
+///
+/// `sorted` is working memory of the same length as `weights`.
+fn project_to_simplex(weights: &mut [f32], sorted: &mut [f32]) {
+    sorted.copy_from_slice(weights);
+    sorted.sort_unstable_by(|a, b| b.total_cmp(a));
+
+    let mut sum = 0.0;
+    let mut theta = 0.0;
+    for (i, &x) in sorted.iter().enumerate() {
+        sum += x;
+        let t = (sum - 1.0) / (i + 1) as f32;
+        if x - t > 0.0 {
+            theta = t;
+        }
+    }
+
+    weights.iter_mut().for_each(|w| *w = (*w - theta).max(0.0));
+}
+
+/// The weight below which a paint is left out of a [`PartsRecipe`] entirely.
+const NEGLIGIBLE_WEIGHT: f32 = 1.0e-3;
+
+/// One paint of a [`PartsRecipe`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Part {
+    /// The name of the paint.
+    pub name: String,
+    /// The number of parts. `0` for a [`trace`](Part::trace).
+    pub parts: u32,
+    /// `true` if the paint is needed in an amount too small to be measured in
+    /// whole parts.
+    pub trace: bool,
+}
+
+/// A [`Recipe`] expressed in whole parts, e.g. `3 parts Yellow : 1 part Blue :
+/// trace White`.
+///
+/// The [`Display`](core::fmt::Display) implementation prints it that way.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartsRecipe {
+    /// The paints used, in palette order.
+    pub parts: Vec<Part>,
+    /// The perceptual distance of the rounded mix to the target, with traces
+    /// left out. See [`Recipe::delta_e`].
+    pub delta_e: f32,
+}
+
+impl fmt::Display for PartsRecipe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, part) in self.parts.iter().enumerate() {
+            if 0 < i {
+                f.write_str(" : ")?;
+            }
+            match (part.trace, part.parts) {
+                (true, _) => write!(f, "trace {}", part.name)?,
+                (false, 1) => write!(f, "1 part {}", part.name)?,
+                (false, parts) => write!(f, "{} parts {}", parts, part.name)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Recipe {
+    /// Rounds the recipe to whole parts of at most `max_parts` in total.
+    ///
+    /// Every total up to `max_parts` is tried. The one whose rounded mix is
+    /// perceptually closest to `target` wins, preferring fewer parts on a
+    /// tie. Paints that round to zero parts but still carry some weight are
+    /// reported as traces.
+    ///
+    /// `palette` must be the one the recipe was solved for.
+    ///
+    /// ```
+    /// # use pigment_mixing::{Palette, Pigment};
+    /// let yellow = Pigment::from_srgb_u8(254, 236, 0);
+    /// let blue = Pigment::from_srgb_u8(25, 0, 89);
+    /// let palette = Palette::new()
+    ///     .with_entry("Yellow", yellow)
+    ///     .with_entry("Blue", blue);
+    ///
+    /// let target = Pigment::from_mix(yellow, blue, 0.25);
+    /// let recipe = palette.recipe(&target).unwrap();
+    ///
+    /// let parts = recipe.to_parts(&palette, &target, 10);
+    /// assert_eq!("3 parts Yellow : 1 part Blue", parts.to_string());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `palette` has a different length than
+    /// [`weights`](Recipe::weights).
+    pub fn to_parts(
+        &self,
+        palette: &Palette,
+        target: &Pigment,
+        max_parts: u32,
+    ) -> PartsRecipe {
+        assert_eq!(self.weights.len(), palette.len(), "one weight per paint");
+
+        let paints = palette.pigments();
+        let target_lab = linear_srgb_to_oklab((*target).into());
+
+        let (parts, delta_e) = (1..=max_parts.max(1))
+            .map(|total| {
+                let parts = round_to_parts(&self.weights, total);
+                let weights: Vec<f32> =
+                    parts.iter().map(|&p| p as f32 / total as f32).collect();
+
+                (parts, distance(weighted_mix(&paints, &weights), target_lab))
+            })
+            // `min_by()` keeps the first of equal elements.
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("there is at least one total");
+
+        // Reduce `6 : 2` to `3 : 1`.
+        let divisor = parts.iter().fold(0, |a, &b| gcd(a, b)).max(1);
+
+        PartsRecipe {
+            parts: palette
+                .iter()
+                .zip(parts.iter().zip(&self.weights))
+                .filter(|(_, (&parts, &weight))| {
+                    0 < parts || NEGLIGIBLE_WEIGHT <= weight
+                })
+                .map(|(entry, (&parts, _))| Part {
+                    name: entry.name.clone(),
+                    parts: parts / divisor,
+                    trace: 0 == parts,
+                })
+                .collect(),
+            delta_e,
+        }
+    }
+}
+
+/// Distributes `total` parts according to `weights` using the largest
+/// remainder method.
+fn round_to_parts(weights: &[f32], total: u32) -> Vec<u32> {
+    let sum: f32 = weights.iter().sum();
+    let exact: Vec<f32> = weights
+        .iter()
+        .map(|w| w / sum.max(f32::EPSILON) * total as f32)
+        .collect();
+
+    let mut parts: Vec<u32> = exact.iter().map(|x| *x as u32).collect();
+    let mut order: Vec<usize> = (0..weights.len()).collect();
+    order.sort_by(|&a, &b| {
+        (exact[b] - parts[b] as f32).total_cmp(&(exact[a] - parts[a] as f32))
+    });
+
+    let assigned: u32 = parts.iter().sum();
+    order
+        .into_iter()
+        .take(total.saturating_sub(assigned) as usize)
+        .for_each(|i| parts[i] += 1);
+
+    parts
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if 0 == b {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{metrics::delta_e_ok, solve::best_ratio, Palette, PaletteEntry};
+use alloc::{string::String, vec, vec::Vec};
+
+/// How a color dropped by [`Palette::reduce()`] is mixed from the kept
+/// paints.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MixRecipe {
+    /// The name of the dropped color.
+    pub name: String,
+    /// The index of the first paint in [`Reduction::paints`].
+    pub a: usize,
+    /// The index of the second paint in [`Reduction::paints`]. Equals `a` if
+    /// the color is matched by a single paint.
+    pub b: usize,
+    /// The ratio to mix `a` with `b`. `0.0` is pure `a`.
+    pub ratio: f32,
+    /// The perceptual distance of the mix to the dropped color as in
+    /// [`Palette::nearest()`], scaled by `100`.
+    pub delta_e: f32,
+}
+
+/// The result of [`Palette::reduce()`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Reduction {
+    /// The paints to keep, in the order of the original palette.
+    pub paints: Palette,
+    /// One recipe per dropped color, in the order of the original palette.
+    pub recipes: Vec<MixRecipe>,
+}
+
+/// The best known way to mix an entry: distance, paint, partner and ratio.
+#[derive(Clone, Copy)]
+struct Reach {
+    delta_e: f32,
+    paint: usize,
+    partner: usize,
+    ratio: f32,
+}
+
+impl Reach {
+    fn improve(&mut self, other: Reach) {
+        if other.delta_e < self.delta_e {
+            *self = other;
+        }
+    }
+}
+
+impl Palette {
+    /// Finds a small subset of the palette from which every other entry can
+    /// be mixed within `tolerance`.
+    ///
+    /// A dropped entry is considered mixable if a single kept paint or a mix
+    /// of two kept paints gets within `tolerance` of it. The distance is the
+    /// Euclidean distance in Oklab, scaled by `100`; a `tolerance` of about
+    /// `2.0` is hard to tell apart for most people.
+    ///
+    /// Finding the smallest subset is NP-hard. This uses a greedy set cover
+    /// that repeatedly keeps the paint which makes the most remaining entries
+    /// mixable. The result is usually close to the optimum but is not
+    /// guaranteed to be minimal.
+    ///
+    /// The cost is roughly quadratic in the number of entries times the
+    /// number of kept paints.
+    ///
+    /// ```
+    /// # use pigment_mixing::{Palette, Pigment};
+    /// let yellow = Pigment::from_srgb_u8(254, 236, 0);
+    /// let blue = Pigment::from_srgb_u8(25, 0, 89);
+    ///
+    /// let palette = Palette::new()
+    ///     .with_entry("Yellow", yellow)
+    ///     .with_entry("Green", Pigment::from_mix(yellow, blue, 0.4))
+    ///     .with_entry("Blue", blue);
+    ///
+    /// let reduction = palette.reduce(1.0);
+    ///
+    /// assert_eq!(2, reduction.paints.len());
+    /// assert_eq!("Green", reduction.recipes[0].name);
+    /// ```
+    pub fn reduce(&self, tolerance: f32) -> Reduction {
+        let entries = self.entries();
+        let n = entries.len();
+
+        // `reach[paint * n + entry]` is the best way to mix `entry` using
+        // `paint`, alone or together with an already kept paint.
+        let mut reach: Vec<Reach> = (0..n * n)
+            .map(|index| {
+                let (paint, entry) = (index / n, index % n);
+                Reach {
+                    delta_e: delta_e_ok(
+                        &entries[paint].pigment,
+                        &entries[entry].pigment,
+                    ),
+                    paint,
+                    partner: paint,
+                    ratio: 0.0,
+                }
+            })
+            .collect();
+
+        let mut kept = vec![false; n];
+        let mut covered: Vec<Option<Reach>> = vec![None; n];
+
+        while let Some(paint) = (0..n)
+            .filter(|&paint| !kept[paint])
+            .map(|paint| {
+                let count = (0..n)
+                    .filter(|&entry| {
+                        covered[entry].is_none()
+                            && (entry == paint
+                                || reach[paint * n + entry].delta_e
+                                    <= tolerance)
+                    })
+                    .count();
+                (paint, count)
+            })
+            .filter(|&(_, count)| 0 < count)
+            // Prefer the first of equally good paints.
+            .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+            .map(|(paint, _)| paint)
+        {
+            kept[paint] = true;
+
+            for entry in 0..n {
+                if covered[entry].is_some() {
+                    continue;
+                }
+                let best = reach[paint * n + entry];
+                if entry == paint || best.delta_e <= tolerance {
+                    covered[entry] = Some(best);
+                    continue;
+                }
+
+                // Extend the reach of the other candidates by mixing them
+                // with the new paint.
+                for candidate in (0..n).filter(|&candidate| !kept[candidate]) {
+                    let (ratio, delta_e) = best_ratio(
+                        &entries[candidate].pigment,
+                        &entries[paint].pigment,
+                        &entries[entry].pigment,
+                    );
+                    reach[candidate * n + entry].improve(Reach {
+                        delta_e,
+                        paint: candidate,
+                        partner: paint,
+                        ratio,
+                    });
+                }
+            }
+        }
+
+        // Map palette indices to indices into the kept paints.
+        let mut paint_index = vec![0; n];
+        let paints = entries
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| kept[*index])
+            .enumerate()
+            .map(|(kept_index, (index, entry))| {
+                paint_index[index] = kept_index;
+                entry.clone()
+            })
+            .collect::<Palette>();
+
+        let recipes = entries
+            .iter()
+            .zip(covered)
+            .enumerate()
+            .filter(|(index, _)| !kept[*index])
+            .map(|(_, (PaletteEntry { name, .. }, reach))| {
+                let reach = reach.expect("every entry is covered");
+                MixRecipe {
+                    name: name.clone(),
+                    a: paint_index[reach.paint],
+                    b: paint_index[reach.partner],
+                    ratio: reach.ratio,
+                    delta_e: reach.delta_e,
+                }
+            })
+            .collect();
+
+        Reduction { paints, recipes }
+    }
+}
@@ -0,0 +1,120 @@
+//! Conversions from and to the pixel types of the
+//! [`rgb`](https://docs.rs/rgb/) crate.
+use crate::Pigment;
+use rgb::{RGB16, RGB8, RGBA8};
+
+/// Converts a `u16` component encoded sRGB (gamma 2.2) value.
+#[inline]
+fn to_u16(value: f32) -> u16 {
+    (value.clamp(0.0, 1.0) * u16::MAX as f32 + 0.5) as _
+}
+
+/// Convert an encoded sRGB (gamma 2.2) [`RGB8`] to a `Pigment`.
+impl From<RGB8> for Pigment {
+    #[inline]
+    fn from(rgb: RGB8) -> Self {
+        Pigment::from_srgb_u8(rgb.r, rgb.g, rgb.b)
+    }
+}
+
+/// Convert an encoded sRGB (gamma 2.2) [`RGB16`] to a `Pigment`.
+impl From<RGB16> for Pigment {
+    #[inline]
+    fn from(rgb: RGB16) -> Self {
+        Pigment::from_srgb_u16(rgb.r, rgb.g, rgb.b)
+    }
+}
+
+/// Convert an encoded sRGB (gamma 2.2) [`RGBA8`] to a `Pigment`.
+///
+/// The alpha channel is ignored. See
+/// [`PigmentAlpha`](crate::PigmentAlpha) to keep it.
+impl From<RGBA8> for Pigment {
+    #[inline]
+    fn from(rgba: RGBA8) -> Self {
+        Pigment::from_srgb_u8(rgba.r, rgba.g, rgba.b)
+    }
+}
+
+/// Convert a `Pigment` to an encoded sRGB (gamma 2.2) [`RGB8`].
+impl From<Pigment> for RGB8 {
+    #[inline]
+    fn from(pigment: Pigment) -> Self {
+        let [r, g, b]: [u8; 3] = pigment.into();
+        RGB8::new(r, g, b)
+    }
+}
+
+/// Convert a `Pigment` to an encoded sRGB (gamma 2.2) [`RGB16`].
+impl From<Pigment> for RGB16 {
+    #[inline]
+    fn from(pigment: Pigment) -> Self {
+        let [r, g, b] = pigment.to_srgb();
+        RGB16::new(to_u16(r), to_u16(g), to_u16(b))
+    }
+}
+
+/// Convert a `Pigment` to an opaque encoded sRGB (gamma 2.2) [`RGBA8`].
+impl From<Pigment> for RGBA8 {
+    #[inline]
+    fn from(pigment: Pigment) -> Self {
+        let [r, g, b]: [u8; 3] = pigment.into();
+        RGBA8::new(r, g, b, u8::MAX)
+    }
+}
+
+/// Mixes two encoded sRGB (gamma 2.2) [`RGB8`] colors.
+///
+/// See [`mix_srgb_u8()`](crate::mix_srgb_u8).
+#[inline]
+pub fn mix_rgb8(a: RGB8, b: RGB8, ratio: f32) -> RGB8 {
+    let [r, g, b] =
+        crate::mix_srgb_u8(&[a.r, a.g, a.b], &[b.r, b.g, b.b], ratio);
+    RGB8::new(r, g, b)
+}
+
+#[cfg(feature = "canvas")]
+impl crate::PigmentBuffer {
+    /// Constructs a buffer from encoded sRGB (gamma 2.2) [`RGB8`] pixels in
+    /// row-major order.
+    ///
+    /// # Panics
+    ///
+    /// If `pixels` does not hold exactly `width` × `height` pixels.
+    pub fn from_rgb8(width: usize, height: usize, pixels: &[RGB8]) -> Self {
+        assert_eq!(width * height, pixels.len(), "input has the wrong size");
+
+        let mut buffer = Self::new(width, height, Pigment::from([0.0; 3]));
+        buffer
+            .pixels_mut()
+            .iter_mut()
+            .zip(pixels)
+            .for_each(|(pigment, &rgb)| *pigment = rgb.into());
+
+        buffer
+    }
+
+    /// Writes the buffer as encoded sRGB (gamma 2.2) [`RGB8`] pixels in
+    /// row-major order into `out`.
+    ///
+    /// # Panics
+    ///
+    /// If `out` does not hold exactly [`width()`](Self::width) ×
+    /// [`height()`](Self::height) pixels.
+    pub fn to_rgb8_into(&self, out: &mut [RGB8]) {
+        assert_eq!(self.pixels().len(), out.len(), "output has the wrong size");
+
+        out.iter_mut()
+            .zip(self.pixels())
+            .for_each(|(out, &pigment)| *out = pigment.into());
+    }
+
+    /// Returns the buffer as encoded sRGB (gamma 2.2) [`RGB8`] pixels in
+    /// row-major order.
+    pub fn to_rgb8(&self) -> alloc::vec::Vec<RGB8> {
+        self.pixels()
+            .iter()
+            .map(|&pigment| pigment.into())
+            .collect()
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{Brush, Canvas, Pigment};
+use num_traits::Float;
+
+/// A smudge tool that smears paint along a stroke.
+///
+/// The brush carries a reservoir of paint. At every dab it deposits some of
+/// that paint and picks up some of the paint under it, mixing both in latent
+/// space. Only wet paint is picked up. This is where pigment mixing differs most from RGB: smearing
+/// yellow into blue gives green, not gray.
+///
+/// ```
+/// # use pigment_mixing::{Canvas, Pigment, RoundBrush, Smudge, Substrate};
+/// let mut canvas = Canvas::new(128, 64, Substrate::default());
+/// let brush = RoundBrush::new(8.0, 0.3);
+///
+/// let yellow = Pigment::from_srgb_u8(254, 236, 0);
+/// let blue = Pigment::from_srgb_u8(25, 0, 89);
+///
+/// for y in 0..64 {
+///     canvas.stamp(&brush, (32.0, y as f32), &yellow, 1.0);
+///     canvas.stamp(&brush, (96.0, y as f32), &blue, 1.0);
+/// }
+///
+/// Smudge::new(0.3, 0.8).stroke(
+///     &mut canvas,
+///     &brush,
+///     &[(16.0, 32.0), (112.0, 32.0)],
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Smudge {
+    /// How much of the paint under the brush is picked up into the reservoir
+    /// at each dab, from `0.0` to `1.0`.
+    pub pickup: f32,
+    /// How much of the reservoir is deposited at each dab, from `0.0` to
+    /// `1.0`.
+    pub strength: f32,
+    /// The paint on the brush and its thickness.
+    reservoir: Option<(Pigment, f32)>,
+}
+
+impl Smudge {
+    /// Constructs a `Smudge` tool with an empty reservoir.
+    #[inline]
+    pub fn new(pickup: f32, strength: f32) -> Self {
+        Self {
+            pickup: pickup.clamp(0.0, 1.0),
+            strength: strength.clamp(0.0, 1.0),
+            reservoir: None,
+        }
+    }
+
+    /// Returns the paint on the brush, if any.
+    #[inline]
+    pub fn reservoir(&self) -> Option<Pigment> {
+        self.reservoir.map(|(pigment, _)| pigment)
+    }
+
+    /// Empties the reservoir, e.g. at the start of a new stroke.
+    #[inline]
+    pub fn clean(&mut self) {
+        self.reservoir = None;
+    }
+
+    /// Smudges along the polyline `points` with dabs spaced a quarter of the
+    /// brush radius apart.
+    ///
+    /// The reservoir is emptied first.
+    pub fn stroke(
+        &mut self,
+        canvas: &mut Canvas,
+        brush: &(impl Brush + ?Sized),
+        points: &[(f32, f32)],
+    ) {
+        self.clean();
+
+        let spacing = (0.25 * brush.radius()).max(1.0);
+        let mut previous: Option<(f32, f32)> = None;
+
+        for &point in points {
+            match previous {
+                None => self.dab(canvas, brush, point),
+                Some(start) => {
+                    let (dx, dy) = (point.0 - start.0, point.1 - start.1);
+                    let steps = (Float::sqrt(dx * dx + dy * dy) / spacing)
+                        .max(1.0) as usize;
+
+                    for step in 1..=steps {
+                        let t = step as f32 / steps as f32;
+                        self.dab(
+                            canvas,
+                            brush,
+                            (start.0 + dx * t, start.1 + dy * t),
+                        );
+                    }
+                }
+            }
+            previous = Some(point);
+        }
+    }
+
+    /// Smudges with a single dab of `brush` centered at `position`.
+    pub fn dab(
+        &mut self,
+        canvas: &mut Canvas,
+        brush: &(impl Brush + ?Sized),
+        position: (f32, f32),
+    ) {
+        let region = canvas.region_around(position, brush.radius());
+        let width = canvas.width();
+        let (paint, thickness, wetness) = canvas.state_mut();
+
+        let falloff = |x: usize, y: usize| {
+            let (dx, dy) =
+                (x as f32 + 0.5 - position.0, y as f32 + 0.5 - position.1);
+            brush.footprint(dx, dy)
+        };
+
+        // The average of the paint under the brush, weighted by the amount
+        // of wet paint, and its average thickness.
+        let mut sum: Option<Pigment> = None;
+        let (mut paint_weight, mut footprint_weight, mut sum_thickness) =
+            (0.0, 0.0, 0.0);
+        paint.for_each_in(region, |x, y, pigment| {
+            let strength = falloff(x, y);
+            let t = thickness[y * width + x];
+            footprint_weight += strength;
+            sum_thickness += strength * t;
+
+            let weight = strength * t * wetness[y * width + x];
+            if 0.0 < weight {
+                sum = Some(match sum {
+                    Some(sum) => sum + weight * *pigment,
+                    None => weight * *pigment,
+                });
+                paint_weight += weight;
+            }
+        });
+        let under = sum.map(|sum| {
+            ((1.0 / paint_weight) * sum, sum_thickness / footprint_weight)
+        });
+
+        let (reservoir, reservoir_thickness) = match (self.reservoir, under) {
+            (Some(reservoir), _) => reservoir,
+            (None, Some(under)) => under,
+            // Nothing to smudge yet.
+            (None, None) => return,
+        };
+
+        paint.for_each_in(region, |x, y, pigment| {
+            let amount = self.strength * falloff(x, y);
+            if amount <= 0.0 {
+                return;
+            }
+
+            let thickness = &mut thickness[y * width + x];
+            if 0.0 < *thickness {
+                pigment.mix(reservoir, amount);
+            } else {
+                *pigment = reservoir;
+            }
+            *thickness += (reservoir_thickness - *thickness) * amount;
+        });
+
+        self.reservoir = Some(match under {
+            Some((under, under_thickness)) => (
+                Pigment::from_mix(reservoir, under, self.pickup),
+                reservoir_thickness
+                    + (under_thickness - reservoir_thickness) * self.pickup,
+            ),
+            None => (reservoir, reservoir_thickness),
+        });
+    }
+}
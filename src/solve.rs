@@ -0,0 +1,97 @@
+use crate::{metrics::linear_srgb_to_oklab, Pigment};
+use num_traits::Float;
+
+/// The number of evenly spaced ratios tried before refining.
+const COARSE_STEPS: usize = 16;
+/// The number of golden-section iterations used to refine the coarse ratio.
+const REFINE_STEPS: usize = 16;
+
+/// Returns the Oklab distance, scaled by `100`, of `pigment` to `target`.
+pub(crate) fn distance(pigment: Pigment, target: [f32; 3]) -> f32 {
+    let lab = linear_srgb_to_oklab(pigment.into());
+    let (dl, da, db) =
+        (lab[0] - target[0], lab[1] - target[1], lab[2] - target[2]);
+
+    100.0 * Float::sqrt(dl * dl + da * da + db * db)
+}
+
+/// Finds the ratio for mixing `a` with `b` that gets perceptually closest to
+/// `target`.
+///
+/// Returns the ratio, to be used with [`Pigment::from_mix()`], and the
+/// remaining distance. The distance is the Euclidean distance in
+/// [Oklab](https://bottosson.github.io/posts/oklab/), scaled by `100`.
+///
+/// Returns `None` if any of the pigments holds non-finite values.
+///
+/// ```
+/// # use pigment_mixing::{solve_ratio, Pigment};
+/// let yellow = Pigment::from_srgb_u8(254, 236, 0);
+/// let blue = Pigment::from_srgb_u8(25, 0, 89);
+/// let green = Pigment::from_mix(yellow, blue, 0.3);
+///
+/// let (ratio, delta_e) = solve_ratio(&yellow, &blue, &green).unwrap();
+///
+/// assert!((ratio - 0.3).abs() < 0.01);
+/// assert!(delta_e < 0.1);
+/// ```
+pub fn solve_ratio(
+    a: &Pigment,
+    b: &Pigment,
+    target: &Pigment,
+) -> Option<(f32, f32)> {
+    let (ratio, delta_e) = best_ratio(a, b, target);
+
+    (ratio.is_finite() && delta_e.is_finite()).then_some((ratio, delta_e))
+}
+
+/// Does the work of [`solve_ratio()`] without checking the result.
+///
+/// The error along the mixing line can have more than one minimum. A coarse
+/// search picks the best region which is then refined with a golden-section
+/// search.
+pub(crate) fn best_ratio(
+    a: &Pigment,
+    b: &Pigment,
+    target: &Pigment,
+) -> (f32, f32) {
+    let target = linear_srgb_to_oklab((*target).into());
+    let error = |ratio: f32| distance(Pigment::from_mix(*a, *b, ratio), target);
+
+    let step = 1.0 / COARSE_STEPS as f32;
+    let (coarse, _) = (0..=COARSE_STEPS)
+        .map(|i| {
+            let ratio = i as f32 * step;
+            (ratio, error(ratio))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .expect("the coarse search is never empty");
+
+    let inverse_phi = 0.618_034;
+    let (mut low, mut high) =
+        ((coarse - step).max(0.0), (coarse + step).min(1.0));
+    let mut x0 = high - inverse_phi * (high - low);
+    let mut x1 = low + inverse_phi * (high - low);
+    let (mut e0, mut e1) = (error(x0), error(x1));
+
+    for _ in 0..REFINE_STEPS {
+        if e0 < e1 {
+            high = x1;
+            (x1, e1) = (x0, e0);
+            x0 = high - inverse_phi * (high - low);
+            e0 = error(x0);
+        } else {
+            low = x0;
+            (x0, e0) = (x1, e1);
+            x1 = low + inverse_phi * (high - low);
+            e1 = error(x1);
+        }
+    }
+
+    // The bracket ends are not evaluated by the golden-section search, so
+    // make sure pure paints are found.
+    [(coarse, error(coarse)), (x0, e0), (x1, e1)]
+        .into_iter()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .expect("candidates are never empty")
+}
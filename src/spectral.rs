@@ -0,0 +1,155 @@
+use num_traits::Float;
+
+/// The number of samples of a [`Spectrum`].
+pub const SPECTRUM_LEN: usize = 36;
+
+/// The wavelength of the first sample of a [`Spectrum`] in nanometers.
+pub const SPECTRUM_START: f32 = 380.0;
+
+/// The distance between the samples of a [`Spectrum`] in nanometers.
+pub const SPECTRUM_STEP: f32 = 10.0;
+
+/// A spectral quantity sampled from 380 nm to 730 nm in 10 nm steps.
+///
+/// This is the sampling most spectrophotometers use.
+pub type Spectrum = [f32; SPECTRUM_LEN];
+
+/// The CIE 1931 2° standard observer color matching functions, x̄, ȳ and z̄.
+const CIE_1931: [[f32; 3]; SPECTRUM_LEN] = [
+    [0.001_368, 0.000_039, 0.006_450],
+    [0.004_243, 0.000_120, 0.020_050],
+    [0.014_310, 0.000_396, 0.067_850],
+    [0.043_510, 0.001_210, 0.207_400],
+    [0.134_380, 0.004_000, 0.645_600],
+    [0.283_900, 0.011_600, 1.385_6],
+    [0.348_280, 0.023_000, 1.747_06],
+    [0.336_200, 0.038_000, 1.772_11],
+    [0.290_800, 0.060_000, 1.669_2],
+    [0.195_360, 0.090_980, 1.287_64],
+    [0.095_640, 0.139_020, 0.812_950],
+    [0.032_010, 0.208_020, 0.465_180],
+    [0.004_900, 0.323_000, 0.272_000],
+    [0.009_300, 0.503_000, 0.158_200],
+    [0.063_270, 0.710_000, 0.078_250],
+    [0.165_500, 0.862_000, 0.042_160],
+    [0.290_400, 0.954_000, 0.020_300],
+    [0.433_450, 0.994_950, 0.008_750],
+    [0.594_500, 0.995_000, 0.003_900],
+    [0.762_100, 0.952_000, 0.002_100],
+    [0.916_300, 0.870_000, 0.001_650],
+    [1.026_3, 0.757_000, 0.001_100],
+    [1.062_2, 0.631_000, 0.000_800],
+    [1.002_6, 0.503_000, 0.000_340],
+    [0.854_450, 0.381_000, 0.000_190],
+    [0.642_400, 0.265_000, 0.000_050],
+    [0.447_900, 0.175_000, 0.000_020],
+    [0.283_500, 0.107_000, 0.0],
+    [0.164_900, 0.061_000, 0.0],
+    [0.087_400, 0.032_000, 0.0],
+    [0.046_770, 0.017_000, 0.0],
+    [0.022_700, 0.008_210, 0.0],
+    [0.011_359, 0.004_102, 0.0],
+    [0.005_790, 0.002_091, 0.0],
+    [0.002_899, 0.001_047, 0.0],
+    [0.001_440, 0.000_520, 0.0],
+];
+
+/// The relative spectral power distribution of CIE standard illuminant D65.
+const D65: Spectrum = [
+    49.975_5, 54.648_2, 82.754_9, 91.486, 93.431_8, 86.682_3, 104.865, 117.008,
+    117.812, 114.861, 115.923, 108.811, 109.354, 107.802, 104.79, 107.689,
+    104.405, 104.046, 100.0, 96.334_2, 95.788, 88.685_6, 90.006_2, 89.599_1,
+    87.698_7, 83.288_6, 83.699_2, 80.026_8, 80.214_6, 82.277_8, 78.284_2,
+    69.721_3, 71.609_1, 74.349, 61.604, 69.885_6,
+];
+
+/// Integrates `reflectance` under D65 with the CIE 1931 observer.
+fn reflectance_to_xyz(reflectance: &Spectrum) -> [f32; 3] {
+    let mut xyz = [0.0f32; 3];
+    for ((r, cmf), d65) in reflectance.iter().zip(&CIE_1931).zip(&D65) {
+        xyz.iter_mut()
+            .zip(cmf)
+            .for_each(|(xyz, cmf)| *xyz += r * d65 * cmf);
+    }
+
+    xyz
+}
+
+/// Converts XYZ (D65) to linear sRGB.
+fn xyz_to_linear_srgb(xyz: [f32; 3]) -> [f32; 3] {
+    let [x, y, z] = xyz;
+
+    [
+        3.240_97 * x - 1.537_383_2 * y - 0.498_610_76 * z,
+        -0.969_243_6 * x + 1.875_967_5 * y + 0.041_555_06 * z,
+        0.055_630_08 * x - 0.203_976_96 * y + 1.056_971_5 * z,
+    ]
+}
+
+/// Converts a reflectance spectrum to a linear sRGB color as seen under D65
+/// by the CIE 1931 2° standard observer.
+///
+/// The result is normalized so a perfect reflector (a reflectance of `1`
+/// everywhere) maps to white.
+pub fn reflectance_to_linear_srgb(reflectance: &Spectrum) -> [f32; 3] {
+    let rgb = xyz_to_linear_srgb(reflectance_to_xyz(reflectance));
+    let white = xyz_to_linear_srgb(reflectance_to_xyz(&[1.0; SPECTRUM_LEN]));
+
+    [rgb[0] / white[0], rgb[1] / white[1], rgb[2] / white[2]]
+}
+
+/// Resamples a spectrum to the sampling of [`Spectrum`].
+///
+/// `samples` start at `start` nanometers and are `step` nanometers apart. Use
+/// this to bring measurements with a different sampling, e.g. 20 nm steps
+/// from 400 nm to 700 nm, into the range used by this crate.
+///
+/// Values between samples are interpolated linearly. Outside the measured
+/// range the first or last sample is repeated.
+///
+/// Returns a spectrum of zeros if `samples` is empty.
+///
+/// ```
+/// # use pigment_mixing::resample_spectrum;
+/// let spectrum = resample_spectrum(400.0, 20.0, &[0.1, 0.3, 0.5]);
+///
+/// assert_eq!(0.1, spectrum[0]); // 380 nm
+/// assert!((spectrum[3] - 0.2).abs() < 1.0e-6); // 410 nm
+/// assert_eq!(0.5, spectrum[35]); // 730 nm
+/// ```
+pub fn resample_spectrum(start: f32, step: f32, samples: &[f32]) -> Spectrum {
+    let mut spectrum = [0.0; SPECTRUM_LEN];
+    let last = match samples.len().checked_sub(1) {
+        Some(last) => last,
+        None => return spectrum,
+    };
+
+    spectrum.iter_mut().enumerate().for_each(|(i, value)| {
+        let wavelength = SPECTRUM_START + i as f32 * SPECTRUM_STEP;
+        let position = if 0.0 < step {
+            ((wavelength - start) / step).clamp(0.0, last as f32)
+        } else {
+            0.0
+        };
+
+        let index = (position as usize).min(last);
+        let next = (index + 1).min(last);
+        let t = position - index as f32;
+
+        *value = samples[index] + (samples[next] - samples[index]) * t;
+    });
+
+    spectrum
+}
+
+/// Returns the reflectance of an opaque layer with the absorption `k` and
+/// scattering `s` coefficients after Kubelka–Munk.
+#[inline]
+pub(crate) fn km_reflectance(k: f32, s: f32) -> f32 {
+    if s <= 0.0 {
+        return 0.0;
+    }
+    let ks = k / s;
+
+    1.0 + ks - Float::sqrt(ks * ks + 2.0 * ks)
+}
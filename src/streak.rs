@@ -0,0 +1,131 @@
+use crate::{noise::ValueNoise, Pigment};
+
+/// How much the noise is stretched along the streak direction.
+const STREAK_STRETCH: f32 = 8.0;
+
+/// Mixes `a` with `b` only partially, like two paints pulled together with a
+/// palette knife or brushed wet into wet.
+///
+/// Returns a function that maps a position to the pigment found there. Streaks
+/// run along the x axis and are about one unit wide. Scale positions to
+/// change their size.
+///
+/// `ratio` is the overall amount of `b`, as in [`Pigment::from_mix()`].
+/// `mixedness` controls how far mixing has progressed: `1.0` is a uniform
+/// mix everywhere, `0.0` leaves bands of unmixed `a` and `b`, with `b`
+/// covering about `ratio` of the area. `seed` selects the noise pattern.
+///
+/// ```
+/// # use pigment_mixing::{streak, Pigment};
+/// let yellow = Pigment::from_srgb_u8(254, 236, 0);
+/// let blue = Pigment::from_srgb_u8(25, 0, 89);
+///
+/// let marbled = streak(&yellow, &blue, 0.5, 0.3, 42);
+///
+/// let width = 64;
+/// let pixels: Vec<[u8; 3]> = (0..width * width)
+///     .map(|i| {
+///         let (x, y) = ((i % width) as f32, (i / width) as f32);
+///         marbled(x / 16.0, y / 16.0).into()
+///     })
+///     .collect();
+/// ```
+pub fn streak(
+    a: &Pigment,
+    b: &Pigment,
+    ratio: f32,
+    mixedness: f32,
+    seed: u64,
+) -> impl Fn(f32, f32) -> Pigment {
+    let (a, b) = (*a, *b);
+    let ratio = ratio.clamp(0.0, 1.0);
+    let mixedness = mixedness.clamp(0.0, 1.0);
+    let noise = ValueNoise::new(seed);
+
+    move |x, y| {
+        let n = noise.uniform(x / STREAK_STRETCH, y);
+
+        // Unmixed paint: a soft threshold so `b` covers about `ratio` of the
+        // area. The softness grows as mixing progresses.
+        let softness = 0.02 + 0.5 * mixedness;
+        let t = ((ratio - n) / softness + 0.5).clamp(0.0, 1.0);
+        let unmixed = t * t * (3.0 - 2.0 * t);
+
+        Pigment::from_mix(a, b, unmixed + (ratio - unmixed) * mixedness)
+    }
+}
+
+/// Fills `out` with samples of `a` and `b` mixed only partially, like two
+/// paints pulled together with a palette knife.
+///
+/// Unlike [`streak()`], which maps positions to pigments, this returns a small
+/// set of pigments along the mixing curve for a renderer to scatter spatially,
+/// e.g. one per bristle of a brush or per particle of a stroke. The samples are
+/// ordered from mostly `a` to mostly `b`.
+///
+/// `ratio`, `mixedness` and `seed` work as in [`streak()`]: with a
+/// `mixedness` of `1.0` every sample is the uniform mix, with `0.0` about
+/// `ratio` of the samples are unmixed `b` and the rest unmixed `a`. In
+/// between, samples are spread around the uniform mix with some noise.
+///
+/// ```
+/// # use pigment_mixing::{palette_knife_into, Pigment};
+/// let yellow = Pigment::from_srgb_u8(254, 236, 0);
+/// let blue = Pigment::from_srgb_u8(25, 0, 89);
+///
+/// let mut samples = [yellow; 5];
+/// palette_knife_into(&yellow, &blue, 0.5, 0.4, 42, &mut samples);
+/// ```
+pub fn palette_knife_into(
+    a: &Pigment,
+    b: &Pigment,
+    ratio: f32,
+    mixedness: f32,
+    seed: u64,
+    out: &mut [Pigment],
+) {
+    let ratio = ratio.clamp(0.0, 1.0);
+    let mixedness = mixedness.clamp(0.0, 1.0);
+    let noise = ValueNoise::new(seed);
+    let n = out.len();
+
+    let softness = 0.02 + 0.5 * mixedness;
+    out.iter_mut().enumerate().for_each(|(i, pigment)| {
+        // The quantile this sample stands for, counted from the `b` end so
+        // samples run from mostly `a` to mostly `b`.
+        let quantile = 1.0 - (i as f32 + 0.5) / n as f32;
+        let t = ((ratio - quantile) / softness + 0.5).clamp(0.0, 1.0);
+        let unmixed = t * t * (3.0 - 2.0 * t);
+
+        // Jitter partially mixed samples. Unmixed and fully mixed paint is
+        // left alone.
+        let jitter = (noise.sample(i as f32, 0.0) - 0.5)
+            * 0.5
+            * mixedness
+            * (1.0 - mixedness);
+
+        *pigment = Pigment::from_mix(
+            *a,
+            *b,
+            (unmixed + (ratio - unmixed) * mixedness + jitter).clamp(0.0, 1.0),
+        );
+    });
+}
+
+/// Returns `count` samples of `a` and `b` mixed only partially.
+///
+/// See [`palette_knife_into()`] for details.
+#[cfg(feature = "alloc")]
+pub fn palette_knife(
+    a: &Pigment,
+    b: &Pigment,
+    ratio: f32,
+    mixedness: f32,
+    seed: u64,
+    count: usize,
+) -> alloc::vec::Vec<Pigment> {
+    let mut out = alloc::vec![*a; count];
+    palette_knife_into(a, b, ratio, mixedness, seed, &mut out);
+
+    out
+}
@@ -0,0 +1,59 @@
+use crate::{glaze, Pigment};
+
+/// The surface paint is applied to, e.g. paper or a primed canvas.
+///
+/// Transparent and dilute paint lets the substrate show through. Resolving
+/// paint against its substrate is what makes a thin watercolor wash look
+/// light on white paper and dark on black.
+///
+/// ```
+/// # use pigment_mixing::{Pigment, Substrate};
+/// let ultramarine = Pigment::from_srgb_u8(25, 0, 89);
+///
+/// let wash = Substrate::default().resolve(&ultramarine, 0.2);
+/// let masstone = Substrate::default().resolve(&ultramarine, 50.0);
+///
+/// let wash: [u8; 3] = wash.into();
+/// let masstone: [u8; 3] = masstone.into();
+/// assert!(masstone[2] < wash[2]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Substrate {
+    /// The color of the bare substrate.
+    pub pigment: Pigment,
+}
+
+impl Substrate {
+    /// Constructs a `Substrate` of the given color.
+    #[inline]
+    pub fn new(pigment: Pigment) -> Self {
+        Self { pigment }
+    }
+
+    /// Constructs a neutral `Substrate` with the given encoded sRGB (gamma
+    /// 2.2) `brightness` from `0.0` to `1.0`.
+    #[inline]
+    pub fn from_brightness(brightness: f32) -> Self {
+        let brightness = brightness.clamp(0.0, 1.0);
+
+        Self::new(Pigment::from_srgb(brightness, brightness, brightness))
+    }
+
+    /// Returns the color of `paint` applied to the substrate in a layer of
+    /// the given `thickness`.
+    ///
+    /// The layer is modelled with [`glaze()`]; see there for the meaning of
+    /// `thickness`. `0.0` is the bare substrate, large values approach the
+    /// masstone of `paint`.
+    #[inline]
+    pub fn resolve(&self, paint: &Pigment, thickness: f32) -> Pigment {
+        glaze(&self.pigment, paint, thickness)
+    }
+}
+
+/// A slightly warm watercolor paper white.
+impl Default for Substrate {
+    fn default() -> Self {
+        Self::new(Pigment::from_srgb_u8(250, 248, 240))
+    }
+}
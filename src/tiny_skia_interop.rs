@@ -0,0 +1,42 @@
+//! Mixing of [tiny-skia](https://docs.rs/tiny-skia/)'s `u8` color types.
+use crate::Pigment;
+use tiny_skia::{ColorU8, PremultipliedColorU8};
+
+/// Mixes two straight alpha [`ColorU8`]s as pigments.
+///
+/// Each color contributes in proportion to its opacity, so a fully
+/// transparent color, which has no color, does not darken the result. Alpha
+/// is interpolated linearly.
+pub fn mix_color_u8(a: ColorU8, b: ColorU8, ratio: f32) -> ColorU8 {
+    let ratio = ratio.clamp(0.0, 1.0);
+    let (alpha_a, alpha_b) = (a.alpha() as f32, b.alpha() as f32);
+
+    let [r, g, b]: [u8; 3] = Pigment::mix_amounts(
+        Pigment::from_srgb_u8(a.red(), a.green(), a.blue()),
+        (1.0 - ratio) * alpha_a,
+        Pigment::from_srgb_u8(b.red(), b.green(), b.blue()),
+        ratio * alpha_b,
+    )
+    .into();
+
+    ColorU8::from_rgba(
+        r,
+        g,
+        b,
+        (alpha_a + (alpha_b - alpha_a) * ratio + 0.5) as u8,
+    )
+}
+
+/// Mixes two [`PremultipliedColorU8`]s, the pixel format of a
+/// `tiny_skia::Pixmap`, as pigments.
+///
+/// The colors are unmultiplied before and premultiplied after mixing. See
+/// [`mix_color_u8()`].
+#[inline]
+pub fn mix_premultiplied_color_u8(
+    a: PremultipliedColorU8,
+    b: PremultipliedColorU8,
+    ratio: f32,
+) -> PremultipliedColorU8 {
+    mix_color_u8(a.demultiply(), b.demultiply(), ratio).premultiply()
+}
@@ -0,0 +1,165 @@
+//! Tonemapping of scene-referred (HDR) colors around the latent conversion.
+//!
+//! The latent space only covers linear sRGB from `0.0` to `1.0`. Brighter
+//! colors are clipped on conversion, so mixing, say, a light source two stops
+//! over white with anything gives the same result as mixing white.
+//!
+//! A [`ToneMap`] compresses the input into `0.0..=1.0` before the conversion
+//! and expands the mix afterwards. Mixes of HDR colors then stay HDR and keep
+//! their relative brightness.
+use crate::Pigment;
+use num_traits::Float;
+
+/// The largest tonemapped value that is expanded. Keeps the inverses finite.
+const MAX_TONEMAPPED: f32 = 0.999;
+
+/// A tonemapping operator and its inverse, applied per linear sRGB channel.
+///
+/// Implemented for pairs of closures `(tonemap, inverse)`:
+///
+/// ```
+/// # use pigment_mixing::{Pigment, ToneMap};
+/// let gamma = (
+///     |rgb: [f32; 3]| rgb.map(|c| c / (c + 0.5)),
+///     |rgb: [f32; 3]| rgb.map(|c| 0.5 * c / (1.0 - c).max(1.0e-3)),
+/// );
+///
+/// let light = Pigment::from_linear_hdr([3.0, 3.0, 2.0], &gamma);
+/// ```
+pub trait ToneMap {
+    /// Maps scene-referred linear sRGB to `0.0..=1.0`.
+    fn tonemap(&self, rgb: [f32; 3]) -> [f32; 3];
+
+    /// Inverts [`tonemap()`](ToneMap::tonemap).
+    fn inverse(&self, rgb: [f32; 3]) -> [f32; 3];
+}
+
+impl<F, G> ToneMap for (F, G)
+where
+    F: Fn([f32; 3]) -> [f32; 3],
+    G: Fn([f32; 3]) -> [f32; 3],
+{
+    #[inline]
+    fn tonemap(&self, rgb: [f32; 3]) -> [f32; 3] {
+        (self.0)(rgb)
+    }
+
+    #[inline]
+    fn inverse(&self, rgb: [f32; 3]) -> [f32; 3] {
+        (self.1)(rgb)
+    }
+}
+
+/// The extended Reinhard operator, `x·(1 + x / white²) / (1 + x)`, which maps
+/// `white` to `1.0`.
+///
+/// With `white` at [`f32::INFINITY`] this is the basic `x / (1 + x)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Reinhard {
+    /// The smallest input mapped to `1.0`.
+    pub white: f32,
+}
+
+impl Default for Reinhard {
+    fn default() -> Self {
+        Self {
+            white: f32::INFINITY,
+        }
+    }
+}
+
+impl ToneMap for Reinhard {
+    #[inline]
+    fn tonemap(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let w2 = self.white * self.white;
+        rgb.map(|x| {
+            let x = x.max(0.0);
+            (x * (1.0 + x / w2) / (1.0 + x)).min(1.0)
+        })
+    }
+
+    #[inline]
+    fn inverse(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let w2 = self.white * self.white;
+        rgb.map(|y| {
+            let y = y.clamp(0.0, MAX_TONEMAPPED);
+            if w2.is_infinite() {
+                y / (1.0 - y)
+            } else {
+                // Solve `x² / w² + (1 - y)·x - y = 0` for `x ≥ 0`.
+                let b = 1.0 - y;
+                0.5 * w2 * (Float::sqrt(b * b + 4.0 * y / w2) - b)
+            }
+        })
+    }
+}
+
+/// Krzysztof Narkowicz's fit of the ACES filmic curve.
+///
+/// The curve has a toe and a shoulder and reaches `1.0` at about `7.2`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AcesFit;
+
+impl ToneMap for AcesFit {
+    #[inline]
+    fn tonemap(&self, rgb: [f32; 3]) -> [f32; 3] {
+        rgb.map(|x| {
+            let x = x.max(0.0);
+            (x * (2.51 * x + 0.03) / (x * (2.43 * x + 0.59) + 0.14)).min(1.0)
+        })
+    }
+
+    #[inline]
+    fn inverse(&self, rgb: [f32; 3]) -> [f32; 3] {
+        rgb.map(|y| {
+            let y = y.clamp(0.0, MAX_TONEMAPPED);
+            // Solve `(2.43·y - 2.51)·x² + (0.59·y - 0.03)·x + 0.14·y = 0`
+            // for `x ≥ 0`.
+            let (a, b, c) = (2.43 * y - 2.51, 0.59 * y - 0.03, 0.14 * y);
+            (-b - Float::sqrt(b * b - 4.0 * a * c)) / (2.0 * a)
+        })
+    }
+}
+
+impl Pigment {
+    /// Constructs a `Pigment` from a scene-referred linear sRGB color,
+    /// compressing it with `tone_map` first.
+    ///
+    /// Convert back with [`to_linear_hdr()`](Pigment::to_linear_hdr) using
+    /// the same `tone_map`. Colors up to about two stops over white survive
+    /// the round trip within a few percent; the closer a channel gets to the
+    /// tonemapped maximum of `1.0`, the more precision is lost.
+    ///
+    /// Mixing happens on the tonemapped colors, so a mix is not the linear
+    /// mix of the HDR colors. Like any pigment mix it may shift hue, e.g. a
+    /// white mixed with a gray turns slightly blue. What the round trip adds
+    /// is that the expanded mix is not limited to `0.0..=1.0`.
+    ///
+    /// ```
+    /// # use pigment_mixing::{Pigment, Reinhard};
+    /// let tone_map = Reinhard::default();
+    ///
+    /// // Two stops over white.
+    /// let light = Pigment::from_linear_hdr([4.0, 4.0, 4.0], &tone_map);
+    /// let rgb = light.to_linear_hdr(&tone_map);
+    /// assert!(rgb.iter().all(|c| (c - 4.0).abs() < 0.05 * 4.0));
+    ///
+    /// // A little of a dark color mixed in need not clip to white.
+    /// let dark = Pigment::from_linear_hdr([0.05, 0.05, 0.05], &tone_map);
+    /// let mix = Pigment::from_mix(light, dark, 0.1).to_linear_hdr(&tone_map);
+    /// assert!(mix.iter().all(|&c| c.is_finite() && 0.0 <= c));
+    /// assert!(mix.iter().any(|&c| 1.0 < c));
+    /// ```
+    #[inline]
+    pub fn from_linear_hdr(rgb: [f32; 3], tone_map: &impl ToneMap) -> Self {
+        Self::from(tone_map.tonemap(rgb))
+    }
+
+    /// Returns the scene-referred linear sRGB color of a `Pigment`
+    /// constructed with [`from_linear_hdr()`](Pigment::from_linear_hdr),
+    /// expanding it with `tone_map`.
+    #[inline]
+    pub fn to_linear_hdr(self, tone_map: &impl ToneMap) -> [f32; 3] {
+        tone_map.inverse(self.into())
+    }
+}
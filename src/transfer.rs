@@ -0,0 +1,46 @@
+//! The sRGB transfer function.
+use crate::float::powf;
+
+/// Decodes an encoded sRGB component to linear.
+#[inline]
+pub(crate) fn decode(encoded: f32) -> f32 {
+    if encoded <= 0.04045 {
+        encoded / 12.92
+    } else {
+        powf((encoded + 0.055) / 1.055, 2.4)
+    }
+}
+
+/// Encodes a linear sRGB component.
+#[inline]
+pub(crate) fn encode(linear: f32) -> f32 {
+    if linear <= 0.003_130_8 {
+        linear * 12.92
+    } else {
+        1.055 * powf(linear, 1.0 / 2.4) - 0.055
+    }
+}
+
+/// Decodes an encoded sRGB color to linear.
+#[inline]
+pub(crate) fn decode_srgb(encoded: [f32; 3]) -> [f32; 3] {
+    encoded.map(decode)
+}
+
+/// Encodes a linear sRGB color.
+#[inline]
+pub(crate) fn encode_srgb(linear: [f32; 3]) -> [f32; 3] {
+    linear.map(encode)
+}
+
+/// Decodes a `u8` component encoded sRGB color to linear.
+#[inline]
+pub(crate) fn decode_srgb_u8(encoded: [u8; 3]) -> [f32; 3] {
+    encoded.map(|c| decode(c as f32 / u8::MAX as f32))
+}
+
+/// Decodes a `u16` component encoded sRGB color to linear.
+#[inline]
+pub(crate) fn decode_srgb_u16(encoded: [u16; 3]) -> [f32; 3] {
+    encoded.map(|c| decode(c as f32 / u16::MAX as f32))
+}
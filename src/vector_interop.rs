@@ -0,0 +1,80 @@
+//! Conversions between `Pigment`s and the 3D vector types renderers store
+//! colors in.
+//!
+//! Vectors hold linear sRGB with `x`, `y` and `z` as red, green and blue.
+use crate::Pigment;
+
+#[cfg(feature = "glam")]
+mod glam_vec3 {
+    use crate::Pigment;
+    use glam::Vec3;
+
+    /// Convert a linear sRGB [`Vec3`] to a `Pigment`.
+    impl From<Vec3> for Pigment {
+        #[inline]
+        fn from(color: Vec3) -> Self {
+            Pigment::from_linear_srgb(color.x, color.y, color.z)
+        }
+    }
+
+    /// Convert a `Pigment` to a linear sRGB [`Vec3`].
+    impl From<Pigment> for Vec3 {
+        #[inline]
+        fn from(pigment: Pigment) -> Self {
+            let [r, g, b]: [f32; 3] = pigment.into();
+            Vec3::new(r, g, b)
+        }
+    }
+
+    /// Mixes two linear sRGB colors stored in [`Vec3`]s as pigments.
+    #[inline]
+    pub fn mix_vec3(a: Vec3, b: Vec3, ratio: f32) -> Vec3 {
+        super::mix(a, b, ratio)
+    }
+}
+#[cfg(feature = "glam")]
+pub use glam_vec3::*;
+
+#[cfg(feature = "mint")]
+mod mint_vector3 {
+    use crate::Pigment;
+    use mint::Vector3;
+
+    /// Convert a linear sRGB [`Vector3`] to a `Pigment`.
+    impl From<Vector3<f32>> for Pigment {
+        #[inline]
+        fn from(color: Vector3<f32>) -> Self {
+            Pigment::from_linear_srgb(color.x, color.y, color.z)
+        }
+    }
+
+    /// Convert a `Pigment` to a linear sRGB [`Vector3`].
+    impl From<Pigment> for Vector3<f32> {
+        #[inline]
+        fn from(pigment: Pigment) -> Self {
+            let [x, y, z]: [f32; 3] = pigment.into();
+            Vector3 { x, y, z }
+        }
+    }
+
+    /// Mixes two linear sRGB colors stored in [`Vector3`]s as pigments.
+    #[inline]
+    pub fn mix_vector3(
+        a: Vector3<f32>,
+        b: Vector3<f32>,
+        ratio: f32,
+    ) -> Vector3<f32> {
+        super::mix(a, b, ratio)
+    }
+}
+#[cfg(feature = "mint")]
+pub use mint_vector3::*;
+
+#[inline]
+fn mix<V>(a: V, b: V, ratio: f32) -> V
+where
+    V: Into<Pigment>,
+    Pigment: Into<V>,
+{
+    Pigment::from_mix(a.into(), b.into(), ratio).into()
+}
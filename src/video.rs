@@ -0,0 +1,246 @@
+use crate::{clamp, mix_linear_srgb_f32, transfer};
+use num_traits::cast::AsPrimitive;
+
+/// The matrix used to convert between `Y'CbCr` and encoded `R'G'B'`.
+///
+/// Both variants assume *limited* (studio) range, i.e. luma in `16..=235` and
+/// chroma in `16..=240`, which is what almost all video uses.
+///
+/// The transfer function of the decoded `R'G'B'` values is treated as sRGB.
+/// Strictly speaking BT.709 video uses a slightly different curve but the
+/// difference is negligible for mixing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum YuvMatrix {
+    /// ITU-R BT.601, used by SD video.
+    Bt601,
+    /// ITU-R BT.709, used by HD video.
+    Bt709,
+}
+
+impl YuvMatrix {
+    /// Returns the `(Kr, Kb)` luma coefficients.
+    #[inline]
+    fn coefficients(self) -> (f32, f32) {
+        match self {
+            YuvMatrix::Bt601 => (0.299, 0.114),
+            YuvMatrix::Bt709 => (0.2126, 0.0722),
+        }
+    }
+
+    #[inline]
+    fn to_linear_srgb(self, y: u8, u: u8, v: u8) -> [f32; 3] {
+        let (kr, kb) = self.coefficients();
+        let kg = 1.0 - kr - kb;
+
+        let y = (y as f32 - 16.0) / 219.0;
+        let cb = (u as f32 - 128.0) / 224.0;
+        let cr = (v as f32 - 128.0) / 224.0;
+
+        let r = y + 2.0 * (1.0 - kr) * cr;
+        let b = y + 2.0 * (1.0 - kb) * cb;
+        let g = (y - kr * r - kb * b) / kg;
+
+        transfer::decode_srgb([
+            clamp(r, 0.0, 1.0),
+            clamp(g, 0.0, 1.0),
+            clamp(b, 0.0, 1.0),
+        ])
+    }
+
+    /// Returns `(Y', Cb, Cr)` with `Y'` in `0..1` and chroma in `-0.5..0.5`.
+    #[inline]
+    fn to_ycbcr(self, color: [f32; 3]) -> (f32, f32, f32) {
+        let (kr, kb) = self.coefficients();
+        let kg = 1.0 - kr - kb;
+
+        let [r, g, b] = transfer::encode_srgb(color);
+
+        let y = kr * r + kg * g + kb * b;
+
+        (
+            y,
+            (b - y) / (2.0 * (1.0 - kb)),
+            (r - y) / (2.0 * (1.0 - kr)),
+        )
+    }
+}
+
+/// A read-only 8-bit 4:2:0 frame.
+///
+/// All planes are expected to be tightly packed. For a frame of `width` ×
+/// `height` pixels the chroma planes have `(width + 1) / 2` × `(height + 1) /
+/// 2` samples.
+#[derive(Clone, Copy, Debug)]
+pub enum Yuv420<'a> {
+    /// Three separate planes: `Y`, `U` (`Cb`) and `V` (`Cr`). A.k.a. `I420`.
+    Planar {
+        y: &'a [u8],
+        u: &'a [u8],
+        v: &'a [u8],
+    },
+    /// A `Y` plane followed by a plane of interleaved `U`/`V` samples. A.k.a.
+    /// `NV12`.
+    SemiPlanar { y: &'a [u8], uv: &'a [u8] },
+}
+
+impl<'a> Yuv420<'a> {
+    #[inline]
+    fn luma(&self) -> &'a [u8] {
+        match *self {
+            Yuv420::Planar { y, .. } | Yuv420::SemiPlanar { y, .. } => y,
+        }
+    }
+
+    #[inline]
+    fn chroma(&self, index: usize) -> (u8, u8) {
+        match *self {
+            Yuv420::Planar { u, v, .. } => (u[index], v[index]),
+            Yuv420::SemiPlanar { uv, .. } => (uv[2 * index], uv[2 * index + 1]),
+        }
+    }
+
+    #[inline]
+    fn assert_len(&self, luma_len: usize, chroma_len: usize) {
+        assert!(self.luma().len() >= luma_len, "Y plane is too small");
+        match *self {
+            Yuv420::Planar { u, v, .. } => {
+                assert!(u.len() >= chroma_len, "U plane is too small");
+                assert!(v.len() >= chroma_len, "V plane is too small");
+            }
+            Yuv420::SemiPlanar { uv, .. } => {
+                assert!(uv.len() >= 2 * chroma_len, "UV plane is too small")
+            }
+        }
+    }
+}
+
+/// A writable 8-bit 4:2:0 frame.
+///
+/// See [`Yuv420`] for the plane layout.
+#[derive(Debug)]
+pub enum Yuv420Mut<'a> {
+    /// Three separate planes: `Y`, `U` (`Cb`) and `V` (`Cr`). A.k.a. `I420`.
+    Planar {
+        y: &'a mut [u8],
+        u: &'a mut [u8],
+        v: &'a mut [u8],
+    },
+    /// A `Y` plane followed by a plane of interleaved `U`/`V` samples. A.k.a.
+    /// `NV12`.
+    SemiPlanar { y: &'a mut [u8], uv: &'a mut [u8] },
+}
+
+impl<'a> Yuv420Mut<'a> {
+    #[inline]
+    fn luma(&mut self) -> &mut [u8] {
+        match self {
+            Yuv420Mut::Planar { y, .. } | Yuv420Mut::SemiPlanar { y, .. } => y,
+        }
+    }
+
+    #[inline]
+    fn set_chroma(&mut self, index: usize, cb: u8, cr: u8) {
+        match self {
+            Yuv420Mut::Planar { u, v, .. } => {
+                u[index] = cb;
+                v[index] = cr;
+            }
+            Yuv420Mut::SemiPlanar { uv, .. } => {
+                uv[2 * index] = cb;
+                uv[2 * index + 1] = cr;
+            }
+        }
+    }
+
+    #[inline]
+    fn assert_len(&mut self, luma_len: usize, chroma_len: usize) {
+        assert!(self.luma().len() >= luma_len, "Y plane is too small");
+        match self {
+            Yuv420Mut::Planar { u, v, .. } => {
+                assert!(u.len() >= chroma_len, "U plane is too small");
+                assert!(v.len() >= chroma_len, "V plane is too small");
+            }
+            Yuv420Mut::SemiPlanar { uv, .. } => {
+                assert!(uv.len() >= 2 * chroma_len, "UV plane is too small")
+            }
+        }
+    }
+}
+
+#[inline]
+fn quantize_video(value: f32, offset: f32, scale: f32) -> u8 {
+    clamp(offset + value * scale + 0.5, 0.0, u8::MAX as f32) as _
+}
+
+/// Mixes two 8-bit 4:2:0 video frames of `width` × `height` pixels and writes
+/// the result to `out`.
+///
+/// The frames may use different plane layouts, e.g. mixing an `I420` frame
+/// with an `NV12` frame into an `I420` output is fine.
+///
+/// Every pixel is converted to linear sRGB using its own luma and the chroma
+/// of its 2×2 block, mixed, and converted back. The output chroma of each
+/// block is the average of the chroma of the mixed pixels in that block.
+///
+/// # Panics
+///
+/// If any of the planes is smaller than required by `width` and `height`.
+pub fn mix_yuv420<T>(
+    yuv_a: &Yuv420,
+    yuv_b: &Yuv420,
+    out: &mut Yuv420Mut,
+    width: usize,
+    height: usize,
+    matrix: YuvMatrix,
+    ratio: T,
+) where
+    T: AsPrimitive<f32>,
+{
+    let ratio: f32 = ratio.as_();
+
+    let chroma_width = width.div_ceil(2);
+    let chroma_height = height.div_ceil(2);
+    let luma_len = width * height;
+    let chroma_len = chroma_width * chroma_height;
+
+    yuv_a.assert_len(luma_len, chroma_len);
+    yuv_b.assert_len(luma_len, chroma_len);
+    out.assert_len(luma_len, chroma_len);
+
+    for chroma_y in 0..chroma_height {
+        for chroma_x in 0..chroma_width {
+            let chroma_index = chroma_y * chroma_width + chroma_x;
+            let (u_a, v_a) = yuv_a.chroma(chroma_index);
+            let (u_b, v_b) = yuv_b.chroma(chroma_index);
+
+            let mut cb_sum = 0.0;
+            let mut cr_sum = 0.0;
+            let mut count = 0.0;
+
+            for y in 2 * chroma_y..height.min(2 * chroma_y + 2) {
+                for x in 2 * chroma_x..width.min(2 * chroma_x + 2) {
+                    let index = y * width + x;
+
+                    let result = mix_linear_srgb_f32(
+                        &matrix.to_linear_srgb(yuv_a.luma()[index], u_a, v_a),
+                        &matrix.to_linear_srgb(yuv_b.luma()[index], u_b, v_b),
+                        ratio,
+                    );
+
+                    let (luma, cb, cr) = matrix.to_ycbcr(result);
+                    out.luma()[index] = quantize_video(luma, 16.0, 219.0);
+
+                    cb_sum += cb;
+                    cr_sum += cr;
+                    count += 1.0;
+                }
+            }
+
+            out.set_chroma(
+                chroma_index,
+                quantize_video(cb_sum / count, 128.0, 224.0),
+                quantize_video(cr_sum / count, 128.0, 224.0),
+            );
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! A JavaScript API via [wasm-bindgen](https://rustwasm.github.io/docs/wasm-bindgen/).
+//!
+//! Build with `wasm-pack build --features wasm`. wasm-bindgen emits
+//! TypeScript definitions for everything exported here. Colors are passed as
+//! `Uint8Array`s of encoded sRGB (gamma 2.2) `[r, g, b]` or as `#rrggbb` hex
+//! strings.
+use crate::{gradient_into_slice, mix_srgb_u8, parse_hex, to_hex, Pigment};
+use alloc::{string::String, vec::Vec};
+use wasm_bindgen::prelude::*;
+
+/// Returns the first three components of `srgb`.
+fn to_srgb_u8(srgb: &[u8]) -> Option<[u8; 3]> {
+    srgb.get(..3)?.try_into().ok()
+}
+
+/// Mixes two colors given as `[r, g, b]`.
+///
+/// Returns `undefined` if a color has fewer than three components.
+#[wasm_bindgen(js_name = mixSrgbU8)]
+pub fn js_mix_srgb_u8(a: &[u8], b: &[u8], ratio: f32) -> Option<Vec<u8>> {
+    Some(mix_srgb_u8(&to_srgb_u8(a)?, &to_srgb_u8(b)?, ratio).to_vec())
+}
+
+/// Mixes two colors given as `#rrggbb` or `rrggbb`.
+///
+/// Returns `undefined` if a color can not be parsed.
+#[wasm_bindgen(js_name = mixHex)]
+pub fn js_mix_hex(a: &str, b: &str, ratio: f32) -> Option<String> {
+    Some(to_hex(mix_srgb_u8(&parse_hex(a)?, &parse_hex(b)?, ratio)))
+}
+
+/// Returns `n` evenly spaced mixes from `a` to `b`, both ends included, as
+/// `[r, g, b, r, g, b, …]`.
+///
+/// Returns `undefined` if a color has fewer than three components.
+#[wasm_bindgen(js_name = gradientSrgbU8)]
+pub fn js_gradient_srgb_u8(a: &[u8], b: &[u8], n: usize) -> Option<Vec<u8>> {
+    let mut gradient = alloc::vec![[0; 3]; n];
+    gradient_into_slice(&to_srgb_u8(a)?, &to_srgb_u8(b)?, &mut gradient);
+
+    Some(gradient.into_iter().flatten().collect())
+}
+
+/// Returns `n` evenly spaced mixes from `a` to `b`, both ends included, as
+/// `#rrggbb` strings.
+///
+/// Returns `undefined` if a color can not be parsed.
+#[wasm_bindgen(js_name = gradientHex)]
+pub fn js_gradient_hex(a: &str, b: &str, n: usize) -> Option<Vec<String>> {
+    let mut gradient = alloc::vec![[0; 3]; n];
+    gradient_into_slice(&parse_hex(a)?, &parse_hex(b)?, &mut gradient);
+
+    Some(gradient.into_iter().map(to_hex).collect())
+}
+
+/// A color represented as pigment mixture.
+///
+/// Mix several colors in this form and convert back once, instead of
+/// converting on every mix.
+#[wasm_bindgen(js_name = Pigment)]
+#[derive(Clone, Debug)]
+pub struct JsPigment(Pigment);
+
+#[wasm_bindgen(js_class = Pigment)]
+impl JsPigment {
+    /// Constructs a pigment from encoded sRGB (gamma 2.2) components.
+    #[wasm_bindgen(js_name = fromSrgbU8)]
+    pub fn from_srgb_u8(r: u8, g: u8, b: u8) -> JsPigment {
+        JsPigment(Pigment::from_srgb_u8(r, g, b))
+    }
+
+    /// Constructs a pigment from `#rrggbb` or `rrggbb`.
+    ///
+    /// Returns `undefined` if the color can not be parsed.
+    #[wasm_bindgen(js_name = fromHex)]
+    pub fn from_hex(hex: &str) -> Option<JsPigment> {
+        let [r, g, b] = parse_hex(hex)?;
+        Some(Self::from_srgb_u8(r, g, b))
+    }
+
+    /// Returns this pigment mixed with `other` using `ratio`.
+    pub fn mix(&self, other: &JsPigment, ratio: f32) -> JsPigment {
+        JsPigment(Pigment::from_mix(self.0, other.0, ratio))
+    }
+
+    /// Returns the color as `[r, g, b]`.
+    #[wasm_bindgen(js_name = toSrgbU8)]
+    pub fn to_srgb_u8(&self) -> Vec<u8> {
+        <[u8; 3]>::from(self.0).to_vec()
+    }
+
+    /// Returns the color as `#rrggbb`.
+    #[wasm_bindgen(js_name = toHex)]
+    pub fn to_hex(&self) -> String {
+        to_hex(self.0.into())
+    }
+
+    /// Returns the latent representation, in which mixing is linear.
+    pub fn latent(&self) -> Vec<f32> {
+        <[f32; crate::pigment::PIGMENT_LEN]>::from(self.0).to_vec()
+    }
+}
@@ -0,0 +1,124 @@
+//! Checks that the pure Rust backends mix like paint.
+//!
+//! These run against the compressed, uncompressed and runtime-loaded tables
+//! as well as the `fixed-point` and `deterministic` backends.
+#![cfg(feature = "pure-rust")]
+use pigment_mixing::{latent_to_srgb, mix_srgb_u8, srgb_to_latent};
+
+const WHITE: [u8; 3] = [255, 255, 255];
+const BLACK: [u8; 3] = [0, 0, 0];
+
+/// Returns the difference between the largest and smallest component.
+fn chroma(srgb: [u8; 3]) -> u8 {
+    srgb.iter().max().unwrap() - srgb.iter().min().unwrap()
+}
+
+#[test]
+fn white_and_black_mix_to_grays() {
+    let mixes =
+        [0.25, 0.5, 0.75].map(|ratio| mix_srgb_u8(&WHITE, &BLACK, ratio));
+
+    for mix in mixes {
+        assert!(chroma(mix) <= 12, "{mixes:?}");
+    }
+    assert!(
+        mixes.windows(2).all(|pair| pair[1][1] < pair[0][1]),
+        "{mixes:?}"
+    );
+}
+
+#[test]
+fn grays_tint_white_gray() {
+    for gray in (0..=128).step_by(16) {
+        let mix = mix_srgb_u8(&[gray; 3], &WHITE, 0.5);
+
+        assert!(chroma(mix) <= 12, "{gray}: {mix:?}");
+    }
+}
+
+#[test]
+fn primaries_mix_like_paint() {
+    let [r, g, b] = mix_srgb_u8(&[252, 211, 0], &[0, 33, 133], 0.5);
+    assert!(r < g && b < g, "yellow and blue: {:?}", [r, g, b]);
+
+    let [r, g, b] = mix_srgb_u8(&[255, 39, 2], &[0, 33, 133], 0.5);
+    assert!(g < r && g < b, "red and blue: {:?}", [r, g, b]);
+
+    let [r, g, b] = mix_srgb_u8(&[128, 2, 46], &[255, 236, 4], 0.5);
+    assert!(g < r && b < g, "magenta and yellow: {:?}", [r, g, b]);
+}
+
+/// A small xorshift generator, so the tests need no dependencies.
+struct XorShift(u32);
+
+impl XorShift {
+    fn next(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0
+    }
+
+    fn srgb_u8(&mut self) -> [u8; 3] {
+        let bits = self.next();
+        [bits as u8, (bits >> 8) as u8, (bits >> 16) as u8]
+    }
+}
+
+fn max_difference(a: [u8; 3], b: [u8; 3]) -> u8 {
+    (0..3).map(|i| a[i].abs_diff(b[i])).max().unwrap_or(0)
+}
+
+#[test]
+fn mixes_at_the_ends_are_the_colors() {
+    let mut rng = XorShift(0x2545_f491);
+
+    for _ in 0..1000 {
+        let (a, b) = (rng.srgb_u8(), rng.srgb_u8());
+
+        assert!(max_difference(a, mix_srgb_u8(&a, &b, 0.0)) <= 1, "{a:?}");
+        assert!(max_difference(b, mix_srgb_u8(&a, &b, 1.0)) <= 1, "{b:?}");
+    }
+}
+
+#[test]
+fn latent_round_trip() {
+    let steps = 17;
+
+    for r in 0..steps {
+        for g in 0..steps {
+            for b in 0..steps {
+                let srgb = [r, g, b].map(|c| c as f32 / (steps - 1) as f32);
+                let round_trip = latent_to_srgb(&srgb_to_latent(srgb));
+
+                for (expected, actual) in srgb.iter().zip(round_trip) {
+                    assert!(
+                        (expected - actual).abs() < 2.0e-3,
+                        "{srgb:?} became {round_trip:?}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "fixed-point")]
+#[test]
+fn fixed_point_matches_float() {
+    use pigment_mixing::fixed;
+
+    let mut rng = XorShift(0x9e37_79b9);
+
+    for _ in 0..10_000 {
+        let (a, b) = (rng.srgb_u8(), rng.srgb_u8());
+        let ratio = (rng.next() >> 16) as i32;
+
+        let float = mix_srgb_u8(&a, &b, ratio as f32 / fixed::ONE as f32);
+        let fixed = fixed::mix_srgb_u8(&a, &b, ratio);
+
+        assert!(
+            max_difference(float, fixed) <= 1,
+            "{a:?} {b:?} {ratio}: {float:?} and {fixed:?}"
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! The C ABI, called as a C program would.
+#![cfg(feature = "capi")]
+use pigment_mixing::{
+    capi::{
+        pm_gradient_srgb8, pm_latent_to_srgb, pm_mix_latent, pm_mix_srgb8,
+        pm_mix_srgb8_buffer, pm_srgb_to_latent, PM_LATENT_SIZE,
+    },
+    mix_srgb_u8,
+};
+use std::ptr;
+
+const YELLOW: [u8; 3] = [252, 211, 0];
+const BLUE: [u8; 3] = [0, 0, 96];
+
+#[test]
+fn mix_matches_rust() {
+    let mut out = [0; 3];
+    unsafe {
+        pm_mix_srgb8(YELLOW.as_ptr(), BLUE.as_ptr(), 0.5, out.as_mut_ptr())
+    };
+
+    assert_eq!(mix_srgb_u8(&YELLOW, &BLUE, 0.5), out);
+}
+
+#[test]
+fn mix_buffer_in_place() {
+    let mut a = [YELLOW, BLUE, YELLOW].concat();
+    let b = [BLUE, YELLOW, YELLOW].concat();
+    let a_ptr = a.as_mut_ptr();
+    unsafe { pm_mix_srgb8_buffer(a_ptr, b.as_ptr(), 0.25, a_ptr, 3) };
+
+    assert_eq!(mix_srgb_u8(&YELLOW, &BLUE, 0.25), a[0..3]);
+    assert_eq!(mix_srgb_u8(&BLUE, &YELLOW, 0.25), a[3..6]);
+    assert_eq!(mix_srgb_u8(&YELLOW, &YELLOW, 0.25), a[6..9]);
+}
+
+#[test]
+fn gradient_ends_are_the_colors() {
+    let mut out = [0; 3 * 8];
+    unsafe {
+        pm_gradient_srgb8(YELLOW.as_ptr(), BLUE.as_ptr(), out.as_mut_ptr(), 8)
+    };
+
+    assert_eq!(mix_srgb_u8(&YELLOW, &BLUE, 0.0), out[..3]);
+    assert_eq!(mix_srgb_u8(&YELLOW, &BLUE, 1.0), out[out.len() - 3..]);
+}
+
+#[test]
+fn latent_round_trip_and_mix() {
+    let mut latent_a = [0.0; PM_LATENT_SIZE];
+    let mut latent_b = [0.0; PM_LATENT_SIZE];
+    let mut srgb = [0; 3];
+
+    unsafe {
+        pm_srgb_to_latent(YELLOW.as_ptr(), latent_a.as_mut_ptr());
+        pm_srgb_to_latent(BLUE.as_ptr(), latent_b.as_mut_ptr());
+
+        pm_latent_to_srgb(latent_a.as_ptr(), srgb.as_mut_ptr());
+        assert_eq!(YELLOW, srgb);
+
+        // Mixes in place into `latent_a`.
+        let latent_a_ptr = latent_a.as_mut_ptr();
+        pm_mix_latent(latent_a_ptr, latent_b.as_ptr(), 0.5, latent_a_ptr);
+        pm_latent_to_srgb(latent_a.as_ptr(), srgb.as_mut_ptr());
+    }
+
+    let expected = mix_srgb_u8(&YELLOW, &BLUE, 0.5);
+    assert!((0..3).all(|i| expected[i].abs_diff(srgb[i]) <= 1));
+}
+
+#[test]
+fn null_pointers_are_ignored() {
+    let mut out = [7; 3];
+    let mut latent = [7.0; PM_LATENT_SIZE];
+
+    unsafe {
+        pm_mix_srgb8(ptr::null(), BLUE.as_ptr(), 0.5, out.as_mut_ptr());
+        pm_mix_srgb8(YELLOW.as_ptr(), BLUE.as_ptr(), 0.5, ptr::null_mut());
+        pm_srgb_to_latent(ptr::null(), latent.as_mut_ptr());
+        pm_srgb_to_latent(YELLOW.as_ptr(), ptr::null_mut());
+        pm_latent_to_srgb(ptr::null(), out.as_mut_ptr());
+        pm_mix_latent(latent.as_ptr(), ptr::null(), 0.5, latent.as_mut_ptr());
+    }
+
+    assert_eq!([7; 3], out);
+    assert_eq!([7.0; PM_LATENT_SIZE], latent);
+}
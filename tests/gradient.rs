@@ -0,0 +1,163 @@
+//! Sampling, rendering and files of gradients.
+#![cfg(feature = "gradient")]
+use pigment_mixing::{Easing, Error, Gradient, GradientStop, Pigment};
+use std::sync::Arc;
+
+fn gradient() -> Gradient {
+    Gradient::new()
+        .with_stop(0.0, Pigment::from_srgb_u8(252, 211, 0))
+        .with_stop(0.4, Pigment::from_srgb_u8(201, 37, 44))
+        .with_stop(1.0, Pigment::from_srgb_u8(0, 0, 96))
+}
+
+/// Returns the largest difference of a `u8` component between both
+/// gradients over `n` samples.
+fn max_difference(a: &Gradient, b: &Gradient, n: usize) -> u8 {
+    a.samples(n)
+        .zip(b.samples(n))
+        .flat_map(|(a, b)| {
+            let (a, b): ([u8; 3], [u8; 3]) = (a.into(), b.into());
+            (0..3).map(move |i| a[i].abs_diff(b[i]))
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+#[test]
+fn custom_easings_can_capture() {
+    let steps = 2.0;
+    let easing = Easing::Custom(Arc::new(move |t| (t * steps).floor() / steps));
+    let yellow = Pigment::from_srgb_u8(252, 211, 0);
+    let blue = Pigment::from_srgb_u8(0, 0, 96);
+
+    let mut gradient = Gradient::new();
+    gradient.insert_stop(
+        GradientStop::new(0.0, yellow).with_easing(easing.clone()),
+    );
+    gradient.add_stop(1.0, blue);
+
+    let srgb = |pigment: Pigment| -> [u8; 3] { pigment.into() };
+    assert_eq!(srgb(yellow), srgb(gradient.sample(0.4)));
+    assert_eq!(
+        srgb(Pigment::from_mix(yellow, blue, 0.5)),
+        srgb(gradient.sample(0.6))
+    );
+
+    // Custom easings are equal if they share the closure.
+    assert_eq!(easing, easing.clone());
+    assert_ne!(easing, Easing::Custom(Arc::new(move |t| t)));
+}
+
+#[test]
+fn ggr_round_trip() {
+    let gradient = gradient();
+    let ggr = Gradient::from_ggr(&gradient.to_ggr("Test", 64)).unwrap();
+
+    // The samples written become the stops.
+    assert_eq!(64, ggr.len());
+    assert!(max_difference(&gradient, &ggr, 64) <= 1);
+
+    // In between, the stops are mixed as pigments, not along the original
+    // gradient.
+    let difference = max_difference(&gradient, &ggr, 256);
+    assert!(difference <= 12, "{difference}");
+}
+
+#[test]
+fn ggr_reads_hard_edges_and_steps() {
+    let ggr = Gradient::from_ggr(
+        "GIMP Gradient\n\
+         Name: Flag\n\
+         2\n\
+         0.0 0.25 0.5 1 1 0 1 1 1 0 1 0 0\n\
+         0.5 0.75 1.0 0 0 1 1 0 0 0.4 1 5 0\n",
+    )
+    .unwrap();
+
+    let srgb = |t: f32| -> [u8; 3] { ggr.sample(t).into() };
+    assert_eq!([255, 255, 0], srgb(0.0));
+    assert_eq!([255, 255, 0], srgb(0.5 - 1.0e-3));
+    assert_eq!([0, 0, 255], srgb(0.5 + 1.0e-3));
+    assert_eq!([0, 0, 255], srgb(0.75 - 1.0e-3));
+    assert_eq!([0, 0, 102], srgb(0.75 + 1.0e-3));
+    assert_eq!([0, 0, 102], srgb(1.0));
+}
+
+#[test]
+fn ggr_keeps_midpoints_and_easing() {
+    let ggr = Gradient::from_ggr(
+        "GIMP Gradient\n\
+         1\n\
+         0.0 0.2 1.0 1 1 0 1 0 0 0.5 1 2 0\n",
+    )
+    .unwrap();
+
+    let stop = &ggr.stops()[0];
+    assert!((stop.midpoint() - 0.2).abs() < 1.0e-6);
+    assert_eq!(Easing::Smoothstep, stop.easing);
+}
+
+#[test]
+fn midpoint_at_zero_starts_with_a_hard_edge() {
+    let yellow = Pigment::from_srgb_u8(252, 211, 0);
+    let blue = Pigment::from_srgb_u8(0, 0, 96);
+    let mut gradient = Gradient::new();
+    gradient.insert_stop(GradientStop::new(0.0, yellow).with_midpoint(0.0));
+    gradient.add_stop(1.0, blue);
+
+    let srgb = |pigment: Pigment| -> [u8; 3] { pigment.into() };
+    assert_eq!(srgb(yellow), srgb(gradient.sample(0.0)));
+    assert_eq!(
+        srgb(Pigment::from_mix(yellow, blue, 0.5)),
+        srgb(gradient.sample(1.0e-6))
+    );
+    assert_eq!(srgb(blue), srgb(gradient.sample(1.0)));
+}
+
+#[test]
+fn invalid_ggr_is_rejected() {
+    assert!(matches!(
+        Gradient::from_ggr("GIMP Palette\n1\n"),
+        Err(Error::InvalidFormat(_))
+    ));
+    assert!(matches!(
+        Gradient::from_ggr("GIMP Gradient\n2\n0 0.5 1 0 0 0 1 1 1 1 1 0 0\n"),
+        Err(Error::UnexpectedEof)
+    ));
+    assert!(matches!(
+        Gradient::from_ggr("GIMP Gradient\n1\n0 0.5 1 red\n"),
+        Err(Error::Parse { line: 3, .. })
+    ));
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn images_without_a_cross_axis_skip_the_ticks() {
+    use pigment_mixing::{GradientImageOptions, GradientOrientation};
+
+    let gradient = gradient();
+
+    for orientation in [
+        GradientOrientation::Horizontal,
+        GradientOrientation::Vertical,
+    ] {
+        let options = GradientImageOptions {
+            orientation,
+            mark_stops: true,
+        };
+        let (width, height) = match orientation {
+            GradientOrientation::Horizontal => (16, 0),
+            GradientOrientation::Vertical => (0, 16),
+        };
+
+        let image = gradient.to_image(width, height, &options);
+        assert_eq!((width, height), image.dimensions());
+
+        // A single pixel across still gets its ticks.
+        let image = gradient.to_image(width.max(1), height.max(1), &options);
+        assert_eq!(
+            image::Rgb([255, 255, 255]),
+            *image.get_pixel(15 * (width / 16), 15 * (height / 16))
+        );
+    }
+}
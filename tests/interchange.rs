@@ -0,0 +1,106 @@
+//! Round trips through the interchange format.
+#![cfg(all(feature = "json", feature = "palette"))]
+use pigment_mixing::{Palette, Pigment};
+
+fn palette() -> Palette {
+    Palette::new()
+        .with_entry("Cadmium Yellow", Pigment::from_srgb_u8(254, 236, 0))
+        .with_entry("Ultramarine", Pigment::from_srgb_u8(25, 0, 89))
+        .with_entry("", Pigment::from_srgb_u8(128, 128, 128))
+}
+
+#[test]
+fn json_round_trip_is_lossless() {
+    let palette = palette();
+
+    assert_eq!(
+        palette,
+        Palette::from_json(&palette.to_json().unwrap()).unwrap()
+    );
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn toml_round_trip_is_lossless() {
+    let palette = palette();
+
+    assert_eq!(
+        palette,
+        Palette::from_toml(&palette.to_toml().unwrap()).unwrap()
+    );
+}
+
+#[cfg(feature = "gradient")]
+#[test]
+fn gradient_json_round_trip_is_lossless() {
+    use pigment_mixing::{Easing, Gradient, GradientStop};
+
+    let mut gradient = Gradient::new();
+    gradient.insert_stop(
+        GradientStop::new(0.0, Pigment::from_srgb_u8(252, 211, 0))
+            .with_easing(Easing::Smoothstep)
+            .with_midpoint(0.25),
+    );
+    gradient.add_stop(1.0, Pigment::from_srgb_u8(0, 0, 96));
+
+    assert_eq!(
+        gradient,
+        Gradient::from_json(&gradient.to_json().unwrap()).unwrap()
+    );
+}
+
+#[test]
+fn srgb_alone_is_enough() {
+    let palette = Palette::from_json(
+        r##"{ "colors": [{ "name": "Yellow", "srgb": "#fcd300" }] }"##,
+    )
+    .unwrap();
+
+    let srgb: [u8; 3] = palette.entries()[0].pigment.into();
+    assert_eq!([252, 211, 0], srgb);
+}
+
+#[test]
+fn latent_of_another_model_falls_back_to_srgb() {
+    // A latent that is far from yellow in any model.
+    let json = r##"{ "colors": [{
+        "srgb": "#fcd300",
+        "latent": [1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+        "model": "some-other-model"
+    }] }"##;
+
+    let srgb: [u8; 3] = Palette::from_json(json).unwrap().entries()[0]
+        .pigment
+        .into();
+    assert_eq!([252, 211, 0], srgb);
+
+    // Without a model the latent cannot be trusted either.
+    let json = json.replace(r#""model": "some-other-model""#, r#""x": 0"#);
+    let srgb: [u8; 3] = Palette::from_json(&json).unwrap().entries()[0]
+        .pigment
+        .into();
+    assert_eq!([252, 211, 0], srgb);
+}
+
+#[test]
+fn latent_alone_is_used() {
+    let pigment = Pigment::from_srgb_u8(25, 0, 89);
+    let latent: [f32; pigment_mixing::LATENT_LEN] = pigment.into();
+    let json = format!(r#"{{ "colors": [{{ "latent": {latent:?} }}] }}"#);
+
+    assert_eq!(
+        pigment,
+        Palette::from_json(&json).unwrap().entries()[0].pigment
+    );
+}
+
+#[test]
+fn invalid_colors_are_rejected() {
+    assert!(
+        Palette::from_json(r#"{ "colors": [{ "name": "None" }] }"#).is_err()
+    );
+    assert!(
+        Palette::from_json(r#"{ "colors": [{ "srgb": "not a color" }] }"#)
+            .is_err()
+    );
+}
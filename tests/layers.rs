@@ -0,0 +1,31 @@
+//! Compositing of layer stacks.
+#![cfg(feature = "canvas")]
+use pigment_mixing::{Error, Layer, LayerStack, Pigment, PigmentBuffer};
+
+fn stack() -> LayerStack {
+    let yellow = PigmentBuffer::new(4, 2, Pigment::from_srgb_u8(254, 236, 0));
+
+    LayerStack::new(4, 2, Pigment::from_srgb_u8(255, 255, 255))
+        .with_layer(Layer::new(yellow).with_alpha(vec![1.0; 8]))
+}
+
+#[test]
+fn opaque_layers_cover_the_background() {
+    let stack = stack();
+    let flat = stack.flatten().unwrap();
+
+    assert_eq!((4, 2), (flat.width(), flat.height()));
+    assert_eq!(stack.layers()[0].buffer.pixels(), flat.pixels());
+}
+
+#[test]
+fn layers_that_no_longer_fit_are_rejected() {
+    let mut short_alpha = stack();
+    short_alpha.layers_mut()[0].alpha.pop();
+    assert!(matches!(short_alpha.flatten(), Err(Error::SizeMismatch(_))));
+
+    let mut resized = stack();
+    resized.layers_mut()[0] =
+        Layer::new(PigmentBuffer::new(2, 2, Pigment::from_srgb_u8(0, 0, 0)));
+    assert!(matches!(resized.to_srgb_u8(), Err(Error::SizeMismatch(_))));
+}
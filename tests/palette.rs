@@ -0,0 +1,122 @@
+//! Round trips of palettes through the supported file formats.
+#![cfg(feature = "palette")]
+use pigment_mixing::{Error, Palette, Pigment, LATENT_LEN};
+
+fn palette() -> Palette {
+    Palette::new()
+        .with_entry("Cadmium Yellow", Pigment::from_srgb_u8(254, 236, 0))
+        .with_entry("Ultramarine", Pigment::from_srgb_u8(25, 0, 89))
+        .with_entry("", Pigment::from_srgb_u8(0, 0, 0))
+        .with_entry("Titanium White", Pigment::from_srgb_u8(255, 255, 255))
+        .with_entry("Grüner Umbra", Pigment::from_srgb_u8(79, 68, 35))
+}
+
+/// A pigment whose color lies outside the sRGB gamut.
+fn out_of_gamut() -> Pigment {
+    let mut latent: [f32; LATENT_LEN] =
+        Pigment::from_srgb(1.0, 0.0, 0.0).into();
+    latent[4] += 0.5;
+    latent[5] -= 0.5;
+
+    Pigment::from_latent(latent)
+}
+
+/// Asserts that both palettes hold the same names and `u8` colors.
+fn assert_same_colors(expected: &Palette, actual: &Palette) {
+    assert_eq!(expected.len(), actual.len());
+
+    for (expected, actual) in expected.iter().zip(actual) {
+        assert_eq!(expected.name, actual.name);
+        assert_eq!(
+            <[u8; 3]>::from(expected.pigment),
+            <[u8; 3]>::from(actual.pigment),
+            "{}",
+            expected.name
+        );
+    }
+}
+
+#[test]
+fn aco_round_trip() {
+    let palette = palette();
+
+    assert_same_colors(
+        &palette,
+        &Palette::from_aco(&palette.to_aco().unwrap()).unwrap(),
+    );
+}
+
+#[test]
+fn ase_round_trip() {
+    let palette = palette();
+
+    assert_same_colors(
+        &palette,
+        &Palette::from_ase(&palette.to_ase().unwrap()).unwrap(),
+    );
+}
+
+#[test]
+fn gpl_round_trip() {
+    let palette = palette();
+
+    assert_same_colors(
+        &palette,
+        &Palette::from_gpl(&palette.to_gpl("Test")).unwrap(),
+    );
+}
+
+#[test]
+fn empty_palette_round_trip() {
+    let palette = Palette::new();
+
+    assert!(Palette::from_aco(&palette.to_aco().unwrap())
+        .unwrap()
+        .is_empty());
+    assert!(Palette::from_ase(&palette.to_ase().unwrap())
+        .unwrap()
+        .is_empty());
+    assert!(Palette::from_gpl(&palette.to_gpl("Empty"))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn ase_clamps_out_of_gamut_colors() {
+    let palette = Palette::new().with_entry("Too Red", out_of_gamut());
+    let ase = palette.to_ase().unwrap();
+
+    // Signature, version, block count, block type, block length, name
+    // length, name and color model precede the components.
+    let name_len = "Too Red".len() + 1;
+    let components = &ase[4 + 4 + 4 + 2 + 4 + 2 + 2 * name_len + 4..][..12];
+
+    components.chunks(4).for_each(|component| {
+        let component = f32::from_be_bytes(component.try_into().unwrap());
+        assert!((0.0..=1.0).contains(&component), "{component}");
+    });
+}
+
+#[test]
+fn palettes_too_large_for_the_format_are_rejected() {
+    let black = Pigment::from_srgb_u8(0, 0, 0);
+    let palette = (0..=u16::MAX as usize)
+        .fold(Palette::new(), |palette, _| palette.with_entry("", black));
+    assert!(matches!(palette.to_aco(), Err(Error::TooLarge(_))));
+    assert!(palette.to_ase().is_ok());
+
+    let name: String = ['a'; u16::MAX as usize].iter().collect();
+    let palette = Palette::new().with_entry(name, black);
+    assert!(matches!(palette.to_ase(), Err(Error::TooLarge(_))));
+}
+
+#[test]
+fn invalid_files_are_rejected() {
+    assert!(matches!(
+        Palette::from_ase(b"ASEX\0\x01\0\0\0\0\0\0"),
+        Err(Error::InvalidFormat(_))
+    ));
+    assert!(Palette::from_ase(b"ASEF\0\x01").is_err());
+    assert!(Palette::from_aco(&[0, 1, 0]).is_err());
+    assert!(Palette::from_gpl("Name: Not a GIMP palette").is_err());
+}
@@ -0,0 +1,278 @@
+//! Quantization and dithering of buffers.
+use pigment_mixing::{
+    dither_srgb_u16_into, dither_srgb_u8_into,
+    dither_srgb_u8_into_with_amplitude, quantize, quantize_bits, Dither,
+    DitherAmplitude, DitherNoise, NoDither, Rng, RoundingMode,
+};
+
+const WIDTH: usize = 64;
+const HEIGHT: usize = 16;
+
+/// A flat image of a gray that lies between two `u8` values.
+fn flat(gray: f32) -> Vec<f32> {
+    vec![gray; WIDTH * HEIGHT * 3]
+}
+
+fn dither_u8(dither: Dither<'_>, rng_seed: u64, pixels: &[f32]) -> Vec<u8> {
+    let mut out = vec![0; pixels.len()];
+    dither_srgb_u8_into(
+        &mut pixels.to_vec(),
+        WIDTH,
+        dither,
+        &mut Rng::new_seed(rng_seed),
+        &mut out,
+    );
+
+    out
+}
+
+fn dither_u8_with(
+    dither: Dither<'_>,
+    noise: DitherNoise,
+    amplitude: DitherAmplitude,
+    pixels: &[f32],
+) -> Vec<u8> {
+    let mut out = vec![0; pixels.len()];
+    dither_srgb_u8_into_with_amplitude(
+        &mut pixels.to_vec(),
+        WIDTH,
+        dither,
+        noise,
+        amplitude,
+        &mut Rng::new_seed(0),
+        &mut out,
+    );
+
+    out
+}
+
+fn mean(values: &[u8]) -> f32 {
+    values.iter().map(|&v| v as f32).sum::<f32>() / values.len() as f32
+}
+
+/// Returns if any pixel of `out` has channels that differ.
+fn has_tinted_pixels(out: &[u8]) -> bool {
+    out.chunks(3)
+        .any(|rgb| rgb[0] != rgb[1] || rgb[1] != rgb[2])
+}
+
+/// The dithers that add noise.
+const NOISE_DITHERS: [Dither<'static>; 4] = [
+    Dither::Random,
+    Dither::Seeded(7),
+    Dither::Ordered,
+    Dither::BlueNoise,
+];
+
+#[test]
+fn quantize_maps_the_full_range() {
+    let rounding = RoundingMode::Nearest;
+
+    assert_eq!(0, quantize::<f32, u8>(0.0, rounding, &mut NoDither));
+    assert_eq!(255, quantize::<f32, u8>(1.0, rounding, &mut NoDither));
+    assert_eq!(255, quantize::<f32, u8>(2.0, rounding, &mut NoDither));
+    assert_eq!(0, quantize::<f32, u8>(-1.0, rounding, &mut NoDither));
+    assert_eq!(0, quantize::<f32, u8>(f32::NAN, rounding, &mut NoDither));
+    assert_eq!(u16::MAX, quantize::<f64, u16>(1.0, rounding, &mut NoDither));
+    assert_eq!(
+        -i16::MAX,
+        quantize::<f32, i16>(-1.0, rounding, &mut NoDither)
+    );
+}
+
+#[test]
+fn rounding_modes() {
+    let half = 2.5 / 255.0;
+    let round = |rounding| quantize::<f32, u8>(half, rounding, &mut NoDither);
+
+    assert_eq!(3, round(RoundingMode::Nearest));
+    assert_eq!(2, round(RoundingMode::NearestEven));
+    assert_eq!(2, round(RoundingMode::Floor));
+}
+
+#[test]
+fn quantize_bits_maps_the_full_range() {
+    for bits in 1..=16 {
+        let max = ((1u32 << bits) - 1) as u16;
+        let rounding = RoundingMode::Nearest;
+
+        assert_eq!(0, quantize_bits(0.0f32, bits, rounding, &mut NoDither));
+        assert_eq!(max, quantize_bits(1.0f32, bits, rounding, &mut NoDither));
+    }
+}
+
+#[test]
+fn dithers_preserve_the_mean() {
+    let gray = 100.3 / 255.0;
+    let pixels = flat(gray);
+    let texture: Vec<u8> = (0..=255).collect();
+
+    for dither in [
+        Dither::Random,
+        Dither::Seeded(7),
+        Dither::Ordered,
+        Dither::BlueNoise,
+        Dither::Texture {
+            texture: &texture,
+            width: 16,
+        },
+        Dither::FloydSteinberg,
+        Dither::Atkinson,
+    ] {
+        let out = dither_u8(dither, 0, &pixels);
+        // Atkinson drops a quarter of the error.
+        let tolerance = if Dither::Atkinson == dither {
+            0.1
+        } else {
+            0.05
+        };
+
+        assert!(out.iter().all(|&v| v == 100 || v == 101), "{dither:?}");
+        assert!(
+            (mean(&out) - gray * 255.0).abs() < tolerance,
+            "{dither:?}: {}",
+            mean(&out)
+        );
+    }
+}
+
+#[test]
+fn dithers_keep_exact_values() {
+    let pixels = flat(100.0 / 255.0);
+
+    for dither in [
+        Dither::Ordered,
+        Dither::BlueNoise,
+        Dither::FloydSteinberg,
+        Dither::Atkinson,
+    ] {
+        assert!(dither_u8(dither, 0, &pixels).iter().all(|&v| v == 100));
+    }
+}
+
+#[test]
+fn seeded_dither_ignores_the_rng() {
+    let pixels = flat(0.3);
+
+    assert_eq!(
+        dither_u8(Dither::Seeded(1), 0, &pixels),
+        dither_u8(Dither::Seeded(1), 1, &pixels)
+    );
+    assert_ne!(
+        dither_u8(Dither::Seeded(1), 0, &pixels),
+        dither_u8(Dither::Seeded(2), 0, &pixels)
+    );
+}
+
+#[test]
+fn amplitude_widens_the_noise() {
+    let out = dither_u8_with(
+        Dither::Random,
+        DitherNoise::PerChannel,
+        DitherAmplitude::Triangular(1.0),
+        &flat(100.0 / 255.0),
+    );
+
+    assert!(out.iter().all(|&v| (99..=101).contains(&v)));
+    assert!(out.iter().any(|&v| v != 100));
+}
+
+#[test]
+fn u16_dither_matches_range() {
+    let mut pixels: Vec<f32> = (0..WIDTH * 3)
+        .map(|i| (i / 3) as f32 / (WIDTH - 1) as f32)
+        .collect();
+    let mut out = vec![0; pixels.len()];
+    dither_srgb_u16_into(
+        &mut pixels,
+        WIDTH,
+        Dither::FloydSteinberg,
+        &mut Rng::new_seed(0),
+        &mut out,
+    );
+
+    assert_eq!([0; 3], out[..3]);
+    assert_eq!([u16::MAX; 3], out[out.len() - 3..]);
+}
+
+#[test]
+fn noise_is_drawn_per_channel() {
+    let pixels = flat(100.5 / 255.0);
+
+    for dither in NOISE_DITHERS {
+        let per_channel = dither_u8_with(
+            dither,
+            DitherNoise::PerChannel,
+            DitherAmplitude::default(),
+            &pixels,
+        );
+        let shared = dither_u8_with(
+            dither,
+            DitherNoise::Shared,
+            DitherAmplitude::default(),
+            &pixels,
+        );
+
+        assert!(has_tinted_pixels(&per_channel), "{dither:?}");
+        assert!(!has_tinted_pixels(&shared), "{dither:?}");
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn quantizer_draws_noise_per_channel() {
+    use pigment_mixing::Quantizer;
+
+    let pixels = flat(100.5 / 255.0);
+
+    for dither in NOISE_DITHERS {
+        for noise in [DitherNoise::PerChannel, DitherNoise::Shared] {
+            let mut quantizer = Quantizer::new(WIDTH, dither).with_noise(noise);
+            let mut rows = vec![0u8; pixels.len()];
+            pixels
+                .chunks(WIDTH * 3)
+                .zip(rows.chunks_mut(WIDTH * 3))
+                .for_each(|(src, dst)| quantizer.quantize_row(src, dst));
+
+            assert_eq!(
+                DitherNoise::PerChannel == noise,
+                has_tinted_pixels(&rows),
+                "{dither:?} {noise:?}"
+            );
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn quantizer_matches_buffer_dithering() {
+    use pigment_mixing::Quantizer;
+
+    let pixels: Vec<f32> = (0..WIDTH * HEIGHT * 3)
+        .map(|i| (i % 97) as f32 / 96.0 * 0.1)
+        .collect();
+
+    for dither in NOISE_DITHERS
+        .into_iter()
+        .chain([Dither::FloydSteinberg, Dither::Atkinson])
+    {
+        for noise in [DitherNoise::PerChannel, DitherNoise::Shared] {
+            let amplitude = DitherAmplitude::Triangular(1.0);
+            let mut quantizer = Quantizer::new(WIDTH, dither)
+                .with_noise(noise)
+                .with_amplitude(amplitude);
+            let mut rows = vec![0u8; pixels.len()];
+            pixels
+                .chunks(WIDTH * 3)
+                .zip(rows.chunks_mut(WIDTH * 3))
+                .for_each(|(src, dst)| quantizer.quantize_row(src, dst));
+
+            assert_eq!(
+                dither_u8_with(dither, noise, amplitude, &pixels),
+                rows,
+                "{dither:?} {noise:?}"
+            );
+            assert_eq!(HEIGHT, quantizer.row());
+        }
+    }
+}
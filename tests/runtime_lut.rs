@@ -0,0 +1,63 @@
+//! Loading the table written by the build script at runtime.
+//!
+//! This is a single test as the loaded table is process-wide state.
+#![cfg(feature = "runtime-lut")]
+use pigment_mixing::{is_lut_loaded, load_lut, mix_srgb_u8, Error};
+use std::{sync::mpsc, thread};
+
+const LUT: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/mixbox_lut.bin"));
+/// The bytes the table starts with.
+const MAGIC: &[u8] = b"MIXBXLU\x01";
+/// The number of samples in the table.
+const LUT_LEN: usize = 33 * 33 * 33;
+
+fn mixes() -> Vec<[u8; 3]> {
+    let colors = [[252, 211, 0], [0, 0, 96], [255, 39, 2], [255, 255, 255]];
+
+    colors
+        .iter()
+        .flat_map(|a| colors.iter().map(move |b| mix_srgb_u8(a, b, 0.5)))
+        .collect()
+}
+
+#[test]
+fn load_lut_round_trip() {
+    let embedded = mixes();
+    assert!(!is_lut_loaded());
+
+    // Broken tables are rejected and leave the embedded one in place.
+    assert!(matches!(
+        load_lut(b"not a table"),
+        Err(Error::InvalidFormat(_))
+    ));
+    assert!(load_lut(&LUT[..LUT.len() / 2]).is_err());
+    let mut extended = LUT.to_vec();
+    extended.push(0);
+    assert!(load_lut(&extended).is_err());
+    assert!(!is_lut_loaded());
+
+    // A thread that already mixed with the embedded table picks up a table
+    // loaded later. This one maps every color to pure white.
+    let (mixed, before) = mpsc::channel();
+    let (loaded, wait) = mpsc::channel();
+    let other = thread::spawn(move || {
+        mixed.send(mixes()).unwrap();
+        wait.recv().unwrap();
+        mixes()
+    });
+    assert_eq!(embedded, before.recv().unwrap());
+
+    let mut white = MAGIC.to_vec();
+    white.resize(MAGIC.len() + 3 * LUT_LEN, 0);
+    load_lut(&white).unwrap();
+    assert!(is_lut_loaded());
+    let whitened = mixes();
+    assert_ne!(embedded, whitened);
+
+    loaded.send(()).unwrap();
+    assert_eq!(whitened, other.join().unwrap());
+
+    // The decoded table is the one that was embedded.
+    load_lut(LUT).unwrap();
+    assert_eq!(embedded, mixes());
+}
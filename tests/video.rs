@@ -0,0 +1,152 @@
+//! Mixing of 4:2:0 video frames.
+use pigment_mixing::{mix_yuv420, Yuv420, Yuv420Mut, YuvMatrix};
+
+/// An odd size, so the last chroma row and column cover a single pixel.
+const WIDTH: usize = 5;
+const HEIGHT: usize = 3;
+const LUMA_LEN: usize = WIDTH * HEIGHT;
+const CHROMA_LEN: usize = WIDTH.div_ceil(2) * HEIGHT.div_ceil(2);
+
+/// The planes of an `I420` frame.
+#[derive(Clone, Debug, PartialEq)]
+struct Frame {
+    y: Vec<u8>,
+    u: Vec<u8>,
+    v: Vec<u8>,
+}
+
+impl Frame {
+    fn flat(y: u8, u: u8, v: u8) -> Self {
+        Frame {
+            y: vec![y; LUMA_LEN],
+            u: vec![u; CHROMA_LEN],
+            v: vec![v; CHROMA_LEN],
+        }
+    }
+
+    /// A frame whose samples all differ.
+    fn ramp() -> Self {
+        Frame {
+            y: (0..LUMA_LEN).map(|i| 16 + 13 * i as u8).collect(),
+            u: (0..CHROMA_LEN).map(|i| 60 + 30 * i as u8).collect(),
+            v: (0..CHROMA_LEN).map(|i| 200 - 25 * i as u8).collect(),
+        }
+    }
+
+    fn planar(&self) -> Yuv420<'_> {
+        Yuv420::Planar {
+            y: &self.y,
+            u: &self.u,
+            v: &self.v,
+        }
+    }
+
+    /// Returns the interleaved chroma of an `NV12` frame.
+    fn uv(&self) -> Vec<u8> {
+        self.u
+            .iter()
+            .zip(&self.v)
+            .flat_map(|(&u, &v)| [u, v])
+            .collect()
+    }
+
+    fn mix(a: &Yuv420, b: &Yuv420, matrix: YuvMatrix, ratio: f32) -> Self {
+        let mut out = Frame::flat(0, 0, 0);
+        mix_yuv420(
+            a,
+            b,
+            &mut Yuv420Mut::Planar {
+                y: &mut out.y,
+                u: &mut out.u,
+                v: &mut out.v,
+            },
+            WIDTH,
+            HEIGHT,
+            matrix,
+            ratio,
+        );
+
+        out
+    }
+
+    /// Returns the largest difference of any sample to `other`.
+    fn max_difference(&self, other: &Frame) -> u8 {
+        [
+            (&self.y, &other.y),
+            (&self.u, &other.u),
+            (&self.v, &other.v),
+        ]
+        .into_iter()
+        .flat_map(|(a, b)| a.iter().zip(b).map(|(a, b)| a.abs_diff(*b)))
+        .max()
+        .unwrap_or(0)
+    }
+}
+
+#[test]
+fn mixing_a_gray_frame_with_itself_keeps_it() {
+    let gray = Frame::flat(126, 128, 128);
+
+    for matrix in [YuvMatrix::Bt601, YuvMatrix::Bt709] {
+        for ratio in [0.0, 0.3, 1.0] {
+            let mix = Frame::mix(&gray.planar(), &gray.planar(), matrix, ratio);
+            assert!(gray.max_difference(&mix) <= 1, "{matrix:?} {ratio}");
+        }
+    }
+}
+
+#[test]
+fn mixes_at_the_ends_are_the_frames() {
+    let (a, b) = (Frame::flat(180, 90, 150), Frame::flat(60, 170, 110));
+
+    for matrix in [YuvMatrix::Bt601, YuvMatrix::Bt709] {
+        let start = Frame::mix(&a.planar(), &b.planar(), matrix, 0.0);
+        let end = Frame::mix(&a.planar(), &b.planar(), matrix, 1.0);
+
+        assert!(a.max_difference(&start) <= 1, "{matrix:?}: {start:?}");
+        assert!(b.max_difference(&end) <= 1, "{matrix:?}: {end:?}");
+    }
+}
+
+#[test]
+fn plane_layouts_mix_the_same() {
+    let (a, b) = (Frame::ramp(), Frame::flat(100, 140, 90));
+    let uv = a.uv();
+    let semi_planar = Yuv420::SemiPlanar { y: &a.y, uv: &uv };
+
+    let planar = Frame::mix(&a.planar(), &b.planar(), YuvMatrix::Bt709, 0.5);
+    assert_eq!(
+        planar,
+        Frame::mix(&semi_planar, &b.planar(), YuvMatrix::Bt709, 0.5)
+    );
+
+    let mut out = Frame::flat(0, 0, 0);
+    let mut out_uv = vec![0; 2 * CHROMA_LEN];
+    mix_yuv420(
+        &a.planar(),
+        &b.planar(),
+        &mut Yuv420Mut::SemiPlanar {
+            y: &mut out.y,
+            uv: &mut out_uv,
+        },
+        WIDTH,
+        HEIGHT,
+        YuvMatrix::Bt709,
+        0.5,
+    );
+    assert_eq!(planar.y, out.y);
+    assert_eq!(planar.uv(), out_uv);
+}
+
+#[test]
+#[should_panic(expected = "V plane is too small")]
+fn small_planes_panic() {
+    let a = Frame::flat(126, 128, 128);
+    let b = Yuv420::Planar {
+        y: &a.y,
+        u: &a.u,
+        v: &a.v[1..],
+    };
+
+    Frame::mix(&a.planar(), &b, YuvMatrix::Bt601, 0.5);
+}